@@ -1,5 +1,5 @@
 use crate::{
-    frame::{Arg, AssignedOrReserved, Frame, FrameError},
+    frame::{Arg, AssignedOrReserved, CatchScope, Frame, FrameError},
     DebugInfo, FunctionFlags, Op, StringFormatFlags,
 };
 use koto_parser::{
@@ -8,6 +8,7 @@ use koto_parser::{
     StringContents, StringFormatOptions, StringNode, SwitchArm,
 };
 use smallvec::SmallVec;
+use std::collections::HashMap;
 use thiserror::Error;
 
 /// The different error types that can be thrown by the Koto runtime
@@ -16,6 +17,8 @@ use thiserror::Error;
 enum ErrorKind {
     #[error("attempting to assign to a temporary value")]
     AssigningToATemporaryValue,
+    #[error("'{0}' is a constant and can't be reassigned")]
+    AssigningToAConstant(String),
     #[error("invalid {kind} op ({op:?})")]
     InvalidBinaryOp { kind: String, op: AstBinaryOp },
     #[error("`{0}` used outside of loop")]
@@ -60,6 +63,8 @@ enum ErrorKind {
     OutOfPositionRootNodeInLookup,
     #[error("The compiled bytecode is larger than the maximum size of 4GB (size: {0} bytes)")]
     ResultingBytecodeIsTooLarge(usize),
+    #[error("'{0}' hasn't been declared, new values must be declared with 'let'")]
+    UndeclaredLocal(String),
     #[error("too many targets in assignment ({0})")]
     TooManyAssignmentTargets(usize),
     #[error(
@@ -69,6 +74,8 @@ enum ErrorKind {
     TooManyContainerEntries(usize),
     #[error("The result of this `break` expression will be ignored")]
     UnassignedBreakValue,
+    #[error("'{0}' was declared with 'var' and can't be rebound here")]
+    UnableToRebindVar(String),
     #[error("unexpected Ellipsis")]
     UnexpectedEllipsis,
     #[error("unexpected Wildcard")]
@@ -190,13 +197,36 @@ impl CompileNodeOutput {
 }
 
 /// The settings used by the [Compiler]
-#[derive(Default)]
 pub struct CompilerSettings {
     /// Causes all top level identifiers to be exported
     ///
     /// This is used by the REPL to automatically export values so that they're available between
     /// chunks.
     pub export_top_level_ids: bool,
+    /// Causes type hints to be compiled into runtime type checks
+    ///
+    /// When disabled, type hints are ignored by the compiler, which can be useful in release
+    /// builds where the overhead of the checks isn't wanted.
+    ///
+    /// Enabled by default.
+    pub enable_type_checks: bool,
+    /// Requires new local values to be declared with `let`
+    ///
+    /// When enabled, assigning to an ID that hasn't been declared results in a compilation error,
+    /// which helps to catch typos that would otherwise silently introduce new values.
+    ///
+    /// Disabled by default.
+    pub require_let: bool,
+}
+
+impl Default for CompilerSettings {
+    fn default() -> Self {
+        Self {
+            export_top_level_ids: false,
+            enable_type_checks: true,
+            require_let: false,
+        }
+    }
 }
 
 /// The compiler used by the Koto language
@@ -395,8 +425,38 @@ impl Compiler {
                 }
                 result
             }
+            Node::RangeStep { range, step } => match &ctx.ast.node(*range).node {
+                // Bounded ranges are made along with their step in a single operation,
+                // allowing float bounds to be preserved, e.g. `0.5..1.5..0.25`.
+                Node::Range {
+                    start,
+                    end,
+                    inclusive,
+                } => self.compile_stepped_range(*start, *end, *step, *inclusive, ctx)?,
+                _ => {
+                    let result = self.assign_result_register(ctx)?;
+                    match result.register {
+                        Some(result_register) => {
+                            self.compile_node(*range, ctx.with_fixed_register(result_register))?;
+                            let step_result = self.compile_node(*step, ctx.with_any_register())?;
+
+                            self.push_op(RangeStep, &[result_register, step_result.unwrap(self)?]);
+
+                            if step_result.is_temporary {
+                                self.pop_register()?;
+                            }
+
+                            result
+                        }
+                        None => {
+                            self.compile_node(*range, ctx.compile_for_side_effects())?;
+                            self.compile_node(*step, ctx.compile_for_side_effects())?
+                        }
+                    }
+                }
+            },
             Node::MainBlock { body, local_count } => {
-                self.compile_frame(*local_count as u8, body, &[], &[], true, ctx)?;
+                self.compile_frame(*local_count as u8, body, None, &[], HashMap::new(), ctx)?;
                 CompileNodeOutput::none()
             }
             Node::Block(expressions) => self.compile_block(expressions, ctx)?,
@@ -408,6 +468,9 @@ impl Compiler {
             Node::NamedCall { id, args } => self.compile_named_call(*id, args, None, ctx)?,
             Node::Import { from, items } => self.compile_import(from, items, ctx)?,
             Node::Export(expression) => self.compile_export(*expression, ctx)?,
+            Node::Const(assignment) => self.compile_const(*assignment, false, ctx)?,
+            Node::Let(declaration) => self.compile_let(*declaration, false, ctx)?,
+            Node::Var(declaration) => self.compile_let(*declaration, true, ctx)?,
             Node::Assign { target, expression } => {
                 self.compile_assign(*target, *expression, false, ctx)?
             }
@@ -422,6 +485,12 @@ impl Compiler {
             Node::Switch(arms) => self.compile_switch(arms, ctx)?,
             Node::Ellipsis(_) => return self.error(ErrorKind::UnexpectedEllipsis),
             Node::Wildcard(_) => return self.error(ErrorKind::UnexpectedWildcard),
+            Node::Type(_) => {
+                return self.error(ErrorKind::UnexpectedNode {
+                    expected: "expression".into(),
+                    unexpected: node.node.clone(),
+                })
+            }
             Node::For(ast_for) => self.compile_for(ast_for, ctx)?,
             Node::While { condition, body } => {
                 self.compile_loop(Some((*condition, false)), *body, ctx)?
@@ -430,6 +499,7 @@ impl Compiler {
                 self.compile_loop(Some((*condition, true)), *body, ctx)?
             }
             Node::Loop { body } => self.compile_loop(None, *body, ctx)?,
+            Node::With { resource, body } => self.compile_with(*resource, *body, ctx)?,
             Node::Break(expression) => match self.frame().current_loop() {
                 Some(loop_info) => {
                     let loop_result_register = loop_info.result_register;
@@ -448,6 +518,7 @@ impl Compiler {
                         (None, None) => {}
                     }
 
+                    self.compile_catch_scope_exits(true);
                     self.push_op(Jump, &[]);
                     self.push_loop_jump_placeholder()?;
 
@@ -463,6 +534,7 @@ impl Compiler {
                     if let Some(result_register) = loop_result_register {
                         self.push_op(SetNull, &[result_register]);
                     }
+                    self.compile_catch_scope_exits(true);
                     self.push_jump_back_op(JumpBack, &[], loop_start_ip);
 
                     CompileNodeOutput::none()
@@ -470,15 +542,18 @@ impl Compiler {
                 None => return self.error(ErrorKind::InvalidLoopKeyword("continue".into())),
             },
             Node::Return(None) => {
+                self.compile_catch_scope_exits(false);
                 let result = self.assign_result_register(ctx)?;
                 match result.register {
                     Some(result_register) => {
                         self.push_op(SetNull, &[result_register]);
+                        self.compile_return_type_check(result_register);
                         self.push_op(Return, &[result_register]);
                     }
                     None => {
                         let register = self.push_register()?;
                         self.push_op(SetNull, &[register]);
+                        self.compile_return_type_check(register);
                         self.push_op(Return, &[register]);
                         self.pop_register()?;
                     }
@@ -489,6 +564,9 @@ impl Compiler {
                 let expression_result = self.compile_node(*expression, ctx.with_any_register())?;
                 let expression_register = expression_result.unwrap(self)?;
 
+                self.compile_return_type_check(expression_register);
+                self.compile_catch_scope_exits(false);
+
                 match ctx.result_register {
                     ResultRegister::Any => {
                         self.push_op(Return, &[expression_register]);
@@ -517,6 +595,7 @@ impl Compiler {
                 let expression_result = self.compile_node(*expression, ctx.with_any_register())?;
                 let expression_register = expression_result.unwrap(self)?;
 
+                self.compile_output_type_check(expression_register);
                 self.push_op(Yield, &[expression_register]);
 
                 if let Some(result_register) = result.register {
@@ -529,6 +608,30 @@ impl Compiler {
 
                 result
             }
+            Node::Await(expression) => {
+                let result = self.assign_result_register(ctx)?;
+
+                let expression_result = self.compile_node(*expression, ctx.with_any_register())?;
+                let expression_register = expression_result.unwrap(self)?;
+
+                // The await is always performed, even if the result isn't used
+                match result.register {
+                    Some(result_register) => {
+                        self.push_op(Await, &[result_register, expression_register]);
+                    }
+                    None => {
+                        let temp_register = self.push_register()?;
+                        self.push_op(Await, &[temp_register, expression_register]);
+                        self.pop_register()?;
+                    }
+                }
+
+                if expression_result.is_temporary {
+                    self.pop_register()?;
+                }
+
+                result
+            }
             Node::Throw(expression) => {
                 // A throw will prevent the result from being used, but the caller should be
                 // provided with a result register regardless.
@@ -585,20 +688,110 @@ impl Compiler {
         Ok(result)
     }
 
+    // Compiles the frame of a function, or of the main block when `function` is None
+    fn compile_stepped_range(
+        &mut self,
+        start: AstIndex,
+        end: AstIndex,
+        step: AstIndex,
+        inclusive: bool,
+        ctx: CompileNodeContext,
+    ) -> Result<CompileNodeOutput> {
+        use Op::*;
+
+        let result = self.assign_result_register(ctx)?;
+
+        match result.register {
+            Some(result_register) => {
+                let start_result = self.compile_node(start, ctx.with_any_register())?;
+                let end_result = self.compile_node(end, ctx.with_any_register())?;
+                let step_result = self.compile_node(step, ctx.with_any_register())?;
+
+                let op = if inclusive {
+                    SteppedRangeInclusive
+                } else {
+                    SteppedRange
+                };
+                self.push_op(
+                    op,
+                    &[
+                        result_register,
+                        start_result.unwrap(self)?,
+                        end_result.unwrap(self)?,
+                        step_result.unwrap(self)?,
+                    ],
+                );
+
+                if start_result.is_temporary {
+                    self.pop_register()?;
+                }
+                if end_result.is_temporary {
+                    self.pop_register()?;
+                }
+                if step_result.is_temporary {
+                    self.pop_register()?;
+                }
+
+                Ok(result)
+            }
+            None => {
+                self.compile_node(start, ctx.compile_for_side_effects())?;
+                self.compile_node(end, ctx.compile_for_side_effects())?;
+                self.compile_node(step, ctx.compile_for_side_effects())
+            }
+        }
+    }
+
     fn compile_frame(
         &mut self,
         local_count: u8,
         expressions: &[AstIndex],
-        args: &[AstIndex],
+        function: Option<&Function>,
         captures: &[ConstantIndex],
-        allow_implicit_return: bool,
+        inherited_constants: HashMap<ConstantIndex, AstIndex>,
         ctx: CompileNodeContext,
     ) -> Result<()> {
-        self.frame_stack.push(Frame::new(
-            local_count,
-            &self.collect_args(args, ctx.ast)?,
-            captures,
-        ));
+        let args = function.map_or(&[] as &[AstIndex], |f| f.args.as_slice());
+        let is_generator = function.is_some_and(|f| f.is_generator);
+
+        // Captured `var`s are cells in the parent frame, and they're also cells in the new frame
+        let cell_captures = match self.frame_stack.last() {
+            Some(parent) => captures
+                .iter()
+                .filter(|id| parent.local_is_cell(**id))
+                .copied()
+                .collect(),
+            None => Vec::new(),
+        };
+
+        let mut frame = Frame::new(local_count, &self.collect_args(args, ctx.ast)?, captures);
+        for id in cell_captures {
+            if let Some(register) = frame.get_local_assigned_register(id) {
+                frame.add_cell_register(register);
+            }
+        }
+        frame.is_generator = is_generator;
+        frame.set_inherited_constants(inherited_constants);
+        if self.settings.enable_type_checks {
+            frame.output_type = function
+                .and_then(|f| f.output_type)
+                .map(|output_type| self.type_hint_constant(output_type, ctx.ast))
+                .transpose()?;
+        }
+        self.frame_stack.push(frame);
+
+        // check args that have type hints
+        if let (Some(function), true) = (function, self.settings.enable_type_checks) {
+            for (arg_index, arg_type) in function.arg_types.iter().enumerate() {
+                if let Some(arg_type) = arg_type {
+                    let type_index = self.type_hint_constant(*arg_type, ctx.ast)?;
+                    self.push_span(ctx.ast.node(*arg_type), ctx.ast);
+                    self.push_op(Op::CheckType, &[arg_index as u8 + 1]);
+                    self.push_var_u32(type_index);
+                    self.pop_span();
+                }
+            }
+        }
 
         // unpack nested args
         for (arg_index, arg) in args.iter().enumerate() {
@@ -615,16 +808,18 @@ impl Compiler {
             }
         }
 
-        let result_register = if allow_implicit_return {
-            ResultRegister::Any
-        } else {
+        // Generators yield their output values rather than returning them
+        let result_register = if is_generator {
             ResultRegister::None
+        } else {
+            ResultRegister::Any
         };
 
         let block_result = self.compile_block(expressions, ctx.with_register(result_register))?;
 
         if let Some(block_register) = block_result.register {
             if !self.frame().last_node_was_return {
+                self.compile_return_type_check(block_register);
                 self.push_op_without_span(Op::Return, &[block_register]);
             }
             if block_result.is_temporary {
@@ -633,6 +828,7 @@ impl Compiler {
         } else {
             let register = self.push_register()?;
             self.push_op(Op::SetNull, &[register]);
+            self.compile_return_type_check(register);
             self.push_op_without_span(Op::Return, &[register]);
             self.pop_register()?;
         }
@@ -642,6 +838,33 @@ impl Compiler {
         Ok(())
     }
 
+    // Checks a value that's about to be returned against the frame's output type hint
+    //
+    // Generators yield their output values, so their return values aren't checked.
+    fn compile_return_type_check(&mut self, value_register: u8) {
+        if !self.frame().is_generator {
+            self.compile_output_type_check(value_register);
+        }
+    }
+
+    // Checks an output value against the frame's output type hint, if there is one
+    fn compile_output_type_check(&mut self, value_register: u8) {
+        if let Some(output_type) = self.frame().output_type {
+            self.push_op(Op::CheckType, &[value_register]);
+            self.push_var_u32(output_type);
+        }
+    }
+
+    fn type_hint_constant(&self, type_hint: AstIndex, ast: &Ast) -> Result<ConstantIndex> {
+        match &ast.node(type_hint).node {
+            Node::Type(type_index) => Ok(*type_index),
+            unexpected => self.error(ErrorKind::UnexpectedNode {
+                expected: "type hint".into(),
+                unexpected: unexpected.clone(),
+            }),
+        }
+    }
+
     fn collect_args(&self, args: &[AstIndex], ast: &Ast) -> Result<Vec<Arg>> {
         // Collect args for local assignment in the new frame
         // Top-level args need to match the arguments as they appear in the arg list, with
@@ -726,7 +949,7 @@ impl Compiler {
             match &ctx.ast.node(*arg).node {
                 Node::Wildcard(_) => {}
                 Node::Id(constant_index) => {
-                    let local_register = self.assign_local_register(*constant_index)?;
+                    let local_register = self.assign_local_register(*constant_index, ctx)?;
                     self.push_op(TempIndex, &[local_register, container_register, arg_index]);
                 }
                 Node::Tuple(nested_args) => {
@@ -742,7 +965,7 @@ impl Compiler {
                         // e.g. [first..., x, y]
                         // We want to assign the slice containing all but the last two items to
                         // the given id.
-                        let id_register = self.assign_local_register(*id)?;
+                        let id_register = self.assign_local_register(*id, ctx)?;
                         let to_index = -(args.len() as i8 - 1) as u8;
                         self.push_op(SliceTo, &[id_register, container_register, to_index]);
                     }
@@ -753,7 +976,7 @@ impl Compiler {
                     // e.g. [x, y, z, rest...]
                     // We want to assign the slice containing all but the first three items
                     // to the given id.
-                    let id_register = self.assign_local_register(*id)?;
+                    let id_register = self.assign_local_register(*id, ctx)?;
                     self.push_op(SliceFrom, &[id_register, container_register, arg_index]);
                 }
                 Node::Ellipsis(None) if is_last_arg => {}
@@ -774,6 +997,9 @@ impl Compiler {
     ) -> Result<CompileNodeOutput> {
         use Op::SetNull;
 
+        // Values declared with `let` are only available within the block
+        self.frame_mut().push_block_scope();
+
         let result = match expressions {
             [] => {
                 let result = self.assign_result_register(ctx)?;
@@ -794,6 +1020,8 @@ impl Compiler {
             }
         };
 
+        self.frame_mut().pop_block_scope();
+
         Ok(result)
     }
 
@@ -807,7 +1035,12 @@ impl Compiler {
         ctx: CompileNodeContext,
     ) -> Result<Option<u8>> {
         let result = match &ctx.ast.node(target).node {
-            Node::Id(constant_index) => Some(self.reserve_local_register(*constant_index)?),
+            Node::Id(constant_index) => {
+                self.push_span(ctx.ast.node(target), ctx.ast);
+                let register = self.reserve_local_register(*constant_index, ctx)?;
+                self.pop_span();
+                Some(register)
+            }
             Node::Meta { .. } | Node::Lookup(_) | Node::Wildcard(_) => None,
             unexpected => {
                 return self.error(ErrorKind::UnexpectedNode {
@@ -829,8 +1062,16 @@ impl Compiler {
     ) -> Result<CompileNodeOutput> {
         use Op::*;
 
+        if !export_assignment {
+            self.check_for_undeclared_local(target, ctx)?;
+        }
+
         let local_assign_register = self.local_register_for_assign_target(target, ctx)?;
+        let cell_register =
+            local_assign_register.filter(|register| self.frame().is_cell_register(*register));
         let value_result_register = match local_assign_register {
+            // Values assigned to a `var` are stored in its cell
+            Some(_) if cell_register.is_some() => ResultRegister::Any,
             Some(local) => ResultRegister::Fixed(local),
             None => ResultRegister::Any,
         };
@@ -844,7 +1085,10 @@ impl Compiler {
 
         match &target_node.node {
             Node::Id(id_index) => {
-                if !value_result.is_temporary {
+                if let Some(cell_register) = cell_register {
+                    self.push_op(StoreCell, &[cell_register, value_register]);
+                    self.commit_local_register(cell_register)?;
+                } else if !value_result.is_temporary {
                     // To ensure that exported rhs ids with the same name as a local that's
                     // currently being assigned can be loaded correctly, only commit the
                     // reserved local as assigned after the rhs has been compiled.
@@ -907,6 +1151,10 @@ impl Compiler {
         let result = self.assign_result_register(ctx)?;
         let stack_count = self.stack_count();
 
+        for target in targets.iter() {
+            self.check_for_undeclared_local(*target, ctx)?;
+        }
+
         // Reserve any assignment registers for IDs on the LHS before compiling the RHS
         let target_registers = targets
             .iter()
@@ -941,16 +1189,30 @@ impl Compiler {
         {
             match &ctx.ast.node(*target).node {
                 Node::Id(id_index) => {
-                    let target_register =
+                    let local_register =
                         target_register.expect("Missing target register for assignment");
+                    let is_cell = self.frame().is_cell_register(local_register);
+                    // Values assigned to a `var` are unpacked into a temporary register,
+                    // and then stored in the var's cell.
+                    let target_register = if is_cell {
+                        self.push_register()?
+                    } else {
+                        local_register
+                    };
+
                     if rhs_is_temp_tuple {
                         self.push_op(TempIndex, &[target_register, iter_register, i as u8]);
                     } else {
                         self.push_op(IterUnpack, &[target_register, iter_register]);
                     }
+
+                    if is_cell {
+                        self.push_op(StoreCell, &[local_register, target_register]);
+                    }
+
                     // The register was reserved before the RHS was compiled, and now it
                     // needs to be committed.
-                    self.commit_local_register(target_register)?;
+                    self.commit_local_register(local_register)?;
 
                     // Multi-assignments typically aren't exported, but exporting
                     // assignments might be forced, e.g. in REPL mode.
@@ -961,6 +1223,10 @@ impl Compiler {
                     if result.register.is_some() {
                         self.push_op(SequencePush, &[target_register]);
                     }
+
+                    if is_cell {
+                        self.pop_register()?;
+                    }
                 }
                 Node::Lookup(lookup) => {
                     let value_register = self.push_register()?;
@@ -1026,7 +1292,20 @@ impl Compiler {
         id: ConstantIndex,
         ctx: CompileNodeContext,
     ) -> Result<CompileNodeOutput> {
-        let result = if let Some(local_register) = self.frame().get_local_assigned_register(id) {
+        let result = if let Some(cell_register) = self.frame().get_local_cell_register(id) {
+            match ctx.result_register {
+                ResultRegister::None => CompileNodeOutput::none(),
+                ResultRegister::Any => {
+                    let register = self.push_register()?;
+                    self.push_op(Op::LoadCell, &[register, cell_register]);
+                    CompileNodeOutput::with_temporary(register)
+                }
+                ResultRegister::Fixed(register) => {
+                    self.push_op(Op::LoadCell, &[register, cell_register]);
+                    CompileNodeOutput::with_assigned(register)
+                }
+            }
+        } else if let Some(local_register) = self.frame().get_local_assigned_register(id) {
             match ctx.result_register {
                 ResultRegister::None => CompileNodeOutput::none(),
                 ResultRegister::Any => CompileNodeOutput::with_assigned(local_register),
@@ -1035,6 +1314,8 @@ impl Compiler {
                     CompileNodeOutput::with_assigned(register)
                 }
             }
+        } else if let Some(literal) = self.frame().inherited_constant(id) {
+            self.compile_node(literal, ctx)?
         } else {
             let result = self.assign_result_register(ctx)?;
             if let Some(result_register) = result.register {
@@ -1059,6 +1340,15 @@ impl Compiler {
         Ok(())
     }
 
+    fn compile_const_export(&mut self, id: ConstantIndex, value_register: u8) -> Result<()> {
+        let id_register = self.push_register()?;
+        self.compile_load_string_constant(id_register, id);
+        self.push_op(Op::ConstExport, &[id_register, value_register]);
+        self.pop_register()?;
+        self.frame_mut().add_to_exported_ids(id);
+        Ok(())
+    }
+
     fn compile_meta_export(
         &mut self,
         meta_id: MetaKeyId,
@@ -1087,6 +1377,16 @@ impl Compiler {
         self.compile_constant_op(result_register, id, Op::LoadNonLocal);
     }
 
+    // Loads a value that isn't directly available in a local register,
+    // either from the cell of a `var`, or as a non-local value
+    fn compile_load_cell_or_non_local(&mut self, result_register: u8, id: ConstantIndex) {
+        if let Some(cell_register) = self.frame().get_local_cell_register(id) {
+            self.push_op(Op::LoadCell, &[result_register, cell_register]);
+        } else {
+            self.compile_load_non_local(result_register, id);
+        }
+    }
+
     fn compile_constant_op(&mut self, result_register: u8, id: u32, op: Op) {
         self.push_op(op, &[result_register]);
         self.push_var_u32(id);
@@ -1129,7 +1429,7 @@ impl Compiler {
                     IdOrString::Id(import_id) => {
                         let import_register = if result.register.is_some() {
                             let import_register = if let Some(name) = item.name {
-                                self.assign_local_register(name)?
+                                self.assign_local_register(name, ctx)?
                             } else {
                                 // The result of the import expression is being assigned,
                                 // so import the item into a temporary register.
@@ -1148,7 +1448,7 @@ impl Compiler {
                             // The register must only be reserved for now otherwise it'll show up in
                             // the import search.
                             let local_id = item.name.unwrap_or(*import_id);
-                            let import_register = self.reserve_local_register(local_id)?;
+                            let import_register = self.reserve_local_register(local_id, ctx)?;
                             self.compile_import_item(import_register, &item.item, ctx)?;
 
                             // Commit the register now that the import is complete
@@ -1164,7 +1464,7 @@ impl Compiler {
                     IdOrString::Str(_) => {
                         let import_register = if let Some(name) = item.name {
                             println!("Assigning local register");
-                            self.assign_local_register(name)?
+                            self.assign_local_register(name, ctx)?
                         } else {
                             self.push_register()?
                         };
@@ -1186,14 +1486,14 @@ impl Compiler {
                     IdOrString::Id(import_id) => {
                         let import_register = if let Some(name) = item.name {
                             // 'import as' has been used, so assign a register for the given name
-                            self.assign_local_register(name)?
+                            self.assign_local_register(name, ctx)?
                         } else if result.register.is_some() {
                             // The result of the import is being assigned,
                             // so import the item into a temporary register.
                             self.push_register()?
                         } else {
                             // Assign the leaf item to a local with a matching name.
-                            self.assign_local_register(*import_id)?
+                            self.assign_local_register(*import_id, ctx)?
                         };
 
                         // Access the item from from_register
@@ -1210,7 +1510,7 @@ impl Compiler {
                     }
                     IdOrString::Str(string) => {
                         let import_register = if let Some(name) = item.name {
-                            self.assign_local_register(name)?
+                            self.assign_local_register(name, ctx)?
                         } else {
                             self.push_register()?
                         };
@@ -1262,6 +1562,7 @@ impl Compiler {
                 self.compile_assign(*target, *expression, true, ctx)
             }
             Node::Map(entries) => self.compile_make_map(entries, true, ctx),
+            Node::Const(assignment) => self.compile_const(*assignment, true, ctx),
             unexpected => self.error(ErrorKind::UnexpectedNode {
                 expected: "ID for export".into(),
                 unexpected: unexpected.clone(),
@@ -1269,6 +1570,121 @@ impl Compiler {
         }
     }
 
+    fn compile_const(
+        &mut self,
+        assignment: AstIndex,
+        export_assignment: bool,
+        ctx: CompileNodeContext,
+    ) -> Result<CompileNodeOutput> {
+        let (target, expression, id) = match &ctx.ast.node(assignment).node {
+            Node::Assign { target, expression } => match &ctx.ast.node(*target).node {
+                Node::Id(id) => (*target, *expression, *id),
+                unexpected => {
+                    return self.error(ErrorKind::UnexpectedNode {
+                        expected: "ID for const".into(),
+                        unexpected: unexpected.clone(),
+                    })
+                }
+            },
+            unexpected => {
+                return self.error(ErrorKind::UnexpectedNode {
+                    expected: "assignment for const".into(),
+                    unexpected: unexpected.clone(),
+                })
+            }
+        };
+
+        let result = self.compile_assign(target, expression, false, ctx)?;
+
+        if export_assignment || self.force_export_assignment() {
+            let Some(value_register) = self.frame().get_local_assigned_register(id) else {
+                return self.error(ErrorKind::MissingResultRegister);
+            };
+            self.compile_const_export(id, value_register)?;
+        }
+
+        // Literal values can be inlined when the constant is accessed in nested functions
+        let literal = is_inlinable_literal(&ctx.ast.node(expression).node).then_some(expression);
+        self.frame_mut().add_constant(id, literal);
+
+        Ok(result)
+    }
+
+    // Compiles a `let` or `var` declaration
+    fn compile_let(
+        &mut self,
+        declaration: AstIndex,
+        is_var: bool,
+        ctx: CompileNodeContext,
+    ) -> Result<CompileNodeOutput> {
+        match &ctx.ast.node(declaration).node {
+            Node::Assign { target, expression } => {
+                self.declare_let_target(*target, is_var, ctx)?;
+                self.compile_assign(*target, *expression, false, ctx)
+            }
+            Node::MultiAssign {
+                targets,
+                expression,
+            } => {
+                for target in targets.iter() {
+                    self.declare_let_target(*target, is_var, ctx)?;
+                }
+                self.compile_multi_assign(targets, *expression, ctx)
+            }
+            unexpected => self.error(ErrorKind::UnexpectedNode {
+                expected: "assignment for let".into(),
+                unexpected: unexpected.clone(),
+            }),
+        }
+    }
+
+    fn declare_let_target(
+        &mut self,
+        target: AstIndex,
+        is_var: bool,
+        ctx: CompileNodeContext,
+    ) -> Result<()> {
+        if let Node::Id(id) = &ctx.ast.node(target).node {
+            self.check_for_constant_assignment(*id, ctx)?;
+            let register = self
+                .frame_mut()
+                .declare_local_register(*id)
+                .map_err(|e| self.make_error(e))?;
+
+            if is_var {
+                // The cell is made before the assigned expression is compiled,
+                // allowing functions in the expression to capture it, e.g. `var f = || f()`
+                self.frame_mut().add_cell_register(register);
+                self.push_op(Op::MakeCell, &[register]);
+            }
+        }
+        Ok(())
+    }
+
+    // Checks that assignment targets have been declared when `let` is required
+    fn check_for_undeclared_local(
+        &mut self,
+        target: AstIndex,
+        ctx: CompileNodeContext,
+    ) -> Result<()> {
+        if !self.settings.require_let {
+            return Ok(());
+        }
+
+        let target_node = ctx.ast.node(target);
+        if let Node::Id(id) = &target_node.node {
+            if self.frame().get_local_assigned_or_reserved_register(*id)
+                == AssignedOrReserved::Unassigned
+            {
+                self.push_span(target_node, ctx.ast);
+                let name = ctx.ast.constants().get_str(*id).to_string();
+                return self.error(ErrorKind::UndeclaredLocal(name));
+            }
+        }
+
+        Ok(())
+    }
+
     fn compile_from(
         &mut self,
         result_register: u8,
@@ -1312,7 +1728,10 @@ impl Compiler {
 
         match item {
             IdOrString::Id(id) => {
-                if let Some(local_register) = self.frame().get_local_assigned_register(*id) {
+                if let Some(cell_register) = self.frame().get_local_cell_register(*id) {
+                    self.push_op(LoadCell, &[result_register, cell_register]);
+                    Ok(())
+                } else if let Some(local_register) = self.frame().get_local_assigned_register(*id) {
                     // The item to be imported is already locally assigned.
                     // It might be better for this to be reported as an error?
                     if local_register != result_register {
@@ -1353,7 +1772,7 @@ impl Compiler {
         // The argument register for the catch block needs to be assigned now
         // so that it can be included in the TryStart op.
         let (catch_register, pop_catch_register) = match &ctx.ast.node(*catch_arg).node {
-            Node::Id(id) => (self.assign_local_register(*id)?, false),
+            Node::Id(id) => (self.assign_local_register(*id, ctx)?, false),
             Node::Wildcard(_) => {
                 // The catch argument is being ignored, so just use a dummy register
                 (self.push_register()?, true)
@@ -1377,7 +1796,9 @@ impl Compiler {
             _ => ResultRegister::None,
         };
 
+        self.frame_mut().push_catch_scope(CatchScope::Try);
         self.compile_node(*try_block, ctx.with_register(try_result_register))?;
+        self.frame_mut().pop_catch_scope();
 
         // Clear the catch point at the end of the try block
         // - if the end of the try block has been reached then the catch block is no longer needed.
@@ -1415,6 +1836,66 @@ impl Compiler {
         }
     }
 
+    fn compile_with(
+        &mut self,
+        resource: AstIndex,
+        body: AstIndex,
+        ctx: CompileNodeContext,
+    ) -> Result<CompileNodeOutput> {
+        use Op::*;
+
+        let result = self.assign_result_register(ctx)?;
+
+        let resource_result = self.compile_node(resource, ctx.with_any_register())?;
+        let resource_register = resource_result.unwrap(self)?;
+
+        // The body is compiled as a try block, with the catch block closing the resource and then
+        // rethrowing the error.
+        let error_register = self.push_register()?;
+        self.push_op(TryStart, &[error_register]);
+        let catch_offset = self.push_offset_placeholder();
+
+        let body_result_register = match result.register {
+            Some(result_register) => ResultRegister::Fixed(result_register),
+            None => ResultRegister::None,
+        };
+
+        self.frame_mut()
+            .push_catch_scope(CatchScope::With(resource_register));
+        self.compile_node(body, ctx.with_register(body_result_register))?;
+        self.frame_mut().pop_catch_scope();
+
+        self.push_op_without_span(TryEnd, &[]);
+        self.push_op_without_span(Close, &[resource_register]);
+        self.push_op_without_span(Jump, &[]);
+        let end_offset = self.push_offset_placeholder();
+
+        self.update_offset_placeholder(catch_offset)?;
+        self.push_op(TryEnd, &[]);
+        self.push_op(Close, &[resource_register]);
+        self.push_op(Throw, &[error_register]);
+
+        self.update_offset_placeholder(end_offset)?;
+
+        self.pop_register()?; // error_register
+        if resource_result.is_temporary {
+            self.pop_register()?;
+        }
+
+        Ok(result)
+    }
+
+    // Clears the catch points of any `try` or `with` blocks that are being exited early,
+    // e.g. by a `return` or `break` expression, closing the resources of `with` blocks.
+    fn compile_catch_scope_exits(&mut self, loop_exit: bool) {
+        for scope in self.frame().exited_catch_scopes(loop_exit) {
+            self.push_op(Op::TryEnd, &[]);
+            if let CatchScope::With(resource_register) = scope {
+                self.push_op(Op::Close, &[resource_register]);
+            }
+        }
+    }
+
     fn compile_unary_op(
         &mut self,
         op: AstUnaryOp,
@@ -1461,7 +1942,7 @@ impl Compiler {
             Less | LessOrEqual | Greater | GreaterOrEqual | Equal | NotEqual => {
                 self.compile_comparison_op(op, lhs, rhs, ctx)
             }
-            And | Or => self.compile_logic_op(op, lhs, rhs, ctx),
+            And | Or | NullCoalesce => self.compile_logic_op(op, lhs, rhs, ctx),
             Pipe => self.compile_piped_call(lhs, rhs, ctx),
         }
     }
@@ -1542,6 +2023,10 @@ impl Compiler {
         let rhs_register = rhs.unwrap(self)?;
 
         let lhs_node = &ctx.ast.node(lhs).node;
+        if let Node::Id(id) = lhs_node {
+            self.check_for_constant_assignment(*id, ctx)?;
+        }
+
         let result = if let Node::Lookup(lookup_node) = lhs_node {
             self.compile_lookup(lookup_node, None, Some(rhs_register), Some(op), ctx)?
         } else {
@@ -1550,6 +2035,13 @@ impl Compiler {
 
             self.push_op(op, &[lhs_register, rhs_register]);
 
+            // If the LHS is a `var` then the result needs to be stored in its cell
+            if let Node::Id(id) = lhs_node {
+                if let Some(cell_register) = self.frame().get_local_cell_register(*id) {
+                    self.push_op(Op::StoreCell, &[cell_register, lhs_register]);
+                }
+            }
+
             // If the LHS is a top-level ID and the export flag is enabled, then export the result
             if let Node::Id(id) = lhs_node {
                 if self.settings.export_top_level_ids && self.frame_stack.len() == 1 {
@@ -1692,6 +2184,7 @@ impl Compiler {
         let jump_op = match op {
             AstBinaryOp::And => Op::JumpIfFalse,
             AstBinaryOp::Or => Op::JumpIfTrue,
+            AstBinaryOp::NullCoalesce => Op::JumpIfNotNull,
             _ => unreachable!(),
         };
 
@@ -1804,6 +2297,9 @@ impl Compiler {
                                         if let Some(fill_constant) = format.fill_character {
                                             self.push_var_u32(fill_constant);
                                         }
+                                        if let Some(representation) = format.representation {
+                                            self.push_bytes(&[representation.as_byte()]);
+                                        }
 
                                         if expression_result.is_temporary {
                                             self.pop_register()?;
@@ -1955,10 +2451,12 @@ impl Compiler {
                     }
                     // ID-only entry, the value should be locally assigned
                     (MapKey::Id(id), None) => match self.frame().get_local_assigned_register(*id) {
-                        Some(register) => CompileNodeOutput::with_assigned(register),
-                        None => {
+                        Some(register) if !self.frame().is_cell_register(register) => {
+                            CompileNodeOutput::with_assigned(register)
+                        }
+                        _ => {
                             let register = self.push_register()?;
-                            self.compile_load_non_local(register, *id);
+                            self.compile_load_cell_or_non_local(register, *id);
                             CompileNodeOutput::with_temporary(register)
                         }
                     },
@@ -2005,9 +2503,14 @@ impl Compiler {
                 }
             };
 
+            // Literal constants are inlined in the nested frame rather than being captured
+            let inherited_constants = self.frame().constants_for_nested_frame();
             let captures = self
                 .frame()
-                .captures_for_nested_frame(&function.accessed_non_locals);
+                .captures_for_nested_frame(&function.accessed_non_locals)
+                .into_iter()
+                .filter(|id| !inherited_constants.contains_key(id))
+                .collect::<Vec<_>>();
             if captures.len() > u8::MAX as usize {
                 return self.error(ErrorKind::FunctionPropertyLimit {
                     property: "captures".into(),
@@ -2044,7 +2547,6 @@ impl Compiler {
                 }
             };
 
-            let allow_implicit_return = !function.is_generator;
             let body_as_slice = [function.body];
             let function_body = match &ctx.ast.node(function.body).node {
                 Node::Block(expressions) => expressions.as_slice(),
@@ -2053,9 +2555,9 @@ impl Compiler {
             self.compile_frame(
                 local_count,
                 function_body,
-                &function.args,
+                Some(function),
                 &captures,
-                allow_implicit_return,
+                inherited_constants,
                 ctx,
             )?;
 
@@ -2143,6 +2645,10 @@ impl Compiler {
             _ => Some(self.push_register()?),
         };
 
+        // Jump placeholders for any null checks in the chain (e.g. `x?.foo`),
+        // which skip to the end of the lookup when a null value is encountered.
+        let mut null_check_jumps = SmallVec::<[usize; 2]>::new();
+
         let mut lookup_node = root_node.clone();
 
         while next_node_index.is_some() {
@@ -2234,6 +2740,20 @@ impl Compiler {
                         ctx.with_fixed_register(call_result_register),
                     )?;
                 }
+                LookupNode::NullCheck => {
+                    // Optional chaining
+                    // e.g. x?.foo
+                    //    - x = Root
+                    //    - ? = NullCheck
+                    //    - foo = Id
+
+                    let Some(&value_register) = node_registers.last() else {
+                        return self.error(ErrorKind::OutOfPositionChildNodeInLookup);
+                    };
+
+                    self.push_op(JumpIfNull, &[value_register]);
+                    null_check_jumps.push(self.push_offset_placeholder());
+                }
             }
 
             // Is the lookup chain complete?
@@ -2411,6 +2931,27 @@ impl Compiler {
             )?;
         }
 
+        // If there were null checks in the chain, then the jumps land here.
+        // Assignments are skipped, otherwise the result of the lookup is null.
+        if !null_check_jumps.is_empty() {
+            match result.register {
+                Some(result_register) if rhs.is_none() => {
+                    self.push_op(Jump, &[]);
+                    let skip_null_ip = self.push_offset_placeholder();
+                    for jump_ip in null_check_jumps {
+                        self.update_offset_placeholder(jump_ip)?;
+                    }
+                    self.push_op(SetNull, &[result_register]);
+                    self.update_offset_placeholder(skip_null_ip)?;
+                }
+                _ => {
+                    for jump_ip in null_check_jumps {
+                        self.update_offset_placeholder(jump_ip)?;
+                    }
+                }
+            }
+        }
+
         self.span_stack.truncate(span_stack_count);
         self.truncate_register_stack(stack_count)?;
 
@@ -2596,7 +3137,12 @@ impl Compiler {
         piped_arg: Option<u8>,
         ctx: CompileNodeContext,
     ) -> Result<CompileNodeOutput> {
-        if let Some(function_register) = self.frame().get_local_assigned_register(function_id) {
+        let local_register = self
+            .frame()
+            .get_local_assigned_register(function_id)
+            .filter(|register| !self.frame().is_cell_register(*register));
+
+        if let Some(function_register) = local_register {
             self.compile_call(function_register, args, piped_arg, None, ctx)
         } else {
             let result = self.assign_result_register(ctx)?;
@@ -2607,7 +3153,7 @@ impl Compiler {
             };
 
             let function_register = self.push_register()?;
-            self.compile_load_non_local(function_register, function_id);
+            self.compile_load_cell_or_non_local(function_register, function_id);
 
             self.compile_call(
                 function_register,
@@ -3062,7 +3608,7 @@ impl Compiler {
                     self.pop_register()?; // pattern_register
                 }
                 Node::Id(id) => {
-                    let id_register = self.assign_local_register(*id)?;
+                    let id_register = self.assign_local_register(*id, ctx)?;
                     if match_is_container {
                         self.push_op(
                             TempIndex,
@@ -3108,7 +3654,7 @@ impl Compiler {
                             // e.g. [x, y, z, rest...]
                             // We want to assign the slice containing all but the first three items
                             // to the given id.
-                            let id_register = self.assign_local_register(*id)?;
+                            let id_register = self.assign_local_register(*id, ctx)?;
                             self.push_op(
                                 SliceFrom,
                                 &[id_register, params.match_register, pattern_index as u8],
@@ -3128,7 +3674,7 @@ impl Compiler {
                             // e.g. [first..., x, y]
                             // We want to assign the slice containing all but the last two items to
                             // the given id.
-                            let id_register = self.assign_local_register(*id)?;
+                            let id_register = self.assign_local_register(*id, ctx)?;
                             let to_index = -(arm_patterns.len() as i8 - 1) as u8;
                             self.push_op(SliceTo, &[id_register, params.match_register, to_index]);
                         }
@@ -3297,7 +3843,7 @@ impl Compiler {
                 match &ctx.ast.node(*single_arg).node {
                     Node::Id(id) => {
                         // e.g. for i in 0..10
-                        let arg_register = self.assign_local_register(*id)?;
+                        let arg_register = self.assign_local_register(*id, ctx)?;
                         self.push_op_without_span(IterNext, &[arg_register, iterator_register]);
                         self.push_loop_jump_placeholder()?;
                     }
@@ -3330,7 +3876,7 @@ impl Compiler {
                 for arg in args.iter() {
                     match &ctx.ast.node(*arg).node {
                         Node::Id(id) => {
-                            let arg_register = self.assign_local_register(*id)?;
+                            let arg_register = self.assign_local_register(*id, ctx)?;
                             self.push_op_without_span(IterUnpack, &[arg_register, temp_register]);
                         }
                         Node::Wildcard(_) => {
@@ -3544,18 +4090,50 @@ impl Compiler {
             .map_err(|e| self.make_error(e))
     }
 
-    fn assign_local_register(&mut self, local: ConstantIndex) -> Result<u8> {
-        self.frame_mut()
+    fn assign_local_register(
+        &mut self,
+        local: ConstantIndex,
+        ctx: CompileNodeContext,
+    ) -> Result<u8> {
+        self.check_for_constant_assignment(local, ctx)?;
+        let register = self
+            .frame_mut()
             .assign_local_register(local)
-            .map_err(|e| self.make_error(e))
+            .map_err(|e| self.make_error(e))?;
+
+        // Values declared with `var` can only be reassigned with `=` or compound assignment
+        if self.frame().is_cell_register(register) {
+            let name = ctx.ast.constants().get_str(local).to_string();
+            return self.error(ErrorKind::UnableToRebindVar(name));
+        }
+
+        Ok(register)
     }
 
-    fn reserve_local_register(&mut self, local: ConstantIndex) -> Result<u8> {
+    fn reserve_local_register(
+        &mut self,
+        local: ConstantIndex,
+        ctx: CompileNodeContext,
+    ) -> Result<u8> {
+        self.check_for_constant_assignment(local, ctx)?;
         self.frame_mut()
             .reserve_local_register(local)
             .map_err(|e| self.make_error(e))
     }
 
+    fn check_for_constant_assignment(
+        &self,
+        local: ConstantIndex,
+        ctx: CompileNodeContext,
+    ) -> Result<()> {
+        if self.frame().is_constant(local) {
+            let name = ctx.ast.constants().get_str(local).to_string();
+            self.error(ErrorKind::AssigningToAConstant(name))
+        } else {
+            Ok(())
+        }
+    }
+
     fn commit_local_register(&mut self, register: u8) -> Result<u8> {
         for deferred_op in self
             .frame_mut()
@@ -3603,6 +4181,23 @@ fn args_size_op(args: &[AstIndex], ast: &Ast) -> (Op, usize) {
     }
 }
 
+// Returns true if the node is a literal value that can be inlined in place of a constant
+fn is_inlinable_literal(node: &Node) -> bool {
+    match node {
+        Node::Null
+        | Node::BoolTrue
+        | Node::BoolFalse
+        | Node::SmallInt(_)
+        | Node::Int(_)
+        | Node::Float(_) => true,
+        Node::Str(string) => matches!(
+            string.contents,
+            StringContents::Literal(_) | StringContents::Raw { .. }
+        ),
+        _ => false,
+    }
+}
+
 #[derive(Default)]
 struct MatchJumpPlaceholders {
     // Jumps to the end of the arm
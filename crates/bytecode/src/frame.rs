@@ -1,6 +1,6 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
-use koto_parser::{ConstantIndex, Span};
+use koto_parser::{AstIndex, ConstantIndex, Span};
 use thiserror::Error;
 
 /// The different error types that can be thrown while compiling a [Frame]
@@ -66,12 +66,34 @@ pub(crate) enum Arg {
     Placeholder,
 }
 
+// A block that registers a catch point while it's active
+#[derive(Clone, Copy, Debug)]
+pub(crate) enum CatchScope {
+    // A `try` block
+    Try,
+    // A `with` block, containing the register of the block's resource
+    With(u8),
+}
+
 #[derive(Clone, Debug, Default)]
 pub(crate) struct Frame {
     loop_stack: Vec<Loop>,
     register_stack: Vec<u8>,
     local_registers: Vec<LocalRegister>,
     exported_ids: HashSet<ConstantIndex>,
+    // Ids that have been declared as constants in the frame,
+    // along with the constant's literal value if it can be inlined in nested frames.
+    constants: HashMap<ConstantIndex, Option<AstIndex>>,
+    // Literal constants declared in parent frames, which are inlined rather than captured
+    inherited_constants: HashMap<ConstantIndex, AstIndex>,
+    // The registers of `let` declarations made in each of the currently active block scopes
+    block_scopes: Vec<Vec<u8>>,
+    // Local registers that contain cells for values declared with `var`,
+    // including captured cells from parent frames.
+    cell_registers: HashSet<u8>,
+    // The currently active `try` and `with` blocks, in the order in which they were entered,
+    // along with the size of the loop stack when each block was entered.
+    catch_scopes: Vec<(CatchScope, usize)>,
     temporary_base: u8,
     temporary_count: u8,
     // Used to decide if an additional return instruction is needed,
@@ -80,6 +102,11 @@ pub(crate) struct Frame {
     // This is a coarse check, e.g. we currently don't check if the last expression
     // returns in all branches, but it'll do for now as an optimization for simple cases.
     pub last_node_was_return: bool,
+    // The output type hint of the frame's function, used when type checks are enabled
+    pub output_type: Option<ConstantIndex>,
+    // Generators have their yielded values checked against the output type rather than the
+    // value that's returned at the end of the function.
+    pub is_generator: bool,
 }
 
 impl Frame {
@@ -137,10 +164,12 @@ impl Frame {
         }
     }
 
+    // Locals are searched in reverse order so that the most recent `let` declaration of an id
+    // takes precedence over earlier declarations that it shadows.
     pub fn get_local_assigned_register(&self, local_name: ConstantIndex) -> Option<u8> {
         self.local_registers
             .iter()
-            .position(|local_register| {
+            .rposition(|local_register| {
                 matches!(local_register,
                     LocalRegister::Assigned(assigned) if *assigned == local_name
                 )
@@ -152,7 +181,7 @@ impl Frame {
         &self,
         local_name: ConstantIndex,
     ) -> AssignedOrReserved {
-        for (i, local_register) in self.local_registers.iter().enumerate() {
+        for (i, local_register) in self.local_registers.iter().enumerate().rev() {
             match local_register {
                 LocalRegister::Assigned(assigned) if *assigned == local_name => {
                     return AssignedOrReserved::Assigned(i as u8);
@@ -185,10 +214,115 @@ impl Frame {
         }
     }
 
+    // Reserves a new local register for a `let` declaration
+    //
+    // Unlike with `reserve_local_register`, a new register is always used, allowing the
+    // declaration to shadow an existing local. If a block scope is active then the declaration
+    // is removed when the scope ends.
+    pub fn declare_local_register(&mut self, local: ConstantIndex) -> Result<u8, FrameError> {
+        self.local_registers
+            .push(LocalRegister::Reserved(local, vec![]));
+
+        let new_local_register = self.local_registers.len() - 1;
+
+        if new_local_register < self.temporary_base as usize {
+            let new_local_register = new_local_register as u8;
+            if let Some(scope) = self.block_scopes.last_mut() {
+                scope.push(new_local_register);
+            }
+            Ok(new_local_register)
+        } else {
+            Err(FrameError::LocalRegisterOverflow)
+        }
+    }
+
+    // Marks a local register as containing a cell
+    pub fn add_cell_register(&mut self, register: u8) {
+        self.cell_registers.insert(register);
+    }
+
+    pub fn is_cell_register(&self, register: u8) -> bool {
+        self.cell_registers.contains(&register)
+    }
+
+    // Returns true if the id's assigned or reserved local register contains a cell
+    pub fn local_is_cell(&self, local_name: ConstantIndex) -> bool {
+        match self.get_local_assigned_or_reserved_register(local_name) {
+            AssignedOrReserved::Assigned(register) | AssignedOrReserved::Reserved(register) => {
+                self.is_cell_register(register)
+            }
+            AssignedOrReserved::Unassigned => false,
+        }
+    }
+
+    // Returns the id's assigned local register if it contains a cell
+    pub fn get_local_cell_register(&self, local_name: ConstantIndex) -> Option<u8> {
+        self.get_local_assigned_register(local_name)
+            .filter(|register| self.is_cell_register(*register))
+    }
+
+    pub fn push_block_scope(&mut self) {
+        self.block_scopes.push(Vec::new());
+    }
+
+    // Ends a block scope, removing any locals that were declared in the block
+    //
+    // The registers themselves aren't reused, so values that are produced by the block remain
+    // valid after the scope has ended.
+    pub fn pop_block_scope(&mut self) {
+        if let Some(scope) = self.block_scopes.pop() {
+            for register in scope {
+                self.local_registers[register as usize] = LocalRegister::Allocated;
+            }
+        }
+    }
+
     pub fn add_to_exported_ids(&mut self, id: ConstantIndex) {
         self.exported_ids.insert(id);
     }
 
+    pub fn add_constant(&mut self, id: ConstantIndex, literal: Option<AstIndex>) {
+        self.constants.insert(id, literal);
+    }
+
+    pub fn is_constant(&self, id: ConstantIndex) -> bool {
+        self.constants.contains_key(&id)
+    }
+
+    pub fn set_inherited_constants(&mut self, constants: HashMap<ConstantIndex, AstIndex>) {
+        self.inherited_constants = constants;
+    }
+
+    // Returns the literal value of a constant from a parent frame that isn't shadowed by a local
+    pub fn inherited_constant(&self, id: ConstantIndex) -> Option<AstIndex> {
+        if self.is_local(id) {
+            None
+        } else {
+            self.inherited_constants.get(&id).copied()
+        }
+    }
+
+    // Returns the literal constants that should be inlined in a nested frame
+    pub fn constants_for_nested_frame(&self) -> HashMap<ConstantIndex, AstIndex> {
+        self.inherited_constants
+            .iter()
+            .filter(|(id, _)| !self.is_local(**id))
+            .map(|(id, literal)| (*id, *literal))
+            .chain(
+                self.constants
+                    .iter()
+                    .filter_map(|(id, literal)| literal.map(|literal| (*id, literal))),
+            )
+            .collect()
+    }
+
+    fn is_local(&self, id: ConstantIndex) -> bool {
+        !matches!(
+            self.get_local_assigned_or_reserved_register(id),
+            AssignedOrReserved::Unassigned
+        )
+    }
+
     pub fn defer_op_until_register_is_committed(
         &mut self,
         reserved_register: u8,
@@ -331,4 +465,39 @@ impl Frame {
     pub fn pop_loop(&mut self) -> Result<Loop, FrameError> {
         self.loop_stack.pop().ok_or(FrameError::EmptyLoopInfoStack)
     }
+
+    pub fn push_catch_scope(&mut self, scope: CatchScope) {
+        self.catch_scopes.push((scope, self.loop_stack.len()));
+    }
+
+    pub fn pop_catch_scope(&mut self) {
+        self.catch_scopes.pop();
+    }
+
+    // Returns the `try` and `with` blocks that will be exited early,
+    // in the order in which their catch points should be cleared.
+    //
+    // If `loop_exit` is true then only the blocks inside the current loop are included.
+    // Otherwise the frame is being exited (e.g. for a `return`), and the frame's catch points
+    // will be discarded, so only the blocks up to and including the outermost `with` block
+    // are included, leaving any outer `try` blocks in place while the resources are closed.
+    pub fn exited_catch_scopes(&self, loop_exit: bool) -> Vec<CatchScope> {
+        let mut result: Vec<CatchScope> = self
+            .catch_scopes
+            .iter()
+            .rev()
+            .take_while(|(_, loop_depth)| !loop_exit || *loop_depth >= self.loop_stack.len())
+            .map(|(scope, _)| *scope)
+            .collect();
+
+        if !loop_exit {
+            let with_count = result
+                .iter()
+                .rposition(|scope| matches!(scope, CatchScope::With(_)))
+                .map_or(0, |position| position + 1);
+            result.truncate(with_count);
+        }
+
+        result
+    }
 }
@@ -46,6 +46,10 @@ pub enum Instruction {
         name: u8,
         value: u8,
     },
+    ConstExport {
+        name: u8,
+        value: u8,
+    },
     Import {
         register: u8,
     },
@@ -103,6 +107,17 @@ pub enum Instruction {
     RangeFull {
         register: u8,
     },
+    RangeStep {
+        register: u8,
+        step: u8,
+    },
+    SteppedRange {
+        register: u8,
+        start: u8,
+        end: u8,
+        step: u8,
+        inclusive: bool,
+    },
     MakeIterator {
         register: u8,
         iterable: u8,
@@ -218,6 +233,14 @@ pub enum Instruction {
         register: u8,
         offset: u16,
     },
+    JumpIfNull {
+        register: u8,
+        offset: u16,
+    },
+    JumpIfNotNull {
+        register: u8,
+        offset: u16,
+    },
     Call {
         result: u8,
         function: u8,
@@ -230,6 +253,24 @@ pub enum Instruction {
     Yield {
         register: u8,
     },
+    Await {
+        register: u8,
+        value: u8,
+    },
+    Close {
+        register: u8,
+    },
+    MakeCell {
+        register: u8,
+    },
+    LoadCell {
+        register: u8,
+        cell: u8,
+    },
+    StoreCell {
+        cell: u8,
+        value: u8,
+    },
     Throw {
         register: u8,
     },
@@ -320,6 +361,10 @@ pub enum Instruction {
         register: u8,
         size: usize,
     },
+    CheckType {
+        register: u8,
+        type_constant: ConstantIndex,
+    },
     StringStart {
         size_hint: u32,
     },
@@ -385,6 +430,12 @@ pub struct StringFormatFlags {
     pub precision: bool,
     /// True if a fill character is specified
     pub fill_character: bool,
+    /// True if a `+` sign should be shown for positive numbers
+    pub show_sign: bool,
+    /// True if numbers should be formatted with thousands separators
+    pub thousands_separator: bool,
+    /// True if a representation is specified
+    pub representation: bool,
 }
 
 impl StringFormatFlags {
@@ -394,6 +445,12 @@ impl StringFormatFlags {
     pub const PRECISION: u8 = 1 << 3;
     /// Set to true when fill_character is defined
     pub const FILL_CHARACTER: u8 = 1 << 4;
+    /// Set to true when show_sign is enabled
+    pub const SHOW_SIGN: u8 = 1 << 5;
+    /// Set to true when thousands_separator is enabled
+    pub const THOUSANDS_SEPARATOR: u8 = 1 << 6;
+    /// Set to true when representation is defined
+    pub const REPRESENTATION: u8 = 1 << 7;
 
     /// Decodes a byte into format flags
     pub fn from_byte(byte: u8) -> Self {
@@ -413,6 +470,9 @@ impl StringFormatFlags {
             min_width: byte & Self::MIN_WIDTH != 0,
             precision: byte & Self::PRECISION != 0,
             fill_character: byte & Self::FILL_CHARACTER != 0,
+            show_sign: byte & Self::SHOW_SIGN != 0,
+            thousands_separator: byte & Self::THOUSANDS_SEPARATOR != 0,
+            representation: byte & Self::REPRESENTATION != 0,
         }
     }
 
@@ -429,6 +489,15 @@ impl StringFormatFlags {
         if self.fill_character {
             result |= Self::FILL_CHARACTER;
         }
+        if self.show_sign {
+            result |= Self::SHOW_SIGN;
+        }
+        if self.thousands_separator {
+            result |= Self::THOUSANDS_SEPARATOR;
+        }
+        if self.representation {
+            result |= Self::REPRESENTATION;
+        }
 
         result
     }
@@ -441,6 +510,9 @@ impl From<StringFormatOptions> for StringFormatFlags {
             min_width: options.min_width.is_some(),
             precision: options.precision.is_some(),
             fill_character: options.fill_character.is_some(),
+            show_sign: options.show_sign,
+            thousands_separator: options.thousands_separator,
+            representation: options.representation.is_some(),
         }
     }
 }
@@ -473,6 +545,9 @@ impl fmt::Debug for Instruction {
             ValueExport { name, value } => {
                 write!(f, "ValueExport\tname: {name}\t\tvalue: {value}")
             }
+            ConstExport { name, value } => {
+                write!(f, "ConstExport\tname: {name}\t\tvalue: {value}")
+            }
             Import { register } => write!(f, "Import\t\tregister: {register}"),
             MakeTempTuple {
                 register,
@@ -519,6 +594,20 @@ impl fmt::Debug for Instruction {
                 write!(f, "RangeFrom\tresult: {register}\tstart: {start}")
             }
             RangeFull { register } => write!(f, "RangeFull\tresult: {register}"),
+            RangeStep { register, step } => {
+                write!(f, "RangeStep\tresult: {register}\tstep: {step}")
+            }
+            SteppedRange {
+                register,
+                start,
+                end,
+                step,
+                inclusive,
+            } => write!(
+                f,
+                "SteppedRange\tresult: {register}\tstart: {start}\tend: {end}\tstep: {step}\
+                 \tinclusive: {inclusive}"
+            ),
             MakeIterator { register, iterable } => {
                 write!(f, "MakeIterator\tresult: {register}\titerable: {iterable}",)
             }
@@ -609,6 +698,12 @@ impl fmt::Debug for Instruction {
             JumpIfFalse { register, offset } => {
                 write!(f, "JumpIfFalse\tresult: {register}\toffset: {offset}")
             }
+            JumpIfNull { register, offset } => {
+                write!(f, "JumpIfNull\tresult: {register}\toffset: {offset}")
+            }
+            JumpIfNotNull { register, offset } => {
+                write!(f, "JumpIfNotNull\tresult: {register}\toffset: {offset}")
+            }
             Call {
                 result,
                 function,
@@ -621,6 +716,15 @@ impl fmt::Debug for Instruction {
             ),
             Return { register } => write!(f, "Return\t\tresult: {register}"),
             Yield { register } => write!(f, "Yield\t\tresult: {register}"),
+            Await { register, value } => {
+                write!(f, "Await\t\tresult: {register}\tvalue: {value}")
+            }
+            Close { register } => write!(f, "Close\t\tregister: {register}"),
+            MakeCell { register } => write!(f, "MakeCell\tregister: {register}"),
+            LoadCell { register, cell } => {
+                write!(f, "LoadCell\tresult: {register}\tcell: {cell}")
+            }
+            StoreCell { cell, value } => write!(f, "StoreCell\tcell: {cell}\tvalue: {value}"),
             Throw { register } => write!(f, "Throw\t\tresult: {register}"),
             Size { register, value } => write!(f, "Size\t\tresult: {register}\tvalue: {value}"),
             IterNext {
@@ -737,6 +841,12 @@ impl fmt::Debug for Instruction {
             CheckSizeMin { register, size } => {
                 write!(f, "CheckSizeMin\tvalue: {register}\tsize: {size}")
             }
+            CheckType {
+                register,
+                type_constant,
+            } => {
+                write!(f, "CheckType\tvalue: {register}\ttype: {type_constant}")
+            }
             StringStart { size_hint } => {
                 write!(f, "StringStart\tsize hint: {size_hint}")
             }
@@ -756,6 +866,15 @@ impl fmt::Debug for Instruction {
                     if let Some(fill_character) = opts.fill_character {
                         write!(f, "\tfill_character: {fill_character}")?;
                     }
+                    if opts.show_sign {
+                        write!(f, "\tshow_sign")?;
+                    }
+                    if opts.thousands_separator {
+                        write!(f, "\tthousands_separator")?;
+                    }
+                    if let Some(representation) = opts.representation {
+                        write!(f, "\trepresentation: {representation:?}")?;
+                    }
                 }
                 Ok(())
             }
@@ -1,6 +1,6 @@
 use crate::{Chunk, FunctionFlags, Instruction, Op, StringFormatFlags};
 use koto_memory::Ptr;
-use koto_parser::StringFormatOptions;
+use koto_parser::{StringFormatOptions, StringFormatRepresentation};
 
 /// An iterator that converts bytecode into a series of [Instruction]s
 #[derive(Clone, Default)]
@@ -142,6 +142,10 @@ impl Iterator for InstructionReader {
                 name: get_u8!(),
                 value: get_u8!(),
             }),
+            Op::ConstExport => Some(ConstExport {
+                name: get_u8!(),
+                value: get_u8!(),
+            }),
             Op::Import => Some(Import {
                 register: get_u8!(),
             }),
@@ -197,6 +201,24 @@ impl Iterator for InstructionReader {
             Op::RangeFull => Some(RangeFull {
                 register: get_u8!(),
             }),
+            Op::RangeStep => Some(RangeStep {
+                register: get_u8!(),
+                step: get_u8!(),
+            }),
+            Op::SteppedRange => Some(SteppedRange {
+                register: get_u8!(),
+                start: get_u8!(),
+                end: get_u8!(),
+                step: get_u8!(),
+                inclusive: false,
+            }),
+            Op::SteppedRangeInclusive => Some(SteppedRange {
+                register: get_u8!(),
+                start: get_u8!(),
+                end: get_u8!(),
+                step: get_u8!(),
+                inclusive: true,
+            }),
             Op::MakeIterator => Some(MakeIterator {
                 register: get_u8!(),
                 iterable: get_u8!(),
@@ -316,6 +338,14 @@ impl Iterator for InstructionReader {
                 register: get_u8!(),
                 offset: get_u16!(),
             }),
+            Op::JumpIfNull => Some(JumpIfNull {
+                register: get_u8!(),
+                offset: get_u16!(),
+            }),
+            Op::JumpIfNotNull => Some(JumpIfNotNull {
+                register: get_u8!(),
+                offset: get_u16!(),
+            }),
             Op::Call => Some(Call {
                 result: get_u8!(),
                 function: get_u8!(),
@@ -328,6 +358,24 @@ impl Iterator for InstructionReader {
             Op::Yield => Some(Yield {
                 register: get_u8!(),
             }),
+            Op::Await => Some(Await {
+                register: get_u8!(),
+                value: get_u8!(),
+            }),
+            Op::Close => Some(Close {
+                register: get_u8!(),
+            }),
+            Op::MakeCell => Some(MakeCell {
+                register: get_u8!(),
+            }),
+            Op::LoadCell => Some(LoadCell {
+                register: get_u8!(),
+                cell: get_u8!(),
+            }),
+            Op::StoreCell => Some(StoreCell {
+                cell: get_u8!(),
+                value: get_u8!(),
+            }),
             Op::Throw => Some(Throw {
                 register: get_u8!(),
             }),
@@ -481,6 +529,10 @@ impl Iterator for InstructionReader {
                 register: get_u8!(),
                 size: get_u8!() as usize,
             }),
+            Op::CheckType => Some(CheckType {
+                register: get_u8!(),
+                type_constant: get_var_u32!(),
+            }),
             Op::StringStart => Some(StringStart {
                 size_hint: get_var_u32!(),
             }),
@@ -493,6 +545,8 @@ impl Iterator for InstructionReader {
 
                     let mut options = StringFormatOptions {
                         alignment: flags.alignment,
+                        show_sign: flags.show_sign,
+                        thousands_separator: flags.thousands_separator,
                         ..Default::default()
                     };
                     if flags.min_width {
@@ -504,6 +558,17 @@ impl Iterator for InstructionReader {
                     if flags.fill_character {
                         options.fill_character = Some(get_var_u32!());
                     }
+                    if flags.representation {
+                        let byte = get_u8!();
+                        match StringFormatRepresentation::from_byte(byte) {
+                            Some(representation) => options.representation = Some(representation),
+                            None => {
+                                return Some(Error {
+                                    message: format!("Invalid format representation: {byte}"),
+                                })
+                            }
+                        }
+                    }
 
                     Some(options)
                 } else {
@@ -10,6 +10,7 @@ use std::{
     io,
     ops::Deref,
     path::{Path, PathBuf},
+    time::SystemTime,
 };
 use thiserror::Error;
 
@@ -129,6 +130,10 @@ impl From<LoaderErrorKind> for LoaderError {
 #[derive(Clone, Default)]
 pub struct Loader {
     chunks: HashMap<PathBuf, Ptr<Chunk>, BuildHasherDefault<FxHasher>>,
+    // The modification times of loaded module files, used to detect changed modules
+    modified_times: HashMap<PathBuf, SystemTime, BuildHasherDefault<FxHasher>>,
+    // Set when modules should be compiled without type checks
+    disable_module_type_checks: bool,
 }
 
 impl Loader {
@@ -180,23 +185,12 @@ impl Loader {
                     chunk: chunk.clone(),
                     path: module_path,
                     loaded_from_cache: true,
+                    modified_time: None,
                 }),
                 None => {
-                    let script = std::fs::read_to_string(&module_path)?;
-
-                    let chunk = self.compile_script(
-                        &script,
-                        Some(&module_path),
-                        CompilerSettings::default(),
-                    )?;
-
-                    self.chunks.insert(module_path.clone(), chunk.clone());
-
-                    Ok(CompileModuleResult {
-                        chunk,
-                        path: module_path,
-                        loaded_from_cache: false,
-                    })
+                    let result = self.compile_module_at_path(module_path)?;
+                    self.cache_module(&result);
+                    Ok(result)
                 }
             }
         };
@@ -220,9 +214,79 @@ impl Loader {
         }
     }
 
+    /// Recompiles a previously loaded module
+    ///
+    /// The module is recompiled from disk even if its file hasn't been modified.
+    ///
+    /// The module's cached chunk isn't replaced by the recompiled chunk until
+    /// [Loader::cache_module] is called, which allows a module that fails to run to be
+    /// detected as modified again by [Loader::modified_modules].
+    pub fn recompile_module(
+        &mut self,
+        module_path: &Path,
+    ) -> Result<CompileModuleResult, LoaderError> {
+        let module_path = canonicalize(module_path)?;
+        self.compile_module_at_path(module_path)
+    }
+
+    /// Caches a compiled module's chunk, along with its file's modification time
+    pub fn cache_module(&mut self, module: &CompileModuleResult) {
+        self.chunks
+            .insert(module.path.clone(), module.chunk.clone());
+        if let Some(modified_time) = module.modified_time {
+            self.modified_times
+                .insert(module.path.clone(), modified_time);
+        }
+    }
+
+    /// Returns the paths of loaded modules whose files have changed since they were compiled
+    ///
+    /// Modules that have been removed since they were loaded are also included.
+    pub fn modified_modules(&self) -> Vec<PathBuf> {
+        self.modified_times
+            .iter()
+            .filter_map(|(path, compiled_time)| {
+                let modified = match std::fs::metadata(path).and_then(|m| m.modified()) {
+                    Ok(current_time) => current_time != *compiled_time,
+                    Err(_) => true,
+                };
+                modified.then(|| path.clone())
+            })
+            .collect()
+    }
+
+    /// Enables or disables type checks in modules that are compiled by the loader
+    ///
+    /// Type checks are enabled by default, see [CompilerSettings::enable_type_checks].
+    pub fn set_module_type_checks(&mut self, enabled: bool) {
+        self.disable_module_type_checks = !enabled;
+    }
+
     /// Clears the compiled module cache
     pub fn clear_cache(&mut self) {
         self.chunks.clear();
+        self.modified_times.clear();
+    }
+
+    fn compile_module_at_path(
+        &mut self,
+        module_path: PathBuf,
+    ) -> Result<CompileModuleResult, LoaderError> {
+        let modified_time = std::fs::metadata(&module_path).and_then(|m| m.modified());
+        let script = std::fs::read_to_string(&module_path)?;
+
+        let settings = CompilerSettings {
+            enable_type_checks: !self.disable_module_type_checks,
+            ..Default::default()
+        };
+        let chunk = self.compile_script(&script, Some(&module_path), settings)?;
+
+        Ok(CompileModuleResult {
+            chunk,
+            path: module_path,
+            loaded_from_cache: false,
+            modified_time: modified_time.ok(),
+        })
     }
 }
 
@@ -230,4 +294,5 @@ pub struct CompileModuleResult {
     pub chunk: Ptr<Chunk>,
     pub path: PathBuf,
     pub loaded_from_cache: bool,
+    pub modified_time: Option<SystemTime>,
 }
@@ -148,7 +148,7 @@ pub enum Op {
     ///
     /// See [StringFormatFlags](crate::StringFormatFlags) for a description of the the format flags.
     ///
-    /// `[*value, format_flags, ?@min_width, ?@precision, ?@fill_character]`
+    /// `[*value, format_flags, ?@min_width, ?@precision, ?@fill_character, ?representation]`
     StringPush,
 
     /// Places the finished string in the target register
@@ -485,19 +485,87 @@ pub enum Op {
     /// `[*value, size]`
     CheckSizeMin,
 
+    /// Throws an error if the value doesn't match the expected type
+    ///
+    /// Used when checking type hints.
+    ///
+    /// `[*value, @type constant]`
+    CheckType,
+
+    /// Exports a constant value by adding it to the module's exports map
+    ///
+    /// The exported value can't be replaced by assignments to the exports map.
+    ///
+    /// Used for expressions like `export const foo = ...`
+    ///
+    /// `[*name, *value]`
+    ConstExport,
+
+    /// Applies a step to the Range in the target register
+    ///
+    /// Used for expressions like `0..10..2`
+    ///
+    /// `[*target, *step]`
+    RangeStep,
+
+    /// Causes the instruction pointer to jump forward, if the value is null
+    ///
+    /// Used for optional chaining, e.g. `x?.foo`
+    ///
+    /// `[*value, offset[2]]`
+    JumpIfNull,
+
+    /// Causes the instruction pointer to jump forward, if the value isn't null
+    ///
+    /// Used for the null-coalescing operator, e.g. `x ?? y`
+    ///
+    /// `[*value, offset[2]]`
+    JumpIfNotNull,
+
+    /// Awaits the result of a future, suspending execution if the future is still pending
+    ///
+    /// Values that aren't futures are copied directly to the result register.
+    ///
+    /// `[*result, *value]`
+    Await,
+
+    /// Closes the resource of a `with` block, via `@close` or `KotoObject::close`
+    ///
+    /// `[*value]`
+    Close,
+
+    /// Makes a cell for a value that's declared with `var`, initialized with null
+    ///
+    /// Cells are shared between the frame that declares the `var` and any functions that
+    /// capture it, allowing the value to be captured by reference.
+    ///
+    /// `[*target]`
+    MakeCell,
+
+    /// Loads the value contained in a cell
+    ///
+    /// `[*result, *cell]`
+    LoadCell,
+
+    /// Stores a value in a cell
+    ///
+    /// `[*cell, *value]`
+    StoreCell,
+
+    /// Makes a Range with defined start and end values, and a step
+    ///
+    /// Used for expressions like `0.0..1.0..0.25`, where the step is applied to the range's
+    /// original bounds rather than to the bounds of an already-constructed integer range.
+    ///
+    /// `[*target, *start, *end, *step]`
+    SteppedRange,
+
+    /// Makes an inclusive Range with defined start and end values, and a step
+    ///
+    /// `[*target, *start, *end, *step]`
+    SteppedRangeInclusive,
+
     // Unused opcodes, allowing for a direct transmutation from a byte to an Op.
-    Unused82,
-    Unused83,
-    Unused84,
-    Unused85,
-    Unused86,
-    Unused87,
-    Unused88,
-    Unused89,
-    Unused90,
-    Unused91,
-    Unused92,
-    Unused93,
     Unused94,
     Unused95,
     Unused96,
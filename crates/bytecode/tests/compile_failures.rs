@@ -3,9 +3,13 @@ mod bytecode {
     use koto_parser::Parser;
 
     fn check_compilation_fails(source: &str) {
+        check_compilation_fails_with_settings(source, CompilerSettings::default());
+    }
+
+    fn check_compilation_fails_with_settings(source: &str, settings: CompilerSettings) {
         match Parser::parse(source) {
             Ok(ast) => {
-                if Compiler::compile(&ast, CompilerSettings::default()).is_ok() {
+                if Compiler::compile(&ast, settings).is_ok() {
                     panic!("\nUnexpected success while compiling: {source}");
                 }
             }
@@ -77,6 +81,132 @@ continue
             check_compilation_fails(source);
         }
 
+        mod constants {
+            use super::*;
+
+            #[test]
+            fn reassigned_const() {
+                let source = "
+const x = 1
+x = 2
+";
+                check_compilation_fails(source);
+            }
+
+            #[test]
+            fn compound_assignment_to_const() {
+                let source = "
+const x = 1
+x += 2
+";
+                check_compilation_fails(source);
+            }
+
+            #[test]
+            fn const_used_as_loop_arg() {
+                let source = "
+const x = 1
+for x in 0..10
+  x
+";
+                check_compilation_fails(source);
+            }
+
+            #[test]
+            fn const_redeclared() {
+                let source = "
+const x = 1
+const x = 2
+";
+                check_compilation_fails(source);
+            }
+
+            #[test]
+            fn const_used_in_multi_assignment() {
+                let source = "
+const x = 1
+y, x = 2, 3
+";
+                check_compilation_fails(source);
+            }
+        }
+
+        mod var_declarations {
+            use super::*;
+
+            #[test]
+            fn var_used_as_loop_arg() {
+                let source = "
+var x = 1
+for x in 0..10
+  x
+";
+                check_compilation_fails(source);
+            }
+
+            #[test]
+            fn var_used_as_catch_arg() {
+                let source = "
+var e = null
+try
+  throw 'x'
+catch e
+  e
+";
+                check_compilation_fails(source);
+            }
+        }
+
+        mod require_let {
+            use super::*;
+
+            fn check_compilation_fails_with_require_let(source: &str) {
+                check_compilation_fails_with_settings(
+                    source,
+                    CompilerSettings {
+                        require_let: true,
+                        ..Default::default()
+                    },
+                );
+            }
+
+            #[test]
+            fn undeclared_assignment() {
+                let source = "
+x = 1
+";
+                check_compilation_fails_with_require_let(source);
+            }
+
+            #[test]
+            fn undeclared_multi_assignment() {
+                let source = "
+let x = 1
+x, y = 2, 3
+";
+                check_compilation_fails_with_require_let(source);
+            }
+
+            #[test]
+            fn assignment_after_block_scope_has_ended() {
+                let source = "
+if true
+  let x = 1
+x = 2
+";
+                check_compilation_fails_with_require_let(source);
+            }
+
+            #[test]
+            fn let_shadowing_a_constant() {
+                let source = "
+const x = 1
+let x = 2
+";
+                check_compilation_fails(source);
+            }
+        }
+
         mod export {
             use super::*;
 
@@ -48,6 +48,7 @@ impl Help {
         result.add_help_from_guide();
 
         let core_lib_files = [
+            include_doc!("core_lib/bytes.md"),
             include_doc!("core_lib/io.md"),
             include_doc!("core_lib/iterator.md"),
             include_doc!("core_lib/koto.md"),
@@ -55,7 +56,9 @@ impl Help {
             include_doc!("core_lib/map.md"),
             include_doc!("core_lib/number.md"),
             include_doc!("core_lib/os.md"),
+            include_doc!("core_lib/parallel.md"),
             include_doc!("core_lib/range.md"),
+            include_doc!("core_lib/set.md"),
             include_doc!("core_lib/string.md"),
             include_doc!("core_lib/test.md"),
             include_doc!("core_lib/tuple.md"),
@@ -66,7 +69,9 @@ impl Help {
         }
 
         let extra_lib_files = [
+            include_doc!("libs/bigint.md"),
             include_doc!("libs/color.md"),
+            include_doc!("libs/decimal.md"),
             include_doc!("libs/geometry.md"),
             include_doc!("libs/json.md"),
             include_doc!("libs/random.md"),
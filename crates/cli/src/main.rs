@@ -222,7 +222,9 @@ fn main() -> Result<()> {
 
 fn add_modules(koto: &Koto) {
     let prelude = koto.prelude();
+    prelude.insert("bigint", koto_bigint::make_module());
     prelude.insert("color", koto_color::make_module());
+    prelude.insert("decimal", koto_decimal::make_module());
     prelude.insert("geometry", koto_geometry::make_module());
     prelude.insert("json", koto_json::make_module());
     prelude.insert("random", koto_random::make_module());
@@ -67,6 +67,19 @@ pub(crate) fn generate_koto_lookup_entries(args: TokenStream, item: TokenStream)
         })
         .unzip();
 
+    // An optional function tagged with #[koto_access], used to implement KotoEntries::access
+    let access_fn = input.items.iter().find_map(|item| match item {
+        ImplItem::Fn(f) if f.attrs.iter().any(|a| a.path().is_ident("koto_access")) => {
+            let fn_name = &f.sig.ident;
+            Some(quote! {
+                fn access(&self, key: &#runtime::KString) -> Option<#runtime::KValue> {
+                    #struct_ident::#fn_name(self, key)
+                }
+            })
+        }
+        _ => None,
+    });
+
     let item = proc_macro2::TokenStream::from(item);
     let result = quote! {
         #item
@@ -87,6 +100,8 @@ pub(crate) fn generate_koto_lookup_entries(args: TokenStream, item: TokenStream)
             fn entries(&self) -> Option<#runtime::KMap> {
                 #entries_map_name.with(|map| Some(map.clone()))
             }
+
+            #access_fn
         }
     };
 
@@ -74,6 +74,12 @@ pub fn derive_koto_copy(input: TokenStream) -> TokenStream {
 /// (e.g. if you want to implement chainable setters), then you can accept a `MethodContext<Self`>
 /// as the function argument and then return `MethodContext::instance_result()`.
 ///
+/// ## `#[koto_access]`
+///
+/// A function tagged with `#[koto_access]` will be used to implement `KotoEntries::access`,
+/// allowing entries to be computed on demand when a key isn't found in the object's methods.
+/// The function should take `&self` and a `&KString` key, and return an `Option<KValue>`.
+///
 /// ## `runtime` attribute
 ///
 /// The macro generates code assuming that the top-level `koto` crate is being used,
@@ -140,5 +146,11 @@ pub fn koto_method(_attr: TokenStream, item: TokenStream) -> TokenStream {
     item
 }
 
+/// See [`koto_impl`](macro@koto_impl)
+#[proc_macro_attribute]
+pub fn koto_access(_attr: TokenStream, item: TokenStream) -> TokenStream {
+    item
+}
+
 const PREFIX_STATIC: &str = "__KOTO_";
 const PREFIX_FUNCTION: &str = "__koto_";
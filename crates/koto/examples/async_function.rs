@@ -0,0 +1,32 @@
+use koto::{executor::block_on, prelude::*};
+use std::{thread, time::Duration};
+
+fn main() {
+    let script = "
+data = await load 'config'
+print 'Loaded: {data}'
+";
+    let mut koto = Koto::default();
+
+    koto.prelude().add_fn("load", |ctx| match ctx.args() {
+        [KValue::Str(name)] => {
+            // Return a pending future to the script, and then resolve it when the data is ready
+            let future = KFuture::pending();
+            let name = name.to_string();
+            thread::spawn({
+                let future = future.clone();
+                move || {
+                    // Simulate some slow I/O
+                    thread::sleep(Duration::from_millis(10));
+                    future.resolve(Ok(format!("{name}.toml").into()));
+                }
+            });
+            Ok(future.into())
+        }
+        unexpected => type_error_with_slice("a string", unexpected),
+    });
+
+    // The script is suspended while the future returned by `load` is pending.
+    // Any executor can be used to run the script, here the simple executor provided by koto is used.
+    block_on(koto.compile_and_run_async(script)).unwrap();
+}
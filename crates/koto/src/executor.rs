@@ -0,0 +1,59 @@
+//! A minimal single-threaded executor for running Koto scripts asynchronously
+//!
+//! This is useful for tests and for simple hosts that don't already use an async runtime.
+//! Hosts that use an async runtime can drive [Koto::run_async](crate::Koto::run_async) with their
+//! runtime's executor instead.
+
+use std::{
+    future::Future,
+    pin::pin,
+    sync::Arc,
+    task::{Context, Poll, Wake, Waker},
+    thread::{self, Thread},
+};
+
+/// Runs a future to completion on the current thread, returning the future's output
+///
+/// The thread is parked while the future is pending, and is unparked when the future's waker is
+/// called. Note that a future that's never woken (e.g. a pending [KFuture](crate::runtime::KFuture)
+/// that's never resolved) will block the thread indefinitely.
+///
+/// Example:
+///
+/// ```
+/// use koto::{executor::block_on, prelude::*};
+///
+/// fn main() -> koto::Result<()> {
+///     let mut koto = Koto::default();
+///
+///     koto.prelude().add_fn("load", |_| {
+///         Ok(KFuture::new(async { Ok(KValue::Number(42.into())) }).into())
+///     });
+///
+///     let result = block_on(koto.compile_and_run_async("await load()"))?;
+///     assert_eq!(koto.value_to_string(result)?, "42");
+///
+///     Ok(())
+/// }
+/// ```
+pub fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = pin!(future);
+    let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+    let mut cx = Context::from_waker(&waker);
+
+    loop {
+        match future.as_mut().poll(&mut cx) {
+            Poll::Ready(output) => return output,
+            Poll::Pending => thread::park(),
+        }
+    }
+}
+
+// Unparks the executor's thread when woken
+struct ThreadWaker(Thread);
+
+impl Wake for ThreadWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
+    }
+}
@@ -33,6 +33,8 @@ pub struct Koto {
     runtime: KotoVm,
     run_tests: bool,
     export_top_level_ids: bool,
+    enable_type_checks: bool,
+    require_let: bool,
     script_path: Option<PathBuf>,
     chunk: Option<Ptr<Chunk>>,
 }
@@ -51,10 +53,18 @@ impl Koto {
 
     /// Creates a new instance of Koto with the given settings
     pub fn with_settings(settings: KotoSettings) -> Self {
+        let runtime = KotoVm::with_settings(settings.vm_settings);
+        runtime
+            .loader()
+            .borrow_mut()
+            .set_module_type_checks(settings.enable_type_checks);
+
         Self {
-            runtime: KotoVm::with_settings(settings.vm_settings),
+            runtime,
             run_tests: settings.run_tests,
             export_top_level_ids: settings.export_top_level_ids,
+            enable_type_checks: settings.enable_type_checks,
+            require_let: settings.require_let,
             chunk: None,
             script_path: None,
         }
@@ -75,6 +85,45 @@ impl Koto {
         self.runtime.exports_mut()
     }
 
+    /// Spawns a VM with its own exports map that shares the runtime's prelude and module caches
+    ///
+    /// With the `arc` feature enabled the VM can be moved to another thread, e.g. to call
+    /// exported functions in parallel.
+    ///
+    /// See [KotoVm::spawn_independent_vm].
+    ///
+    /// Example:
+    ///
+    /// ```
+    /// use koto::prelude::*;
+    ///
+    /// fn main() -> koto::Result<()> {
+    ///     let mut koto = Koto::default();
+    ///     koto.compile_and_run("export square = |n| n * n")?;
+    ///     let square = koto.exports().get("square").unwrap();
+    ///
+    ///     let threads = (0..4)
+    ///         .map(|i| {
+    ///             let mut vm = koto.spawn_independent_vm();
+    ///             let square = square.clone();
+    ///             std::thread::spawn(move || vm.call_function(square, KValue::Number(i.into())))
+    ///         })
+    ///         .collect::<Vec<_>>();
+    ///
+    ///     for (i, thread) in threads.into_iter().enumerate() {
+    ///         match thread.join().unwrap()? {
+    ///             KValue::Number(result) => assert_eq!(result, i * i),
+    ///             other => panic!("Unexpected result: {}", other.type_as_string()),
+    ///         }
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn spawn_independent_vm(&self) -> KotoVm {
+        self.runtime.spawn_independent_vm()
+    }
+
     /// Compiles a Koto script, returning the complied chunk if successful
     ///
     /// On success, the chunk is cached as the current chunk for subsequent calls to [Koto::run].
@@ -84,6 +133,8 @@ impl Koto {
             self.script_path.as_deref(),
             CompilerSettings {
                 export_top_level_ids: self.export_top_level_ids,
+                enable_type_checks: self.enable_type_checks,
+                require_let: self.require_let,
             },
         )?;
 
@@ -108,6 +159,37 @@ impl Koto {
         self.run()
     }
 
+    /// Runs the chunk last compiled with [compile](Koto::compile) asynchronously
+    ///
+    /// Execution is suspended when the script uses `await` on a pending [KFuture], allowing the
+    /// host to perform asynchronous work without blocking the runtime.
+    ///
+    /// Any executor can be used to drive the returned future, with [executor::block_on] available
+    /// as a simple single-threaded option.
+    ///
+    /// Tests and `@main` are run after the script has completed, and can't suspend execution.
+    ///
+    /// See [KotoVm::run_async].
+    ///
+    /// [executor::block_on]: crate::executor::block_on
+    pub async fn run_async(&mut self) -> Result<KValue> {
+        let Some(chunk) = self.chunk.clone() else {
+            return runtime_error!("Nothing to run");
+        };
+
+        let result = self.runtime.run_async(chunk).await?;
+        self.finish_run(result)
+    }
+
+    /// Compiles and runs a Koto script asynchronously, and returns the script's result
+    ///
+    /// This is equivalent to calling [compile](Self::compile) followed by
+    /// [run_async](Self::run_async).
+    pub async fn compile_and_run_async(&mut self, script: &str) -> Result<KValue> {
+        self.compile(script)?;
+        self.run_async().await
+    }
+
     /// Calls a function with the given arguments
     ///
     /// If the provided value isn't [callable](KValue::is_callable) then an error will be returned.
@@ -144,6 +226,25 @@ impl Koto {
         self.runtime.loader().borrow_mut().clear_cache();
     }
 
+    /// Reloads the modules at the given paths, preserving their existing exports maps
+    ///
+    /// Each module's new exports are patched into the map that was produced when the module was
+    /// first imported, and the module's `@reload` function is called with the previous exports.
+    ///
+    /// See [KotoVm::reload_modules].
+    pub fn reload_modules(&mut self, paths: &[PathBuf]) -> Result<()> {
+        self.runtime.reload_modules(paths)
+    }
+
+    /// Reloads any imported modules whose files have been modified since they were last compiled
+    ///
+    /// The paths of the reloaded modules are returned.
+    ///
+    /// See [KotoVm::reload_modified_modules].
+    pub fn reload_modified_modules(&mut self) -> Result<Vec<PathBuf>> {
+        self.runtime.reload_modified_modules()
+    }
+
     /// Sets the arguments that can be accessed from within the script via `koto.args()`
     pub fn set_args(&mut self, args: &[String]) -> Result<()> {
         use KValue::{Map, Str, Tuple};
@@ -208,7 +309,11 @@ impl Koto {
 
     fn run_chunk(&mut self, chunk: Ptr<Chunk>) -> Result<KValue> {
         let result = self.runtime.run(chunk)?;
+        self.finish_run(result)
+    }
 
+    // Runs the script's tests and @main function after the script has been run
+    fn finish_run(&mut self, result: KValue) -> Result<KValue> {
         if self.run_tests {
             let maybe_tests = self.runtime.exports().get_meta_value(&MetaKey::Tests);
             match maybe_tests {
@@ -243,6 +348,19 @@ pub struct KotoSettings {
     /// This is used by the REPL, allowing for incremental compilation and execution of expressions
     /// that need to share declared values.
     pub export_top_level_ids: bool,
+    /// Whether or not type hints should be checked at runtime
+    ///
+    /// Disabling type checks removes their runtime overhead, e.g. for release builds of scripts
+    /// that have already been tested with type checks enabled.
+    ///
+    /// The setting applies to the main script and to any modules that it imports.
+    pub enable_type_checks: bool,
+    /// Whether or not new values in the main script need to be declared with `let`
+    ///
+    /// When enabled, assigning to an undeclared ID results in a compilation error.
+    ///
+    /// Imported modules aren't affected by this setting.
+    pub require_let: bool,
     /// Settings that apply to the runtime
     pub vm_settings: KotoVmSettings,
 }
@@ -317,6 +435,8 @@ impl Default for KotoSettings {
         Self {
             run_tests: true,
             export_top_level_ids: false,
+            enable_type_checks: true,
+            require_let: false,
             vm_settings: KotoVmSettings::default(),
         }
     }
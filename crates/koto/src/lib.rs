@@ -28,6 +28,7 @@
 
 #![warn(missing_docs)]
 
+pub mod executor;
 mod koto;
 pub mod prelude;
 
@@ -0,0 +1,190 @@
+//! Tests for suspending execution with `await` while the host resolves futures
+
+use koto::{executor::block_on, prelude::*, PtrMut};
+use std::{
+    future::Future,
+    pin::{pin, Pin},
+    task::{Context, Poll, Waker},
+};
+
+// A future that's pending when first polled, and then resolves with the given value
+struct Delayed {
+    value: Option<KValue>,
+    polled: bool,
+}
+
+impl Delayed {
+    fn new(value: KValue) -> Self {
+        Self {
+            value: Some(value),
+            polled: false,
+        }
+    }
+}
+
+impl Future for Delayed {
+    type Output = koto::Result<KValue>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        if self.polled {
+            Poll::Ready(Ok(self.value.take().unwrap_or_default()))
+        } else {
+            self.polled = true;
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        }
+    }
+}
+
+fn koto_with_load_fn() -> Koto {
+    let koto = Koto::default();
+    koto.prelude().add_fn("load", |ctx| match ctx.args() {
+        [value] => Ok(KFuture::new(Delayed::new(value.clone())).into()),
+        unexpected => type_error_with_slice("a value", unexpected),
+    });
+    koto
+}
+
+fn check_async_script(koto: &mut Koto, script: &str, expected: &str) {
+    let result = block_on(koto.compile_and_run_async(script)).unwrap();
+    assert_eq!(koto.value_to_string(result).unwrap(), expected);
+}
+
+mod async_run {
+    use super::*;
+
+    #[test]
+    fn await_wrapped_futures() {
+        let script = "
+x = await load 1
+y = await load 2
+x + y
+";
+        check_async_script(&mut koto_with_load_fn(), script, "3");
+    }
+
+    #[test]
+    fn await_in_function() {
+        let script = "
+f = |x| (await load x) * 2
+f 21
+";
+        check_async_script(&mut koto_with_load_fn(), script, "42");
+    }
+
+    #[test]
+    fn await_in_loop() {
+        let script = "
+result = []
+for i in 0..3
+  result.push await load i
+result
+";
+        check_async_script(&mut koto_with_load_fn(), script, "[0, 1, 2]");
+    }
+
+    #[test]
+    fn await_non_future_value() {
+        check_async_script(&mut Koto::default(), "await 99", "99");
+    }
+
+    #[test]
+    fn failed_future_can_be_caught() {
+        let mut koto = Koto::default();
+        koto.prelude().add_fn("fail", |_| {
+            Ok(KFuture::new(async { runtime_error!("Failed to load") }).into())
+        });
+
+        let script = "
+try
+  await fail()
+catch error
+  'caught: {error}'
+";
+        check_async_script(&mut koto, script, "caught: Failed to load");
+    }
+
+    #[test]
+    fn host_resolves_pending_future() {
+        let mut koto = Koto::default();
+        let requests: PtrMut<Vec<KFuture>> = PtrMut::default();
+
+        koto.prelude().add_fn("request", {
+            let requests = requests.clone();
+            move |_| {
+                let future = KFuture::pending();
+                requests.borrow_mut().push(future.clone());
+                Ok(future.into())
+            }
+        });
+
+        koto.compile("(await request()) + 1").unwrap();
+
+        let waker = Waker::noop();
+        let mut cx = Context::from_waker(waker);
+        let result = {
+            let mut run = pin!(koto.run_async());
+
+            // The script is suspended until the host resolves the request
+            assert!(run.as_mut().poll(&mut cx).is_pending());
+            assert!(run.as_mut().poll(&mut cx).is_pending());
+            assert_eq!(requests.borrow().len(), 1);
+
+            requests.borrow()[0].resolve(Ok(KValue::Number(41.into())));
+
+            match run.as_mut().poll(&mut cx) {
+                Poll::Ready(result) => result.unwrap(),
+                Poll::Pending => panic!("Expected the run to be complete"),
+            }
+        };
+
+        assert_eq!(koto.value_to_string(result).unwrap(), "42");
+    }
+
+    #[test]
+    fn cancelled_run_leaves_vm_usable() {
+        let mut koto = Koto::default();
+        koto.prelude()
+            .add_fn("request", |_| Ok(KFuture::pending().into()));
+
+        koto.compile("f = || await request()\nf()").unwrap();
+
+        {
+            let waker = Waker::noop();
+            let mut cx = Context::from_waker(waker);
+            let mut run = pin!(koto.run_async());
+            assert!(run.as_mut().poll(&mut cx).is_pending());
+        }
+
+        let result = koto.compile_and_run("1 + 1").unwrap();
+        assert_eq!(koto.value_to_string(result).unwrap(), "2");
+    }
+}
+
+mod should_fail {
+    use super::*;
+
+    #[test]
+    fn await_pending_future_in_sync_run() {
+        let mut koto = koto_with_load_fn();
+        assert!(koto.compile_and_run("await load 1").is_err());
+    }
+
+    #[test]
+    fn await_pending_future_in_native_callback() {
+        let mut koto = koto_with_load_fn();
+        let script = "[1, 2].each(|x| await load x).to_list()";
+        assert!(block_on(koto.compile_and_run_async(script)).is_err());
+    }
+
+    #[test]
+    fn await_pending_future_in_generator() {
+        let mut koto = koto_with_load_fn();
+        let script = "
+gen = ||
+  yield await load 1
+gen().to_list()
+";
+        assert!(block_on(koto.compile_and_run_async(script)).is_err());
+    }
+}
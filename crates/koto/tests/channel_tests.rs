@@ -0,0 +1,67 @@
+//! Tests for passing values between Rust and Koto with channels
+
+use koto::{prelude::*, runtime::core_lib::parallel::Channel};
+use std::thread;
+
+fn number(value: KValue) -> i64 {
+    match value {
+        KValue::Number(n) => n.into(),
+        other => panic!("Expected a number, found '{}'", other.type_as_string()),
+    }
+}
+
+#[test]
+fn host_thread_sends_to_script() {
+    let mut koto = Koto::default();
+    koto.compile_and_run("export sum = |channel| channel.fold 0, |total, n| total + n")
+        .unwrap();
+    let sum = koto.exports().get("sum").unwrap();
+
+    // The bounded channel causes the sender to wait for the script to receive values
+    let channel = Channel::bounded(2);
+    let sender = {
+        let channel = channel.clone();
+        thread::spawn(move || {
+            for i in 1..=100 {
+                channel.send_value(i.into()).unwrap();
+            }
+            channel.close_channel();
+        })
+    };
+
+    let result = koto.call_function(sum, KValue::from(channel)).unwrap();
+    sender.join().unwrap();
+
+    assert_eq!(number(result), 5050);
+}
+
+#[test]
+fn script_sends_to_host_thread() {
+    let mut koto = Koto::default();
+    koto.compile_and_run(
+        "
+export produce = |channel|
+  for i in 0..10
+    channel.send i * i
+  channel.close()
+",
+    )
+    .unwrap();
+    let produce = koto.exports().get("produce").unwrap();
+
+    let channel = Channel::unbounded();
+    let mut vm = koto.spawn_independent_vm();
+    let producer = {
+        let channel = channel.clone();
+        thread::spawn(move || vm.call_function(produce, KValue::from(channel)))
+    };
+
+    let mut received = Vec::new();
+    while let Some(value) = channel.recv_value().unwrap() {
+        received.push(number(value));
+    }
+    producer.join().unwrap().unwrap();
+
+    assert_eq!(received, (0..10).map(|i| i * i).collect::<Vec<_>>());
+    assert!(channel.is_channel_closed());
+}
@@ -42,13 +42,16 @@ mod core_lib {
         };
     }
 
+    test_core_lib_examples!(bytes);
     test_core_lib_examples!(iterator);
     test_core_lib_examples!(koto);
     test_core_lib_examples!(list);
     test_core_lib_examples!(map);
     test_core_lib_examples!(number);
     test_core_lib_examples!(os);
+    test_core_lib_examples!(parallel);
     test_core_lib_examples!(range);
+    test_core_lib_examples!(set);
     test_core_lib_examples!(string);
     test_core_lib_examples!(test);
     test_core_lib_examples!(tuple);
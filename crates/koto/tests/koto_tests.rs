@@ -99,6 +99,7 @@ mod koto_tests {
     use super::*;
 
     koto_test!(assignment);
+    koto_test!(bytes);
     koto_test!(comments);
     koto_test!(control_flow);
     koto_test!(enums);
@@ -120,13 +121,21 @@ mod koto_tests {
     koto_test!(number_ops);
     koto_test!(numbers);
     koto_test!(os);
+    koto_test!(parallel);
     koto_test!(primes);
     koto_test!(ranges);
+    koto_test!(sets);
     koto_test!(strings);
     koto_test!(tests);
     koto_test!(tuples);
+    koto_test!(type_hints);
     koto_test!(types);
 
     koto_test!(error_handling, "error_handling_module/main.koto");
-    koto_test!(import, "test_module/baz.koto", "test_module/main.koto");
+    koto_test!(
+        import,
+        "test_module/baz.koto",
+        "test_module/consts.koto",
+        "test_module/main.koto"
+    );
 }
@@ -0,0 +1,234 @@
+//! Tests for reloading imported modules
+//!
+//! Modules are written to a temporary directory, imported by a main script, and then modified
+//! and reloaded.
+
+use koto::prelude::*;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+use tempfile::TempDir;
+
+struct ReloadTest {
+    koto: Koto,
+    dir: TempDir,
+}
+
+impl ReloadTest {
+    fn new(main_script: &str, modules: &[(&str, &str)]) -> Self {
+        let dir = tempfile::tempdir().expect("Failed to create temp dir");
+
+        for (name, contents) in modules {
+            fs::write(module_path(dir.path(), name), contents).unwrap();
+        }
+
+        let main_path = dir.path().join("main.koto");
+        fs::write(&main_path, main_script).unwrap();
+
+        let mut koto = Koto::default();
+        koto.set_script_path(Some(&main_path)).unwrap();
+        if let Err(error) = koto.compile_and_run(main_script) {
+            panic!("{error}");
+        }
+
+        Self { koto, dir }
+    }
+
+    fn path(&self, name: &str) -> PathBuf {
+        module_path(self.dir.path(), name)
+    }
+
+    fn update_module(&self, name: &str, contents: &str) {
+        let path = self.path(name);
+        fs::write(&path, contents).unwrap();
+
+        // Make sure that the change is visible regardless of the file system's time resolution
+        fs::File::options()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_modified(SystemTime::now() + Duration::from_secs(10))
+            .unwrap();
+    }
+
+    fn check_export(&mut self, name: &str, expected: &str) {
+        let value = self.koto.exports().get(name).expect("Missing export");
+        let result = self.koto.value_to_string(value).unwrap();
+        assert_eq!(result, expected);
+    }
+
+    fn check_expression(&mut self, expression: &str, expected: &str) {
+        let result = match self.koto.compile_and_run(expression) {
+            Ok(result) => result,
+            Err(error) => panic!("{error}"),
+        };
+        let result = self.koto.value_to_string(result).unwrap();
+        assert_eq!(result, expected);
+    }
+}
+
+fn module_path(dir: &Path, name: &str) -> PathBuf {
+    dir.join(name).with_extension("koto")
+}
+
+mod module_reload {
+    use super::*;
+
+    #[test]
+    fn exports_are_patched_into_the_imported_map() {
+        let mut test = ReloadTest::new(
+            "\
+export foo = import foo
+",
+            &[(
+                "foo",
+                "\
+export x = 1
+export f = || 'hello'
+",
+            )],
+        );
+
+        test.update_module(
+            "foo",
+            "\
+export x = 2
+export f = || 'goodbye'
+",
+        );
+
+        test.koto.reload_modules(&[test.path("foo")]).unwrap();
+
+        test.check_export("foo", "{x: 2, f: ||}");
+        test.check_expression("foo.f()", "goodbye");
+    }
+
+    #[test]
+    fn state_is_migrated_with_reload_function() {
+        let mut test = ReloadTest::new(
+            "\
+export foo = import foo
+foo.count += 10
+",
+            &[(
+                "foo",
+                "\
+export count = 0
+",
+            )],
+        );
+
+        test.update_module(
+            "foo",
+            "\
+export count = 0
+export version = 2
+
+@reload = |previous|
+  export count = previous.count
+",
+        );
+
+        test.koto.reload_modules(&[test.path("foo")]).unwrap();
+
+        test.check_expression("foo.count", "10");
+        test.check_expression("foo.version", "2");
+    }
+
+    #[test]
+    fn modified_modules_are_reloaded() {
+        let mut test = ReloadTest::new(
+            "\
+export foo = import foo
+export bar = import bar
+",
+            &[("foo", "export x = 'foo'"), ("bar", "export x = 'bar'")],
+        );
+
+        assert!(test.koto.reload_modified_modules().unwrap().is_empty());
+
+        test.update_module("bar", "export x = 'bar!'");
+
+        let reloaded = test.koto.reload_modified_modules().unwrap();
+        assert_eq!(reloaded, vec![fs::canonicalize(test.path("bar")).unwrap()]);
+
+        test.check_expression("foo.x", "foo");
+        test.check_expression("bar.x", "bar!");
+        assert!(test.koto.reload_modified_modules().unwrap().is_empty());
+    }
+
+    #[test]
+    fn failed_reload_keeps_previous_exports() {
+        let mut test = ReloadTest::new("export foo = import foo", &[("foo", "export x = 1")]);
+
+        test.update_module(
+            "foo",
+            "\
+export x = 2
+throw 'oops'
+",
+        );
+
+        assert!(test.koto.reload_modules(&[test.path("foo")]).is_err());
+
+        test.check_expression("foo.x", "1");
+        test.check_expression("(import foo).x", "1");
+    }
+
+    #[test]
+    fn failed_reload_is_retried() {
+        let mut test = ReloadTest::new("export foo = import foo", &[("foo", "export x = 1")]);
+
+        test.update_module(
+            "foo",
+            "\
+export x = 2
+throw 'oops'
+",
+        );
+
+        assert!(test.koto.reload_modified_modules().is_err());
+        test.check_expression("foo.x", "1");
+
+        // The module is still considered to be modified after the failed reload
+        assert!(test.koto.reload_modified_modules().is_err());
+
+        test.update_module("foo", "export x = 3");
+
+        let reloaded = test.koto.reload_modified_modules().unwrap();
+        assert_eq!(reloaded, vec![fs::canonicalize(test.path("foo")).unwrap()]);
+        test.check_expression("foo.x", "3");
+        test.check_expression("(import foo).x", "3");
+    }
+
+    #[test]
+    fn failed_reload_doesnt_prevent_other_reloads() {
+        let mut test = ReloadTest::new(
+            "\
+export foo = import foo
+export bar = import bar
+",
+            &[("foo", "export x = 'foo'"), ("bar", "export x = 'bar'")],
+        );
+
+        test.update_module("foo", "throw 'oops'");
+        test.update_module("bar", "export x = 'bar!'");
+
+        assert!(test
+            .koto
+            .reload_modules(&[test.path("foo"), test.path("bar")])
+            .is_err());
+
+        test.check_expression("foo.x", "foo");
+        test.check_expression("bar.x", "bar!");
+    }
+
+    #[test]
+    fn reloading_a_module_that_hasnt_been_imported() {
+        let mut test = ReloadTest::new("x = 1", &[("foo", "export x = 1")]);
+
+        assert!(test.koto.reload_modules(&[test.path("foo")]).is_err());
+    }
+}
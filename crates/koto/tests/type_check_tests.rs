@@ -0,0 +1,44 @@
+//! Tests for the `enable_type_checks` setting
+
+use koto::prelude::*;
+use std::fs;
+
+const SCRIPT: &str = "
+from helpers import describe
+f = |x: Number| -> String x
+describe f 21
+";
+
+const MODULE: &str = "
+export describe = |x: String| x
+";
+
+fn run_script(enable_type_checks: bool) -> koto::Result<KValue> {
+    let dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let script_path = dir.path().join("main.koto");
+    fs::write(&script_path, SCRIPT).unwrap();
+    fs::write(dir.path().join("helpers.koto"), MODULE).unwrap();
+
+    let mut koto = Koto::with_settings(KotoSettings {
+        enable_type_checks,
+        ..Default::default()
+    });
+    koto.set_script_path(Some(&script_path))?;
+    koto.compile_and_run(SCRIPT)
+}
+
+#[test]
+fn type_checks_enabled_by_default() {
+    let error = run_script(true).unwrap_err();
+    assert!(error
+        .to_string()
+        .contains("Expected 'String', but found 'Int'"));
+}
+
+#[test]
+fn type_checks_disabled() {
+    match run_script(false).unwrap() {
+        KValue::Number(n) => assert_eq!(n, 21),
+        other => panic!("Unexpected result: {}", other.type_as_string()),
+    }
+}
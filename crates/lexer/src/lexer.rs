@@ -22,12 +22,14 @@ pub enum Token {
     StringLiteral,
 
     // Symbols
+    Arrow,
     At,
     Colon,
     Comma,
     Dot,
     Ellipsis,
     Function,
+    Question,
     RoundOpen,
     RoundClose,
     SquareOpen,
@@ -58,6 +60,8 @@ pub enum Token {
     Less,
     LessOrEqual,
 
+    NullCoalesce,
+
     // Pipe is detected by the parser instead of the lexer
     Pipe,
 
@@ -92,12 +96,14 @@ pub enum Token {
     Try,
     Until,
     While,
+    With,
     Yield,
 
     // Reserved keywords
     Await,
     Const,
     Let,
+    Var,
 }
 
 impl Token {
@@ -672,7 +678,9 @@ impl<'a> TokenLexer<'a> {
             check_keyword!("true", True);
             check_keyword!("try", Try);
             check_keyword!("until", Until);
+            check_keyword!("var", Var);
             check_keyword!("while", While);
+            check_keyword!("with", With);
             check_keyword!("yield", Yield);
         }
 
@@ -705,11 +713,23 @@ impl<'a> TokenLexer<'a> {
             };
         }
 
+        // `....` is lexed as two ranges rather than an ellipsis, allowing a step to follow a full
+        // range, e.g. `x[....-1]`
+        if remaining.starts_with("....") {
+            self.advance_line(2);
+            return Some(Range);
+        }
+
         check_symbol!("...", Ellipsis);
 
         check_symbol!("..=", RangeInclusive);
         check_symbol!("..", Range);
 
+        check_symbol!("->", Arrow);
+
+        check_symbol!("??", NullCoalesce);
+        check_symbol!("?", Question);
+
         check_symbol!("==", Equal);
         check_symbol!("!=", NotEqual);
         check_symbol!(">=", GreaterOrEqual);
@@ -1460,6 +1480,57 @@ x = [i for i in 0..5]";
             );
         }
 
+        #[test]
+        fn ranges_with_steps() {
+            let input = "\
+a[0..10..2]
+b[....-1]";
+            check_lexer_output(
+                input,
+                &[
+                    (Id, Some("a"), 0),
+                    (SquareOpen, None, 0),
+                    (Number, Some("0"), 0),
+                    (Range, None, 0),
+                    (Number, Some("10"), 0),
+                    (Range, None, 0),
+                    (Number, Some("2"), 0),
+                    (SquareClose, None, 0),
+                    (NewLine, None, 0),
+                    (Id, Some("b"), 1),
+                    (SquareOpen, None, 1),
+                    (Range, None, 1),
+                    (Range, None, 1),
+                    (Subtract, None, 1),
+                    (Number, Some("1"), 1),
+                    (SquareClose, None, 1),
+                ],
+            );
+        }
+
+        #[test]
+        fn optional_chaining() {
+            let input = "x?.foo?[0]?() ?? y";
+            check_lexer_output(
+                input,
+                &[
+                    (Id, Some("x"), 0),
+                    (Question, None, 0),
+                    (Dot, None, 0),
+                    (Id, Some("foo"), 0),
+                    (Question, None, 0),
+                    (SquareOpen, None, 0),
+                    (Number, Some("0"), 0),
+                    (SquareClose, None, 0),
+                    (Question, None, 0),
+                    (RoundOpen, None, 0),
+                    (RoundClose, None, 0),
+                    (NullCoalesce, None, 0),
+                    (Id, Some("y"), 0),
+                ],
+            );
+        }
+
         #[test]
         fn function() {
             let input = "\
@@ -1499,6 +1570,23 @@ f()";
             );
         }
 
+        #[test]
+        fn function_with_type_hints() {
+            let input = "|x: Number| -> String";
+            check_lexer_output(
+                input,
+                &[
+                    (Function, None, 0),
+                    (Id, Some("x"), 0),
+                    (Colon, None, 0),
+                    (Id, Some("Number"), 0),
+                    (Function, None, 0),
+                    (Arrow, None, 0),
+                    (Id, Some("String"), 0),
+                ],
+            );
+        }
+
         #[test]
         fn if_inline() {
             let input = "1 + if true then 0 else 1";
@@ -67,6 +67,8 @@ pub enum ExpectedIndentation {
     UntilBody,
     #[error("Expected indented block as while loop body")]
     WhileBody,
+    #[error("Expected indented block for with expression")]
+    WithBody,
 }
 
 /// A syntax error encountered by the [Parser]
@@ -87,6 +89,8 @@ pub enum SyntaxError {
     ExpectedCatch,
     #[error("Expected closing parenthesis ')'")]
     ExpectedCloseParen,
+    #[error("Expected an assignment to an ID after 'const'")]
+    ExpectedConstAssignment,
     #[error("Expected expression after 'else'.")]
     ExpectedElseExpression,
     #[error("Expected condition for 'else if'.")]
@@ -115,6 +119,10 @@ pub enum SyntaxError {
     ExpectedIndexEnd,
     #[error("Expected index expression")]
     ExpectedIndexExpression,
+    #[error("Expected an assignment to one or more IDs after 'let'")]
+    ExpectedLetAssignment,
+    #[error("Expected a step value after '..'")]
+    ExpectedRangeStep,
     #[error("Expected id after 'as'")]
     ExpectedIdAfterAs,
     #[error("Expected List end ']'")]
@@ -129,6 +137,8 @@ pub enum SyntaxError {
     ExpectedMapKey,
     #[error("Expected value after ':' in Map")]
     ExpectedMapValue,
+    #[error("Expected '.', '[', or '(' after '?'")]
+    ExpectedOptionalLookup,
     #[error("Expected expression in match arm")]
     ExpectedMatchArmExpression,
     #[error("Expected expression after then in match arm")]
@@ -155,10 +165,16 @@ pub enum SyntaxError {
     ExpectedTestName,
     #[error("Expected expression after 'then'")]
     ExpectedThenExpression,
+    #[error("Expected type after ':' or '->'")]
+    ExpectedType,
     #[error("Expected condition in until loop")]
     ExpectedUntilCondition,
+    #[error("Expected an assignment to one or more IDs after 'var'")]
+    ExpectedVarAssignment,
     #[error("Expected condition in while loop")]
     ExpectedWhileCondition,
+    #[error("Expected resource in with expression")]
+    ExpectedWithResource,
     #[error(transparent)]
     FormatStringError(StringFormatError),
     #[error("Non-inline if expression isn't allowed in this context")]
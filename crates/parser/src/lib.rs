@@ -16,7 +16,9 @@ pub use crate::{
     error::{format_source_excerpt, Error, Result},
     node::*,
     parser::Parser,
-    string_format_options::{StringAlignment, StringFormatOptions},
+    string_format_options::{
+        StringAlignment, StringFormatError, StringFormatOptions, StringFormatRepresentation,
+    },
     string_slice::StringSlice,
 };
 pub use koto_lexer::{Position, RawStringDelimiter, Span, StringQuote, StringType};
@@ -16,6 +16,9 @@ pub enum Node {
     /// An identifer
     Id(ConstantIndex),
 
+    /// A type hint, e.g. `Number` in `|x: Number|`
+    Type(ConstantIndex),
+
     /// A meta identifier, e.g. `@display` or `@test my_test`
     Meta(MetaKeyId, Option<ConstantIndex>),
 
@@ -101,6 +104,16 @@ pub enum Node {
     /// Used when indexing a list or tuple, and the full contents are to be returned.
     RangeFull,
 
+    /// A range with a step between its values
+    ///
+    /// e.g. `0..10..2` - every second value from 0 up to but not including 10
+    RangeStep {
+        /// The range that the step is applied to
+        range: AstIndex,
+        /// The step between the range's values
+        step: AstIndex,
+    },
+
     /// A map literal, with a series of keys and values
     ///
     /// Values are optional for inline maps.
@@ -148,6 +161,27 @@ pub enum Node {
     /// The export item will be a map literal, with each map entry added to the exports map
     Export(AstIndex),
 
+    /// A constant declaration
+    ///
+    /// e.g. `const x = 42`
+    ///
+    /// The declaration will be an [Node::Assign] with an ID as its target.
+    Const(AstIndex),
+
+    /// A block-scoped declaration
+    ///
+    /// e.g. `let x = 42`, `let a, b = foo()`
+    ///
+    /// The declaration will be an [Node::Assign] or [Node::MultiAssign] with IDs as targets.
+    Let(AstIndex),
+
+    /// A block-scoped declaration of values that are captured by reference
+    ///
+    /// e.g. `var count = 0`
+    ///
+    /// The declaration will be an [Node::Assign] or [Node::MultiAssign] with IDs as targets.
+    Var(AstIndex),
+
     /// An assignment expression
     ///
     /// Used for single-assignment, multiple-assignment is represented by [Node::MultiAssign].
@@ -230,6 +264,16 @@ pub enum Node {
         body: AstIndex,
     },
 
+    /// A `with` block
+    With {
+        /// The resource that will be closed when the block is exited
+        ///
+        /// This will either be an expression, or an assignment of the resource to an ID.
+        resource: AstIndex,
+        /// The block's body
+        body: AstIndex,
+    },
+
     /// An `until` expression
     Until {
         /// The condition for the until loop
@@ -256,6 +300,9 @@ pub enum Node {
     /// A yield expression
     Yield(AstIndex),
 
+    /// An await expression
+    Await(AstIndex),
+
     /// A debug expression
     Debug {
         /// The stored string of the debugged expression to be used when printing the result
@@ -272,6 +319,7 @@ impl fmt::Display for Node {
             Null => write!(f, "Null"),
             Nested(_) => write!(f, "Nested"),
             Id(_) => write!(f, "Id"),
+            Type(_) => write!(f, "Type"),
             Meta(_, _) => write!(f, "Meta"),
             Lookup(_) => write!(f, "Lookup"),
             BoolTrue => write!(f, "BoolTrue"),
@@ -287,6 +335,7 @@ impl fmt::Display for Node {
             RangeFrom { .. } => write!(f, "RangeFrom"),
             RangeTo { .. } => write!(f, "RangeTo"),
             RangeFull => write!(f, "RangeFull"),
+            RangeStep { .. } => write!(f, "RangeStep"),
             Map(_) => write!(f, "Map"),
             MainBlock { .. } => write!(f, "MainBlock"),
             Block(_) => write!(f, "Block"),
@@ -294,6 +343,9 @@ impl fmt::Display for Node {
             NamedCall { .. } => write!(f, "NamedCall"),
             Import { .. } => write!(f, "Import"),
             Export(_) => write!(f, "Export"),
+            Const(_) => write!(f, "Const"),
+            Let(_) => write!(f, "Let"),
+            Var(_) => write!(f, "Var"),
             Assign { .. } => write!(f, "Assign"),
             MultiAssign { .. } => write!(f, "MultiAssign"),
             UnaryOp { .. } => write!(f, "UnaryOp"),
@@ -306,6 +358,7 @@ impl fmt::Display for Node {
             Ellipsis(_) => write!(f, "Ellipsis"),
             For(_) => write!(f, "For"),
             While { .. } => write!(f, "While"),
+            With { .. } => write!(f, "With"),
             Until { .. } => write!(f, "Until"),
             Loop { .. } => write!(f, "Loop"),
             Break(_) => write!(f, "Break"),
@@ -314,6 +367,7 @@ impl fmt::Display for Node {
             Try { .. } => write!(f, "Try"),
             Throw(_) => write!(f, "Throw"),
             Yield { .. } => write!(f, "Yield"),
+            Await { .. } => write!(f, "Await"),
             Debug { .. } => write!(f, "Debug"),
         }
     }
@@ -324,6 +378,13 @@ impl fmt::Display for Node {
 pub struct Function {
     /// The function's arguments
     pub args: Vec<AstIndex>,
+    /// The type hints for the function's arguments
+    ///
+    /// There's an entry for each argument in `args`, with `None` used for arguments that don't
+    /// have a type hint.
+    pub arg_types: Vec<Option<AstIndex>>,
+    /// The function's output type hint, e.g. `String` in `|x| -> String`
+    pub output_type: Option<AstIndex>,
     /// The number of locally assigned values
     ///
     /// Used by the compiler when reserving registers for local values at the start of the frame.
@@ -440,6 +501,7 @@ pub enum AstBinaryOp {
     GreaterOrEqual,
     And,
     Or,
+    NullCoalesce,
     Pipe,
 }
 
@@ -494,6 +556,11 @@ pub enum LookupNode {
         ///   `99 >> foo.bar(42)` is equivalent to `foo.bar(42)(99)`.
         with_parens: bool,
     },
+    /// A null check, `?`, used in optional chaining, e.g. `x?.foo`, `x?[0]`, `f?()`
+    ///
+    /// If the preceding value in the chain is null,
+    /// then the rest of the lookup chain is skipped and the result is null.
+    NullCheck,
 }
 
 /// An arm in a match expression
@@ -576,6 +643,8 @@ pub enum MetaKeyId {
     NotEqual,
     /// @[]
     Index,
+    /// @[]=
+    SetIndex,
 
     /// @display
     Display,
@@ -589,6 +658,12 @@ pub enum MetaKeyId {
     Negate,
     /// @size
     Size,
+    /// @hash
+    Hash,
+    /// @access
+    Access,
+    /// @close
+    Close,
     /// @type
     Type,
     /// @base
@@ -608,6 +683,8 @@ pub enum MetaKeyId {
 
     /// @main
     Main,
+    /// @reload
+    Reload,
 
     /// @meta name
     Named,
@@ -29,12 +29,29 @@ struct Frame {
     // non-local accesses.
     pending_accesses: HashSet<ConstantIndex>,
     pending_assignments: HashSet<ConstantIndex>,
+    // The number of ids declared with `let` or `var`
+    //
+    // `let` and `var` declarations are block-scoped and can shadow other locals in the frame,
+    // so each declaration needs its own local register.
+    let_declaration_count: usize,
+    // IDs that have been declared with `var`
+    //
+    // Assigning to one of these IDs in a nested frame modifies the `var` rather than
+    // assigning a new local value, see `Parser::assignment_refers_to_parent_var`.
+    var_ids: HashSet<ConstantIndex>,
+    // True while the assignment in a `let`, `const`, or `var` declaration is being parsed
+    parsing_declaration: bool,
 }
 
 impl Frame {
     // The number of local values declared within the frame
     fn local_count(&self) -> usize {
-        self.ids_assigned_in_frame.len()
+        self.ids_assigned_in_frame.len() + self.let_declaration_count
+    }
+
+    // Returns true if the id has been assigned in the frame, or is currently being assigned
+    fn is_local(&self, id: ConstantIndex) -> bool {
+        self.ids_assigned_in_frame.contains(&id) || self.pending_assignments.contains(&id)
     }
 
     // Non-locals accessed in a nested frame need to be declared as also accessed in this
@@ -352,9 +369,11 @@ impl<'source> Parser<'source> {
             self.consume_until_token_with_context(&block_context);
         }
 
-        // If the block is a single expression then it doesn't need to be wrapped in a Block node
-        if block.len() == 1 {
-            Ok(Some(*block.first().unwrap()))
+        // If the block is a single expression then it doesn't need to be wrapped in a Block node,
+        // unless it's a `let` or `var` declaration, which needs the block to define its scope.
+        if block.len() == 1 && !matches!(self.ast.node(block[0]).node, Node::Let(_) | Node::Var(_))
+        {
+            Ok(Some(block[0]))
         } else {
             self.push_node_with_start_span(Node::Block(block), start_span)
                 .map(Some)
@@ -624,6 +643,7 @@ impl<'source> Parser<'source> {
 
                         And => AstBinaryOp::And,
                         Or => AstBinaryOp::Or,
+                        NullCoalesce => AstBinaryOp::NullCoalesce,
 
                         Greater if maybe_pipe.is_some() => AstBinaryOp::Pipe,
 
@@ -679,7 +699,12 @@ impl<'source> Parser<'source> {
             // Note which identifiers are being assigned to
             match self.ast.node(*lhs_expression).node.clone() {
                 Node::Id(id_index) => {
-                    self.frame_mut()?.add_local_id_assignment(id_index);
+                    if self.assignment_refers_to_parent_var(id_index) {
+                        // The `var` will be captured, with the assignment modifying its value
+                        self.frame_mut()?.add_id_access(id_index);
+                    } else {
+                        self.frame_mut()?.add_local_id_assignment(id_index);
+                    }
                 }
                 Node::Meta { .. } | Node::Lookup(_) | Node::Wildcard(_) => {}
                 _ => return self.error(SyntaxError::ExpectedAssignmentTarget),
@@ -843,9 +868,22 @@ impl<'source> Parser<'source> {
                     self.consume_token_and_error(SyntaxError::ExpectedExpression)
                 }
             }
+            Token::Await => {
+                self.consume_token_with_context(context);
+                if let Some(expression) = self.parse_expression(&ExpressionContext {
+                    allow_space_separated_call: true,
+                    expected_indentation: Indentation::Greater,
+                    ..*context
+                })? {
+                    self.push_node(Node::Await(expression))
+                } else {
+                    self.consume_token_and_error(SyntaxError::ExpectedExpression)
+                }
+            }
             Token::Loop => self.consume_loop_block(context),
             Token::For => self.consume_for_loop(context),
             Token::While => self.consume_while_loop(context),
+            Token::With => self.consume_with_block(context),
             Token::Until => self.consume_until_loop(context),
             Token::Break => {
                 self.consume_token_with_context(context);
@@ -867,11 +905,10 @@ impl<'source> Parser<'source> {
             Token::Debug => self.consume_debug_expression(),
             Token::From | Token::Import => self.consume_import(context),
             Token::Export => self.consume_export(context),
+            Token::Const => self.consume_const(context),
+            Token::Let => self.consume_let(context),
+            Token::Var => self.consume_var(context),
             Token::Try => self.consume_try_expression(context),
-            // Reserved keywords
-            Token::Await => self.consume_token_and_error(SyntaxError::ReservedKeyword),
-            Token::Const => self.consume_token_and_error(SyntaxError::ReservedKeyword),
-            Token::Let => self.consume_token_and_error(SyntaxError::ReservedKeyword),
             // An error occurred in the lexer
             Token::Error => self.consume_token_and_error(SyntaxError::LexerError),
             _ => return Ok(None),
@@ -894,6 +931,7 @@ impl<'source> Parser<'source> {
 
         // Parse function's args
         let mut arg_nodes = Vec::new();
+        let mut arg_types = Vec::new();
         let mut arg_ids = Vec::new();
         let mut is_variadic = false;
 
@@ -909,12 +947,20 @@ impl<'source> Parser<'source> {
 
                     if self.peek_token() == Some(Token::Ellipsis) {
                         self.consume_token();
+                        arg_types.push(None);
                         is_variadic = true;
                         break;
                     }
+
+                    if self.peek_next_token_on_same_line() == Some(Token::Colon) {
+                        arg_types.push(Some(self.consume_type_hint()?));
+                    } else {
+                        arg_types.push(None);
+                    }
                 }
                 Some(IdOrWildcard::Wildcard(maybe_id)) => {
-                    arg_nodes.push(self.push_node(Node::Wildcard(maybe_id))?)
+                    arg_nodes.push(self.push_node(Node::Wildcard(maybe_id))?);
+                    arg_types.push(None);
                 }
                 None => match self.peek_token() {
                     Some(Token::Self_) => {
@@ -936,6 +982,7 @@ impl<'source> Parser<'source> {
                             Node::Tuple(tuple_args),
                             nested_span_start,
                         )?);
+                        arg_types.push(None);
                     }
                     _ => break,
                 },
@@ -958,6 +1005,12 @@ impl<'source> Parser<'source> {
             return self.error(SyntaxError::ExpectedFunctionArgsEnd);
         }
 
+        let output_type = if self.peek_next_token_on_same_line() == Some(Token::Arrow) {
+            Some(self.consume_type_hint()?)
+        } else {
+            None
+        };
+
         // body
         let mut function_frame = Frame::default();
         function_frame.ids_assigned_in_frame.extend(arg_ids.iter());
@@ -989,6 +1042,8 @@ impl<'source> Parser<'source> {
         self.ast.push(
             Node::Function(Function {
                 args: arg_nodes,
+                arg_types,
+                output_type,
                 local_count,
                 accessed_non_locals: Vec::from_iter(function_frame.accessed_non_locals),
                 body,
@@ -1002,6 +1057,24 @@ impl<'source> Parser<'source> {
         )
     }
 
+    // Parses a type hint following a ':' or '->'
+    //
+    // e.g.
+    //   f = |x: Number| -> String
+    //   #     ^ You are here
+    //   #               ^ ...or here
+    fn consume_type_hint(&mut self) -> Result<AstIndex> {
+        self.consume_next_token_on_same_line(); // Token::Colon or Token::Arrow
+
+        match self.consume_next_token_on_same_line() {
+            Some(Token::Id) => {
+                let type_index = self.add_current_slice_as_string_constant()?;
+                self.push_node(Node::Type(type_index))
+            }
+            _ => self.error(SyntaxError::ExpectedType),
+        }
+    }
+
     // Helper for parse_function() that recursively parses nested function arguments
     // e.g.
     //   f = |(foo, bar, (x, y))|
@@ -1263,12 +1336,15 @@ impl<'source> Parser<'source> {
     fn next_token_is_lookup_start(&mut self, context: &ExpressionContext) -> bool {
         use Token::*;
 
-        if matches!(self.peek_token(), Some(Dot | SquareOpen | RoundOpen)) {
+        if matches!(
+            self.peek_token(),
+            Some(Dot | SquareOpen | RoundOpen | Question)
+        ) {
             true
         } else if context.allow_linebreaks {
             matches!(
                 self.peek_token_with_context(context),
-                Some(peeked) if peeked.token == Dot
+                Some(peeked) if matches!(peeked.token, Dot | Question)
             )
         } else {
             false
@@ -1328,6 +1404,19 @@ impl<'source> Parser<'source> {
                         return self.error(SyntaxError::ExpectedIndexEnd);
                     }
                 }
+                // Optional chaining, e.g. `x?.foo`, `x?[0]`, `f?()`
+                Token::Question => {
+                    self.consume_token();
+
+                    if !matches!(
+                        self.peek_token(),
+                        Some(Token::Dot | Token::SquareOpen | Token::RoundOpen)
+                    ) {
+                        return self.consume_token_and_error(SyntaxError::ExpectedOptionalLookup);
+                    }
+
+                    lookup.push((LookupNode::NullCheck, node_start_span));
+                }
                 // Map access
                 Token::Dot => {
                     self.consume_token();
@@ -1352,7 +1441,7 @@ impl<'source> Parser<'source> {
                     let Some(peeked) = self.peek_token_with_context(&node_context) else {
                         break;
                     };
-                    if peeked.token == Token::Dot {
+                    if matches!(peeked.token, Token::Dot | Token::Question) {
                         // Indented Dot on a following line?
 
                         // Consume up until the Dot,
@@ -1470,31 +1559,26 @@ impl<'source> Parser<'source> {
                 _ => index_expression,
             }
         } else {
-            // Look for RangeTo/RangeFull
-            // e.g. x[..10], y[..]
-            match self.consume_next_token_on_same_line() {
-                Some(Token::Range) => {
-                    if let Some(end_expression) = self.parse_expression(&index_context)? {
-                        self.push_node(Node::RangeTo {
-                            end: end_expression,
-                            inclusive: false,
-                        })?
-                    } else {
-                        self.push_node(Node::RangeFull)?
-                    }
-                }
-                Some(Token::RangeInclusive) => {
-                    if let Some(end_expression) = self.parse_expression(&index_context)? {
-                        self.push_node(Node::RangeTo {
-                            end: end_expression,
-                            inclusive: true,
-                        })?
-                    } else {
-                        self.push_node(Node::RangeFull)?
-                    }
-                }
+            // Look for RangeTo/RangeFull, with an optional step
+            // e.g. x[..10], y[..], z[....-1]
+            let inclusive = match self.consume_next_token_on_same_line() {
+                Some(Token::Range) => false,
+                Some(Token::RangeInclusive) => true,
                 _ => return self.error(SyntaxError::ExpectedIndexExpression),
-            }
+            };
+
+            let end_expression = if self.peek_token() == Some(Token::Range) {
+                None
+            } else {
+                self.parse_expression_start(&[], 0, &index_context)?
+            };
+
+            let range = match end_expression {
+                Some(end) => self.push_node(Node::RangeTo { end, inclusive })?,
+                None => self.push_node(Node::RangeFull)?,
+            };
+
+            self.consume_range_step(range, &index_context)?
         };
 
         Ok(result)
@@ -1562,7 +1646,13 @@ impl<'source> Parser<'source> {
             start_span = self.current_span();
         }
 
-        let rhs = self.parse_expression(&ExpressionContext::inline())?;
+        // The end of the range is parsed without checking for a following range, which would
+        // introduce the range's step, e.g. `0..10..2`.
+        let rhs = if self.peek_next_token_on_same_line() == Some(Token::Range) {
+            None
+        } else {
+            self.parse_expression_start(&[], 0, &ExpressionContext::inline())?
+        };
 
         let range_node = match (lhs, rhs) {
             (Some(start), Some(end)) => Range {
@@ -1576,9 +1666,36 @@ impl<'source> Parser<'source> {
         };
 
         let range_node = self.push_node_with_start_span(range_node, start_span)?;
+        let range_node = self.consume_range_step(range_node, &ExpressionContext::inline())?;
         self.check_for_lookup_after_node(range_node, context)
     }
 
+    // Parses an optional step following a range
+    //
+    // e.g.
+    //   x = 0..10..2
+    //            ^ You are here
+    fn consume_range_step(
+        &mut self,
+        range: AstIndex,
+        context: &ExpressionContext,
+    ) -> Result<AstIndex> {
+        if self.peek_next_token_on_same_line() != Some(Token::Range) {
+            return Ok(range);
+        }
+
+        self.consume_next_token_on_same_line(); // Token::Range
+
+        let start_span = *self.ast.span(self.ast.node(range).span);
+
+        match self.parse_expression_start(&[], 0, context)? {
+            Some(step) => {
+                self.push_node_with_start_span(Node::RangeStep { range, step }, start_span)
+            }
+            None => self.consume_token_and_error(SyntaxError::ExpectedRangeStep),
+        }
+    }
+
     fn consume_export(&mut self, context: &ExpressionContext) -> Result<AstIndex> {
         self.consume_token_with_context(context); // Token::Export
 
@@ -1591,6 +1708,108 @@ impl<'source> Parser<'source> {
         self.push_node_with_start_span(Node::Export(expression), start_span)
     }
 
+    // Parses a constant declaration
+    //
+    // e.g.
+    //   const x = 42
+    //   ^ You are here
+    fn consume_const(&mut self, context: &ExpressionContext) -> Result<AstIndex> {
+        self.consume_token_with_context(context); // Token::Const
+
+        let start_span = self.current_span();
+
+        self.frame_mut()?.parsing_declaration = true;
+        let expression = self.parse_expression(&ExpressionContext::permissive());
+        self.frame_mut()?.parsing_declaration = false;
+
+        let Some(expression) = expression? else {
+            return self.consume_token_and_error(SyntaxError::ExpectedConstAssignment);
+        };
+
+        match &self.ast.node(expression).node {
+            Node::Assign { target, .. } if matches!(self.ast.node(*target).node, Node::Id(_)) => {}
+            _ => return self.error(SyntaxError::ExpectedConstAssignment),
+        }
+
+        self.push_node_with_start_span(Node::Const(expression), start_span)
+    }
+
+    // Parses a block-scoped declaration
+    //
+    // e.g.
+    //   let x = 42
+    //   ^ You are here
+    fn consume_let(&mut self, context: &ExpressionContext) -> Result<AstIndex> {
+        self.consume_token_with_context(context); // Token::Let
+
+        let start_span = self.current_span();
+        let expression = self.parse_declaration(SyntaxError::ExpectedLetAssignment)?;
+
+        self.push_node_with_start_span(Node::Let(expression), start_span)
+    }
+
+    // Parses a block-scoped declaration of values that are captured by reference
+    //
+    // e.g.
+    //   var x = 42
+    //   ^ You are here
+    fn consume_var(&mut self, context: &ExpressionContext) -> Result<AstIndex> {
+        self.consume_token_with_context(context); // Token::Var
+
+        let start_span = self.current_span();
+        let expression = self.parse_declaration(SyntaxError::ExpectedVarAssignment)?;
+
+        let targets = match &self.ast.node(expression).node {
+            Node::Assign { target, .. } => vec![*target],
+            Node::MultiAssign { targets, .. } => targets.clone(),
+            _ => vec![],
+        };
+        for target in targets {
+            if let Node::Id(id) = self.ast.node(target).node {
+                self.frame_mut()?.var_ids.insert(id);
+            }
+        }
+
+        self.push_node_with_start_span(Node::Var(expression), start_span)
+    }
+
+    // Parses the assignment that follows `let` or `var`
+    //
+    // The assignment targets must be IDs (or wildcards in multi-assignments), with each ID
+    // being given its own local register.
+    fn parse_declaration(&mut self, error: SyntaxError) -> Result<AstIndex> {
+        self.frame_mut()?.parsing_declaration = true;
+        let expression = self.parse_expressions(&ExpressionContext::permissive(), TempResult::No);
+        self.frame_mut()?.parsing_declaration = false;
+
+        let Some(expression) = expression? else {
+            return self.consume_token_and_error(error);
+        };
+
+        let declared_ids = match &self.ast.node(expression).node {
+            Node::Assign { target, .. } => match self.ast.node(*target).node {
+                Node::Id(_) => 1,
+                _ => return self.error(error),
+            },
+            Node::MultiAssign { targets, .. } => {
+                let mut declared_ids = 0;
+                for target in targets.iter() {
+                    match self.ast.node(*target).node {
+                        Node::Id(_) => declared_ids += 1,
+                        Node::Wildcard(_) => {}
+                        _ => return self.error(error),
+                    }
+                }
+                declared_ids
+            }
+            _ => return self.error(error),
+        };
+
+        self.frame_mut()?.let_declaration_count += declared_ids;
+
+        Ok(expression)
+    }
+
     fn consume_throw_expression(&mut self) -> Result<AstIndex> {
         self.consume_next_token_on_same_line(); // Token::Throw
 
@@ -1966,9 +2185,13 @@ impl<'source> Parser<'source> {
                 "next_back" => MetaKeyId::NextBack,
                 "negate" => MetaKeyId::Negate,
                 "size" => MetaKeyId::Size,
+                "hash" => MetaKeyId::Hash,
+                "access" => MetaKeyId::Access,
+                "close" => MetaKeyId::Close,
                 "type" => MetaKeyId::Type,
                 "base" => MetaKeyId::Base,
                 "main" => MetaKeyId::Main,
+                "reload" => MetaKeyId::Reload,
                 "tests" => MetaKeyId::Tests,
                 "pre_test" => MetaKeyId::PreTest,
                 "post_test" => MetaKeyId::PostTest,
@@ -1991,7 +2214,13 @@ impl<'source> Parser<'source> {
                 _ => return self.error(SyntaxError::UnexpectedMetaKey),
             },
             Some(Token::SquareOpen) => match self.consume_token() {
-                Some(Token::SquareClose) => MetaKeyId::Index,
+                Some(Token::SquareClose) => match self.peek_token() {
+                    Some(Token::Assign) => {
+                        self.consume_token();
+                        MetaKeyId::SetIndex
+                    }
+                    _ => MetaKeyId::Index,
+                },
                 _ => return self.error(SyntaxError::UnexpectedMetaKey),
             },
             Some(Token::Function) => match self.consume_token() {
@@ -2082,6 +2311,19 @@ impl<'source> Parser<'source> {
         }
     }
 
+    fn consume_with_block(&mut self, context: &ExpressionContext) -> Result<AstIndex> {
+        self.consume_token_with_context(context); // Token::With
+
+        let Some(resource) = self.parse_expression(&ExpressionContext::inline())? else {
+            return self.consume_token_and_error(SyntaxError::ExpectedWithResource);
+        };
+
+        match self.parse_indented_block()? {
+            Some(body) => self.push_node(Node::With { resource, body }),
+            None => self.consume_token_and_error(ExpectedIndentation::WithBody),
+        }
+    }
+
     fn consume_until_loop(&mut self, context: &ExpressionContext) -> Result<AstIndex> {
         self.consume_token_with_context(context); // Token::Until
 
@@ -3176,6 +3418,31 @@ impl<'source> Parser<'source> {
         None
     }
 
+    // Returns true if assigning to the id should modify a `var` that was declared in a parent frame
+    //
+    // The assignment is then treated as an access of the `var`, so that it gets captured by
+    // reference rather than being shadowed by a new local value.
+    fn assignment_refers_to_parent_var(&self, id: ConstantIndex) -> bool {
+        let Some((frame, parent_frames)) = self.frame_stack.split_last() else {
+            return false;
+        };
+
+        if frame.parsing_declaration || frame.is_local(id) {
+            return false;
+        }
+
+        for parent in parent_frames.iter().rev() {
+            if parent.var_ids.contains(&id) {
+                return true;
+            }
+            if parent.is_local(id) {
+                return false;
+            }
+        }
+
+        false
+    }
+
     fn frame(&self) -> Result<&Frame> {
         match self.frame_stack.last() {
             Some(frame) => Ok(frame),
@@ -3220,6 +3487,7 @@ fn operator_precedence(op: Token) -> Option<(u8, u8)> {
         AddAssign | SubtractAssign | MultiplyAssign | DivideAssign | RemainderAssign => {
             (4, MIN_PRECEDENCE_AFTER_PIPE)
         }
+        NullCoalesce => (5, 6),
         Or => (7, 8),
         And => (9, 10),
         // Chained comparisons require right-associativity
@@ -16,6 +16,12 @@ pub struct StringFormatOptions {
     pub precision: Option<u32>,
     /// The character that padded strings should use to fill empty space
     pub fill_character: Option<ConstantIndex>,
+    /// Set to true when a `+` sign should be shown for positive numbers
+    pub show_sign: bool,
+    /// Set to true when the integer part of numbers should use `,` as a thousands separator
+    pub thousands_separator: bool,
+    /// The representation that should be used when formatting numbers
+    pub representation: Option<StringFormatRepresentation>,
 }
 
 impl StringFormatOptions {
@@ -24,76 +30,105 @@ impl StringFormatOptions {
         format_string: &str,
         constants: &mut ConstantPoolBuilder,
     ) -> Result<Self, StringFormatError> {
-        use FormatParsePosition::*;
-        let mut position = Start;
+        let (mut result, fill) = Self::parse_with_fill(format_string)?;
+
+        if let Some(fill) = fill {
+            result.fill_character = Some(
+                constants
+                    .add_string(fill)
+                    .map_err(|_| StringFormatError::InternalError)?,
+            );
+        }
+
+        Ok(result)
+    }
+
+    /// Parses a format string, returning the fill character separately from the options
+    ///
+    /// The returned options don't have a `fill_character` constant, which allows format strings
+    /// to be parsed at runtime without a constant pool.
+    ///
+    /// The format string syntax is `[[fill]align][+][0][width][,][.precision][representation]`.
+    pub fn parse_with_fill(format_string: &str) -> Result<(Self, Option<&str>), StringFormatError> {
         let mut result = Self::default();
-        let mut chars = format_string.chars().peekable();
+        let mut fill = None;
 
         let char_to_alignment = |c: char| match c {
-            '<' => StringAlignment::Left,
-            '^' => StringAlignment::Center,
-            '>' => StringAlignment::Right,
-            _ => unreachable!(),
+            '<' => Some(StringAlignment::Left),
+            '^' => Some(StringAlignment::Center),
+            '>' => Some(StringAlignment::Right),
+            _ => None,
         };
 
-        let mut add_string_constant = |s: &str| {
-            constants
-                .add_string(s)
-                .map_err(|_| StringFormatError::InternalError)
+        // Check for an optional fill grapheme followed by alignment
+        let mut graphemes = format_string.graphemes(true);
+        let remaining = match (graphemes.next(), graphemes.next()) {
+            (Some(first), Some(second)) if second.len() == 1 => {
+                match char_to_alignment(second.chars().next().unwrap()) {
+                    Some(alignment) => {
+                        fill = Some(first);
+                        result.alignment = alignment;
+                        &format_string[first.len() + 1..]
+                    }
+                    None => format_string,
+                }
+            }
+            _ => format_string,
         };
 
-        while let Some(next) = chars.next() {
-            match (next, chars.peek(), position) {
-                // Check for single-char fill character at the start of the string
-                (_, Some('<' | '^' | '>'), Start) => {
-                    result.fill_character =
-                        Some(add_string_constant(&format_string[0..next.len_utf8()])?);
-                    result.alignment = char_to_alignment(chars.next().unwrap());
-                    position = MinWidth;
-                }
-                ('<' | '^' | '>', _, Start | Alignment) => {
-                    result.alignment = char_to_alignment(next);
-                    position = MinWidth;
-                }
-                ('0', Some('0'..='9'), Start | MinWidth) => {
-                    result.fill_character = Some(add_string_constant("0")?);
-                    position = MinWidth;
-                }
-                ('0'..='9', _, Start | MinWidth) => {
-                    result.min_width = Some(consume_u32(next, &mut chars)?);
-                    position = Precision;
-                }
-                ('.', Some(_), Start | MinWidth | Precision) => {
-                    let first_digit = chars.next().unwrap();
+        let mut chars = remaining.chars().peekable();
+
+        if fill.is_none() {
+            if let Some(alignment) = chars.peek().and_then(|c| char_to_alignment(*c)) {
+                chars.next();
+                result.alignment = alignment;
+            }
+        }
+
+        if chars.next_if_eq(&'+').is_some() {
+            result.show_sign = true;
+        }
+
+        if chars.peek() == Some(&'0') {
+            chars.next();
+            match chars.peek() {
+                Some('0'..='9') => fill = Some("0"),
+                // A single '0' is a min width of 0
+                _ => result.min_width = Some(0),
+            }
+        }
+
+        if let Some(first_digit @ '0'..='9') = chars.peek().cloned() {
+            chars.next();
+            result.min_width = Some(consume_u32(first_digit, &mut chars)?);
+        }
+
+        if chars.next_if_eq(&',').is_some() {
+            result.thousands_separator = true;
+        }
+
+        if chars.next_if_eq(&'.').is_some() {
+            match chars.next() {
+                Some(first_digit) => {
                     result.precision = Some(consume_u32(first_digit, &mut chars)?);
-                    position = End;
-                }
-                (_, _, Start) => {
-                    // Unwrapping here is fine, format_string is valid UTF-8
-                    let fill = format_string.graphemes(true).next().unwrap();
-                    // The fill grapheme cluster can only appear at the start of the format string
-                    chars = format_string[fill.len()..].chars().peekable();
-                    result.fill_character = Some(add_string_constant(fill)?);
-                    position = Alignment;
-                }
-                (other, _, _) => {
-                    return Err(StringFormatError::UnexpectedToken(other));
                 }
+                None => return Err(StringFormatError::ExpectedPrecision),
             }
         }
 
-        Ok(result)
-    }
-}
+        if let Some(c) = chars.next() {
+            result.representation = Some(
+                StringFormatRepresentation::from_char(c)
+                    .ok_or(StringFormatError::UnexpectedToken(c))?,
+            );
+        }
+
+        if let Some(unexpected) = chars.next() {
+            return Err(StringFormatError::UnexpectedToken(unexpected));
+        }
 
-// Used during parsing of a format string, see [StringFormatOptions::parse]
-#[derive(Copy, Clone, Debug)]
-enum FormatParsePosition {
-    Start,
-    Alignment,
-    MinWidth,
-    Precision,
-    End,
+        Ok((result, fill))
+    }
 }
 
 fn consume_u32(first: char, chars: &mut Peekable<Chars>) -> Result<u32, StringFormatError> {
@@ -118,6 +153,59 @@ fn consume_u32(first: char, chars: &mut Peekable<Chars>) -> Result<u32, StringFo
     Ok(n as u32)
 }
 
+/// Representation options for formatted numbers
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum StringFormatRepresentation {
+    /// `x`, lowercase hexadecimal
+    HexLower,
+    /// `X`, uppercase hexadecimal
+    HexUpper,
+    /// `b`, binary
+    Binary,
+    /// `o`, octal
+    Octal,
+    /// `e`, scientific notation with a lowercase exponent
+    ExpLower,
+    /// `E`, scientific notation with an uppercase exponent
+    ExpUpper,
+}
+
+impl StringFormatRepresentation {
+    /// Returns the representation that matches the given format character
+    pub fn from_char(c: char) -> Option<Self> {
+        use StringFormatRepresentation::*;
+
+        let result = match c {
+            'x' => HexLower,
+            'X' => HexUpper,
+            'b' => Binary,
+            'o' => Octal,
+            'e' => ExpLower,
+            'E' => ExpUpper,
+            _ => return None,
+        };
+
+        Some(result)
+    }
+
+    /// Returns the representation that matches the given byte
+    ///
+    /// See [StringFormatRepresentation::as_byte].
+    pub fn from_byte(byte: u8) -> Option<Self> {
+        use StringFormatRepresentation::*;
+
+        [HexLower, HexUpper, Binary, Octal, ExpLower, ExpUpper]
+            .into_iter()
+            .find(|representation| *representation as u8 == byte)
+    }
+
+    /// Returns the representation as a byte, used when serializing format options
+    pub fn as_byte(&self) -> u8 {
+        *self as u8
+    }
+}
+
 /// Alignment options for formatted strings
 #[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
 #[allow(missing_docs)]
@@ -137,6 +225,8 @@ pub enum StringAlignment {
 pub enum StringFormatError {
     #[error("Expected a number '{0}'")]
     ExpectedNumber(char),
+    #[error("Expected a precision value after '.'")]
+    ExpectedPrecision,
     #[error("{0} is larger than the maximum of {}", u32::MAX)]
     FormatNumberIsTooLarge(u64),
     #[error("An unexpected internal error occurred")]
@@ -222,6 +312,7 @@ mod tests {
                     fill_character: Some(0),
                     min_width: Some(20),
                     precision: Some(10),
+                    ..Default::default()
                 },
             ),
             (
@@ -252,4 +343,64 @@ mod tests {
             ),
         ])
     }
+
+    #[test]
+    fn sign_separator_and_representation() {
+        test_parse_format_string(&[
+            (
+                "+",
+                StringFormatOptions {
+                    show_sign: true,
+                    ..Default::default()
+                },
+            ),
+            (
+                "+08,.2",
+                StringFormatOptions {
+                    fill_character: Some(0),
+                    min_width: Some(8),
+                    precision: Some(2),
+                    show_sign: true,
+                    thousands_separator: true,
+                    ..Default::default()
+                },
+            ),
+            (
+                "x",
+                StringFormatOptions {
+                    representation: Some(StringFormatRepresentation::HexLower),
+                    ..Default::default()
+                },
+            ),
+            (
+                "_>10b",
+                StringFormatOptions {
+                    alignment: StringAlignment::Right,
+                    fill_character: Some(0),
+                    min_width: Some(10),
+                    representation: Some(StringFormatRepresentation::Binary),
+                    ..Default::default()
+                },
+            ),
+            (
+                ".3E",
+                StringFormatOptions {
+                    precision: Some(3),
+                    representation: Some(StringFormatRepresentation::ExpUpper),
+                    ..Default::default()
+                },
+            ),
+        ])
+    }
+
+    #[test]
+    fn invalid_format_strings() {
+        for format_string in ["+-", "10z", ".", "x1", ",,"] {
+            let mut constants = ConstantPoolBuilder::default();
+            assert!(
+                StringFormatOptions::parse(format_string, &mut constants).is_err(),
+                "Expected an error for '{format_string}'"
+            );
+        }
+    }
 }
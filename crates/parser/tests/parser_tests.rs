@@ -296,6 +296,7 @@ null"#;
                                     min_width: Some(3),
                                     precision: Some(2),
                                     fill_character: Some(2),
+                                    ..Default::default()
                                 },
                             },
                             StringNode::Literal(0),
@@ -843,6 +844,36 @@ x =
             )
         }
 
+        #[test]
+        fn map_block_index_meta_keys() {
+            let source = r#"
+x =
+  @[]: 0
+  @[]=: 1
+"#;
+            check_ast(
+                source,
+                &[
+                    Id(0), // x
+                    SmallInt(0),
+                    SmallInt(1),
+                    Map(vec![
+                        (MapKey::Meta(MetaKeyId::Index, None), Some(1)),
+                        (MapKey::Meta(MetaKeyId::SetIndex, None), Some(2)),
+                    ]),
+                    Assign {
+                        target: 0,
+                        expression: 3,
+                    },
+                    MainBlock {
+                        body: vec![4],
+                        local_count: 1,
+                    },
+                ],
+                Some(&[Constant::Str("x")]),
+            )
+        }
+
         #[test]
         fn assigning_map_to_meta_key() {
             let source = r#"
@@ -984,6 +1015,36 @@ min..max
             )
         }
 
+        #[test]
+        fn range_with_step() {
+            let source = "
+0..10..2
+0....-1";
+            check_ast(
+                source,
+                &[
+                    SmallInt(0),
+                    SmallInt(10),
+                    Range {
+                        start: 0,
+                        end: 1,
+                        inclusive: false,
+                    },
+                    SmallInt(2),
+                    RangeStep { range: 2, step: 3 },
+                    SmallInt(0), // 5
+                    RangeFrom { start: 5 },
+                    SmallInt(-1),
+                    RangeStep { range: 6, step: 7 },
+                    MainBlock {
+                        body: vec![4, 8],
+                        local_count: 0,
+                    },
+                ],
+                None,
+            )
+        }
+
         #[test]
         fn range_from_lookups() {
             let source = "foo.bar..foo.baz";
@@ -1472,6 +1533,250 @@ export
                 Some(&[Constant::Str("a"), Constant::Str("b")]),
             )
         }
+
+        #[test]
+        fn export_const() {
+            let source = "export const a = 42";
+
+            check_ast(
+                source,
+                &[
+                    Id(0),
+                    SmallInt(42),
+                    Assign {
+                        target: 0,
+                        expression: 1,
+                    },
+                    Const(2),
+                    Export(3),
+                    MainBlock {
+                        body: vec![4],
+                        local_count: 1,
+                    },
+                ],
+                Some(&[Constant::Str("a")]),
+            )
+        }
+    }
+
+    mod constants {
+        use super::*;
+
+        #[test]
+        fn const_assignment() {
+            let source = "
+const x = 'hello'
+x
+";
+
+            check_ast(
+                source,
+                &[
+                    Id(0),
+                    string_literal(1, StringQuote::Single),
+                    Assign {
+                        target: 0,
+                        expression: 1,
+                    },
+                    Const(2),
+                    Id(0),
+                    MainBlock {
+                        body: vec![3, 4],
+                        local_count: 1,
+                    },
+                ],
+                Some(&[Constant::Str("x"), Constant::Str("hello")]),
+            )
+        }
+    }
+
+    mod let_declarations {
+        use super::*;
+
+        #[test]
+        fn single_declaration() {
+            let source = "
+let x = 1
+";
+
+            check_ast(
+                source,
+                &[
+                    Id(0),
+                    SmallInt(1),
+                    Assign {
+                        target: 0,
+                        expression: 1,
+                    },
+                    Let(2),
+                    MainBlock {
+                        body: vec![3],
+                        local_count: 2, // The let declaration gets its own local register
+                    },
+                ],
+                Some(&[Constant::Str("x")]),
+            )
+        }
+
+        #[test]
+        fn multi_declaration() {
+            let source = "
+let a, _, b = foo()
+";
+
+            check_ast(
+                source,
+                &[
+                    Id(0),
+                    Wildcard(None),
+                    Id(1),
+                    Id(2),
+                    Lookup((
+                        LookupNode::Call {
+                            args: vec![],
+                            with_parens: true,
+                        },
+                        None,
+                    )),
+                    Lookup((LookupNode::Root(3), Some(4))), // 5
+                    MultiAssign {
+                        targets: vec![0, 1, 2],
+                        expression: 5,
+                    },
+                    Let(6),
+                    MainBlock {
+                        body: vec![7],
+                        local_count: 4,
+                    },
+                ],
+                Some(&[Constant::Str("a"), Constant::Str("b"), Constant::Str("foo")]),
+            )
+        }
+    }
+
+    mod var_declarations {
+        use super::*;
+
+        #[test]
+        fn single_declaration() {
+            let source = "
+var x = 1
+";
+
+            check_ast(
+                source,
+                &[
+                    Id(0),
+                    SmallInt(1),
+                    Assign {
+                        target: 0,
+                        expression: 1,
+                    },
+                    Var(2),
+                    MainBlock {
+                        body: vec![3],
+                        local_count: 2,
+                    },
+                ],
+                Some(&[Constant::Str("x")]),
+            )
+        }
+
+        #[test]
+        fn assignment_in_nested_function() {
+            let source = "
+var x = 1
+f = || x = 2
+";
+
+            check_ast(
+                source,
+                &[
+                    Id(0),
+                    SmallInt(1),
+                    Assign {
+                        target: 0,
+                        expression: 1,
+                    },
+                    Var(2),
+                    Id(1),
+                    Id(0), // 5
+                    SmallInt(2),
+                    Assign {
+                        target: 5,
+                        expression: 6,
+                    },
+                    Function(koto_parser::Function {
+                        args: vec![],
+                        arg_types: vec![],
+                        output_type: None,
+                        // The assignment modifies the captured var rather than assigning a local
+                        local_count: 0,
+                        accessed_non_locals: vec![0],
+                        body: 7,
+                        is_variadic: false,
+                        is_generator: false,
+                    }),
+                    Assign {
+                        target: 4,
+                        expression: 8,
+                    },
+                    MainBlock {
+                        body: vec![3, 9],
+                        local_count: 3,
+                    },
+                ],
+                Some(&[Constant::Str("x"), Constant::Str("f")]),
+            )
+        }
+
+        #[test]
+        fn let_in_nested_function_shadows_var() {
+            let source = "
+var x = 1
+f = || let x = 2
+";
+
+            check_ast(
+                source,
+                &[
+                    Id(0),
+                    SmallInt(1),
+                    Assign {
+                        target: 0,
+                        expression: 1,
+                    },
+                    Var(2),
+                    Id(1),
+                    Id(0), // 5
+                    SmallInt(2),
+                    Assign {
+                        target: 5,
+                        expression: 6,
+                    },
+                    Let(7),
+                    Function(koto_parser::Function {
+                        args: vec![],
+                        arg_types: vec![],
+                        output_type: None,
+                        local_count: 2,
+                        accessed_non_locals: vec![],
+                        body: 8,
+                        is_variadic: false,
+                        is_generator: false,
+                    }),
+                    Assign {
+                        target: 4,
+                        expression: 9,
+                    }, // 10
+                    MainBlock {
+                        body: vec![3, 10],
+                        local_count: 3,
+                    },
+                ],
+                Some(&[Constant::Str("x"), Constant::Str("f")]),
+            )
+        }
     }
 
     mod arithmetic {
@@ -1783,6 +2088,34 @@ a = (1
     mod logic {
         use super::*;
 
+        #[test]
+        fn null_coalesce() {
+            let source = "a ?? b ?? 42";
+            check_ast(
+                source,
+                &[
+                    Id(0),
+                    Id(1),
+                    BinaryOp {
+                        op: AstBinaryOp::NullCoalesce,
+                        lhs: 0,
+                        rhs: 1,
+                    },
+                    SmallInt(42),
+                    BinaryOp {
+                        op: AstBinaryOp::NullCoalesce,
+                        lhs: 2,
+                        rhs: 3,
+                    },
+                    MainBlock {
+                        body: vec![4],
+                        local_count: 0,
+                    },
+                ],
+                Some(&[Constant::Str("a"), Constant::Str("b")]),
+            )
+        }
+
         #[test]
         fn and_or() {
             let source = "0 < 1 and 1 > 0 or true";
@@ -2009,6 +2342,8 @@ a",
                     Block(vec![2, 3]),
                     Function(koto_parser::Function {
                         args: vec![],
+                        arg_types: vec![],
+                        output_type: None,
                         local_count: 0,
                         accessed_non_locals: vec![0],
                         body: 4,
@@ -2023,6 +2358,79 @@ a",
                 Some(&[Constant::Str("x")]),
             )
         }
+
+        #[test]
+        fn with_block() {
+            let source = "
+with f = open x
+  f.read()
+";
+
+            check_ast(
+                source,
+                &[
+                    Id(0), // f
+                    Id(2), // x
+                    NamedCall {
+                        id: 1,
+                        args: vec![1],
+                    },
+                    Assign {
+                        target: 0,
+                        expression: 2,
+                    },
+                    Id(0),
+                    Lookup((
+                        LookupNode::Call {
+                            args: vec![],
+                            with_parens: true,
+                        },
+                        None,
+                    )), // 5
+                    Lookup((LookupNode::Id(3), Some(5))),
+                    Lookup((LookupNode::Root(4), Some(6))),
+                    With {
+                        resource: 3,
+                        body: 7,
+                    },
+                    MainBlock {
+                        body: vec![8],
+                        local_count: 1,
+                    },
+                ],
+                Some(&[
+                    Constant::Str("f"),
+                    Constant::Str("open"),
+                    Constant::Str("x"),
+                    Constant::Str("read"),
+                ]),
+            )
+        }
+
+        #[test]
+        fn with_block_without_assignment() {
+            let source = "
+with x
+  y
+";
+
+            check_ast(
+                source,
+                &[
+                    Id(0),
+                    Id(1),
+                    With {
+                        resource: 0,
+                        body: 1,
+                    },
+                    MainBlock {
+                        body: vec![2],
+                        local_count: 0,
+                    },
+                ],
+                Some(&[Constant::Str("x"), Constant::Str("y")]),
+            )
+        }
     }
 
     mod loops {
@@ -2217,6 +2625,8 @@ a()";
                     SmallInt(42),
                     Function(koto_parser::Function {
                         args: vec![],
+                        arg_types: vec![],
+                        output_type: None,
                         local_count: 0,
                         accessed_non_locals: vec![],
                         body: 1,
@@ -2272,6 +2682,8 @@ a()";
                     },
                     Function(koto_parser::Function {
                         args: vec![0, 1],
+                        arg_types: vec![None, None],
+                        output_type: None,
                         local_count: 2,
                         accessed_non_locals: vec![],
                         body: 4,
@@ -2287,6 +2699,41 @@ a()";
             )
         }
 
+        #[test]
+        fn inline_with_type_hints() {
+            let source = "|x: Number, y| -> String y";
+            check_ast(
+                source,
+                &[
+                    Id(0),
+                    Type(1),
+                    Id(2),
+                    Type(3),
+                    Id(2),
+                    Function(koto_parser::Function {
+                        args: vec![0, 2],
+                        arg_types: vec![Some(1), None],
+                        output_type: Some(3),
+                        local_count: 2,
+                        accessed_non_locals: vec![],
+                        body: 4,
+                        is_variadic: false,
+                        is_generator: false,
+                    }), // 5
+                    MainBlock {
+                        body: vec![5],
+                        local_count: 0,
+                    },
+                ],
+                Some(&[
+                    Constant::Str("x"),
+                    Constant::Str("Number"),
+                    Constant::Str("y"),
+                    Constant::Str("String"),
+                ]),
+            )
+        }
+
         #[test]
         fn inline_var_args() {
             let source = "|x, y...| x + y.size()";
@@ -2313,6 +2760,8 @@ a()";
                     },
                     Function(koto_parser::Function {
                         args: vec![0, 1],
+                        arg_types: vec![None, None],
+                        output_type: None,
                         local_count: 2,
                         accessed_non_locals: vec![],
                         body: 7,
@@ -2354,6 +2803,8 @@ f 42";
                     Block(vec![4, 5]),
                     Function(koto_parser::Function {
                         args: vec![1],
+                        arg_types: vec![None],
+                        output_type: None,
                         local_count: 2,
                         accessed_non_locals: vec![],
                         body: 6,
@@ -2396,6 +2847,8 @@ f 42";
                     Id(3), // z
                     Function(koto_parser::Function {
                         args: vec![3],
+                        arg_types: vec![None],
+                        output_type: None,
                         local_count: 1,
                         accessed_non_locals: vec![],
                         body: 4,
@@ -2414,6 +2867,8 @@ f 42";
                     Block(vec![6, 8]),
                     Function(koto_parser::Function {
                         args: vec![1],
+                        arg_types: vec![None],
+                        output_type: None,
                         local_count: 2,
                         accessed_non_locals: vec![],
                         body: 9,
@@ -2593,6 +3048,8 @@ foo
                     Id(2),
                     Function(koto_parser::Function {
                         args: vec![1],
+                        arg_types: vec![None],
+                        output_type: None,
                         local_count: 1,
                         accessed_non_locals: vec![],
                         body: 2,
@@ -2655,6 +3112,8 @@ f x";
                     },
                     Function(koto_parser::Function {
                         args: vec![1],
+                        arg_types: vec![None],
+                        output_type: None,
                         local_count: 1,
                         accessed_non_locals: vec![0],
                         body: 3,
@@ -2690,6 +3149,8 @@ f x";
                     },
                     Function(koto_parser::Function {
                         args: vec![2],
+                        arg_types: vec![None],
+                        output_type: None,
                         local_count: 1,
                         accessed_non_locals: vec![0],
                         body: 4,
@@ -2705,6 +3166,8 @@ f x";
                     },
                     Function(koto_parser::Function {
                         args: vec![7],
+                        arg_types: vec![None],
+                        output_type: None,
                         local_count: 1,
                         accessed_non_locals: vec![1],
                         body: 9,
@@ -2829,6 +3292,8 @@ foo.bar x
                     },
                     Function(koto_parser::Function {
                         args: vec![1],
+                        arg_types: vec![None],
+                        output_type: None,
                         local_count: 1,
                         accessed_non_locals: vec![],
                         body: 6,
@@ -2865,6 +3330,8 @@ f = ||
                     Map(vec![(MapKey::Id(1), Some(1)), (MapKey::Id(3), Some(2))]),
                     Function(koto_parser::Function {
                         args: vec![],
+                        arg_types: vec![],
+                        output_type: None,
                         local_count: 0,
                         accessed_non_locals: vec![2],
                         body: 3,
@@ -2912,6 +3379,8 @@ f = ||
                     ]),
                     Function(koto_parser::Function {
                         args: vec![],
+                        arg_types: vec![],
+                        output_type: None,
                         local_count: 0,
                         accessed_non_locals: vec![3],
                         body: 4,
@@ -2960,6 +3429,8 @@ f()";
                     },
                     Function(koto_parser::Function {
                         args: vec![2],
+                        arg_types: vec![None],
+                        output_type: None,
                         local_count: 1,
                         accessed_non_locals: vec![],
                         body: 7,
@@ -2969,6 +3440,8 @@ f()";
                     Map(vec![(MapKey::Id(1), Some(1)), (MapKey::Id(2), Some(8))]),
                     Function(koto_parser::Function {
                         args: vec![],
+                        arg_types: vec![],
+                        output_type: None,
                         local_count: 0,
                         accessed_non_locals: vec![],
                         body: 9,
@@ -3049,6 +3522,8 @@ f = |n|
                     }),
                     Function(koto_parser::Function {
                         args: vec![3],
+                        arg_types: vec![None],
+                        output_type: None,
                         local_count: 2,
                         accessed_non_locals: vec![],
                         body: 14,
@@ -3063,6 +3538,8 @@ f = |n|
                     Block(vec![16, 17]),
                     Function(koto_parser::Function {
                         args: vec![1],
+                        arg_types: vec![None],
+                        output_type: None,
                         local_count: 2,
                         accessed_non_locals: vec![],
                         body: 18,
@@ -3113,6 +3590,8 @@ f = |n|
                     Block(vec![4, 5]),
                     Function(koto_parser::Function {
                         args: vec![],
+                        arg_types: vec![],
+                        output_type: None,
                         local_count: 1,
                         accessed_non_locals: vec![0], // initial read of x via capture
                         body: 6,
@@ -3153,6 +3632,8 @@ f = |n|
                     },
                     Function(koto_parser::Function {
                         args: vec![],
+                        arg_types: vec![],
+                        output_type: None,
                         local_count: 2,
                         accessed_non_locals: vec![], // b is locally assigned when accessed
                         body: 7,
@@ -3185,6 +3666,8 @@ f = |n|
                     },
                     Function(koto_parser::Function {
                         args: vec![],
+                        arg_types: vec![],
+                        output_type: None,
                         local_count: 0,
                         accessed_non_locals: vec![0], // initial read of x via capture
                         body: 2,
@@ -3227,6 +3710,8 @@ y z";
                     },
                     Function(koto_parser::Function {
                         args: vec![5],
+                        arg_types: vec![None],
+                        output_type: None,
                         local_count: 1,
                         accessed_non_locals: vec![],
                         body: 8,
@@ -3265,6 +3750,8 @@ y z";
                     Yield(0),
                     Function(koto_parser::Function {
                         args: vec![],
+                        arg_types: vec![],
+                        output_type: None,
                         local_count: 0,
                         accessed_non_locals: vec![],
                         body: 1,
@@ -3292,6 +3779,8 @@ y z";
                     Yield(2),
                     Function(koto_parser::Function {
                         args: vec![],
+                        arg_types: vec![],
+                        output_type: None,
                         local_count: 0,
                         accessed_non_locals: vec![],
                         body: 3,
@@ -3322,6 +3811,8 @@ y z";
                     Yield(1),
                     Function(koto_parser::Function {
                         args: vec![],
+                        arg_types: vec![],
+                        output_type: None,
                         local_count: 0,
                         accessed_non_locals: vec![],
                         body: 2,
@@ -3337,6 +3828,36 @@ y z";
             )
         }
 
+        #[test]
+        fn await_call_result() {
+            let source = "x = await load y";
+            check_ast(
+                source,
+                &[
+                    Id(0),
+                    Id(2),
+                    NamedCall {
+                        id: 1,
+                        args: vec![1],
+                    },
+                    Await(2),
+                    Assign {
+                        target: 0,
+                        expression: 3,
+                    },
+                    MainBlock {
+                        body: vec![4],
+                        local_count: 1,
+                    },
+                ],
+                Some(&[
+                    Constant::Str("x"),
+                    Constant::Str("load"),
+                    Constant::Str("y"),
+                ]),
+            )
+        }
+
         #[test]
         fn unpack_call_args() {
             let sources = [
@@ -3368,6 +3889,8 @@ y z";
                     Id(0),
                     Function(koto_parser::Function {
                         args: vec![0, 6, 7],
+                        arg_types: vec![None, None, None],
+                        output_type: None,
                         local_count: 3,
                         accessed_non_locals: vec![],
                         body: 8,
@@ -3463,6 +3986,40 @@ y z";
             )
         }
 
+        #[test]
+        fn index_range_with_step() {
+            let source = "
+x[..3..2]
+x[....2]
+";
+            check_ast(
+                source,
+                &[
+                    Id(0),
+                    SmallInt(3),
+                    RangeTo {
+                        end: 1,
+                        inclusive: false,
+                    },
+                    SmallInt(2),
+                    RangeStep { range: 2, step: 3 },
+                    Lookup((LookupNode::Index(4), None)), // 5
+                    Lookup((LookupNode::Root(0), Some(5))),
+                    Id(0),
+                    RangeFull,
+                    SmallInt(2),
+                    RangeStep { range: 8, step: 9 }, // 10
+                    Lookup((LookupNode::Index(10), None)),
+                    Lookup((LookupNode::Root(7), Some(11))),
+                    MainBlock {
+                        body: vec![6, 12],
+                        local_count: 0,
+                    },
+                ],
+                Some(&[Constant::Str("x")]),
+            )
+        }
+
         #[test]
         fn index_range_from_and_sub_index() {
             let source = "x[10..][0]";
@@ -3503,6 +4060,53 @@ y z";
             )
         }
 
+        #[test]
+        fn optional_lookup_id() {
+            let source = "x?.foo";
+            check_ast(
+                source,
+                &[
+                    Id(0),
+                    Lookup((LookupNode::Id(1), None)),
+                    Lookup((LookupNode::NullCheck, Some(1))),
+                    Lookup((LookupNode::Root(0), Some(2))),
+                    MainBlock {
+                        body: vec![3],
+                        local_count: 0,
+                    },
+                ],
+                Some(&[Constant::Str("x"), Constant::Str("foo")]),
+            )
+        }
+
+        #[test]
+        fn optional_index_and_call() {
+            let source = "x?[0]?()";
+            check_ast(
+                source,
+                &[
+                    Id(0),
+                    SmallInt(0),
+                    Lookup((
+                        LookupNode::Call {
+                            args: vec![],
+                            with_parens: true,
+                        },
+                        None,
+                    )),
+                    Lookup((LookupNode::NullCheck, Some(2))),
+                    Lookup((LookupNode::Index(1), Some(3))),
+                    Lookup((LookupNode::NullCheck, Some(4))), // 5
+                    Lookup((LookupNode::Root(0), Some(5))),
+                    MainBlock {
+                        body: vec![6],
+                        local_count: 0,
+                    },
+                ],
+                Some(&[Constant::Str("x")]),
+            )
+        }
+
         #[test]
         fn lookup_call() {
             let source = "x.bar()";
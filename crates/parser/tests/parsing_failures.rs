@@ -122,6 +122,16 @@ for x in y if f x
                 check_parsing_fails("f = |a, [b, c]| a");
             }
 
+            #[test]
+            fn missing_arg_type_hint() {
+                check_parsing_fails("f = |x: | x");
+            }
+
+            #[test]
+            fn missing_output_type_hint() {
+                check_parsing_fails("f = |x| -> 42");
+            }
+
             #[test]
             fn missing_commas_in_call() {
                 check_parsing_fails("f 1 2 3");
@@ -160,6 +170,34 @@ x .foo
 ";
                 check_parsing_fails(source);
             }
+
+            #[test]
+            fn optional_lookup_without_access() {
+                check_parsing_fails("x?");
+            }
+
+            #[test]
+            fn detached_optional_lookup() {
+                check_parsing_fails("x? .foo");
+            }
+        }
+
+        mod with_blocks {
+            use super::*;
+
+            #[test]
+            fn missing_resource() {
+                let source = "
+with
+  x
+";
+                check_parsing_fails(source);
+            }
+
+            #[test]
+            fn missing_body() {
+                check_parsing_fails("with x");
+            }
         }
 
         mod maps {
@@ -240,6 +278,20 @@ x = {'y'}
             }
         }
 
+        mod ranges {
+            use super::*;
+
+            #[test]
+            fn missing_step() {
+                check_parsing_fails("x = 0..10..");
+            }
+
+            #[test]
+            fn missing_step_in_index() {
+                check_parsing_fails("x[....]");
+            }
+        }
+
         mod match_expressions {
             use super::*;
 
@@ -304,6 +356,40 @@ match [1, 2, 3]
             }
         }
 
+        mod constants {
+            use super::*;
+
+            #[test]
+            fn const_without_assignment() {
+                check_parsing_fails("const x");
+            }
+
+            #[test]
+            fn const_with_lookup_target() {
+                check_parsing_fails("const x.y = 1");
+            }
+
+            #[test]
+            fn let_without_assignment() {
+                check_parsing_fails("let x");
+            }
+
+            #[test]
+            fn let_with_lookup_target() {
+                check_parsing_fails("let x, y.z = 1, 2");
+            }
+
+            #[test]
+            fn var_without_assignment() {
+                check_parsing_fails("var x");
+            }
+
+            #[test]
+            fn var_with_lookup_target() {
+                check_parsing_fails("var x.y = 1");
+            }
+        }
+
         mod switch_expressions {
             use super::*;
 
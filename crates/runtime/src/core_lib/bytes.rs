@@ -0,0 +1,712 @@
+//! The `bytes` core library module
+
+use super::iterator::collect_pair;
+use crate::{derive::*, prelude::*, KIteratorOutput as Output, Result};
+use std::hash::{Hash, Hasher};
+
+/// Initializes the `bytes` core library module
+pub fn make_module() -> KMap {
+    use KValue::{Number, Str};
+
+    let result = KMap::with_type("core.bytes");
+
+    result.add_fn("from_base64", |ctx| match ctx.args() {
+        [Str(s)] => match decode_base64(s.as_str()) {
+            Some(data) => Ok(Bytes::from(data).into()),
+            None => runtime_error!("'{s}' isn't a valid base64 string"),
+        },
+        unexpected => type_error_with_slice("a String", unexpected),
+    });
+
+    result.add_fn("from_hex", |ctx| match ctx.args() {
+        [Str(s)] => match decode_hex(s.as_str()) {
+            Some(data) => Ok(Bytes::from(data).into()),
+            None => runtime_error!("'{s}' isn't a valid hex string"),
+        },
+        unexpected => type_error_with_slice("a String", unexpected),
+    });
+
+    result.add_fn("new", |ctx| match ctx.args() {
+        [] => Ok(Bytes::default().into()),
+        [value] => {
+            let value = value.clone();
+            Ok(Bytes::from(make_byte_vec(ctx.vm, value)?).into())
+        }
+        unexpected => type_error_with_slice(
+            "no arguments, or a String, Bytes, or an iterable of Numbers",
+            unexpected,
+        ),
+    });
+
+    result.add_fn("pack", |ctx| match ctx.args() {
+        [Str(format), values @ ..] => {
+            let format = Format::parse(format)?;
+            Ok(Bytes::from(format.pack(values)?).into())
+        }
+        unexpected => type_error_with_slice(
+            "a format String, followed by the values to pack",
+            unexpected,
+        ),
+    });
+
+    result.add_fn("pack_size", |ctx| match ctx.args() {
+        [Str(format)] => Ok(Format::parse(format)?.size.into()),
+        unexpected => type_error_with_slice("a format String", unexpected),
+    });
+
+    result.add_fn("with_size", |ctx| {
+        let (size, fill) = match ctx.args() {
+            [Number(size)] if *size >= 0.0 => (size, 0),
+            [Number(size), Number(fill)] if *size >= 0.0 => (size, byte_from_number(*fill)?),
+            unexpected => {
+                return type_error_with_slice(
+                    "a non-negative Number, with an optional fill byte",
+                    unexpected,
+                )
+            }
+        };
+        Ok(Bytes::from(vec![fill; usize::from(size)]).into())
+    });
+
+    result
+}
+
+/// The Bytes type used in the bytes module
+///
+/// A mutable buffer of bytes, useful when working with binary data.
+#[derive(Clone, Default, KotoCopy, KotoType)]
+pub struct Bytes(Vec<u8>);
+
+#[koto_impl(runtime = crate)]
+impl Bytes {
+    /// Returns a reference to the contained bytes
+    pub fn data(&self) -> &[u8] {
+        &self.0
+    }
+
+    #[koto_method]
+    fn clear(ctx: MethodContext<Self>) -> Result<KValue> {
+        ctx.instance_mut()?.0.clear();
+        ctx.instance_result()
+    }
+
+    #[koto_method]
+    fn extend(ctx: MethodContext<Self>) -> Result<KValue> {
+        match ctx.args {
+            [value] => {
+                // The new data is prepared before borrowing the instance,
+                // allowing an instance to be extended with itself.
+                let data = make_byte_vec(&mut ctx.vm.spawn_shared_vm(), value.clone())?;
+                ctx.instance_mut()?.0.extend(data);
+                ctx.instance_result()
+            }
+            unexpected => {
+                type_error_with_slice("a String, Bytes, or an iterable of Numbers", unexpected)
+            }
+        }
+    }
+
+    #[koto_method]
+    fn push(ctx: MethodContext<Self>) -> Result<KValue> {
+        match ctx.args {
+            [KValue::Number(n)] => {
+                let byte = byte_from_number(*n)?;
+                ctx.instance_mut()?.0.push(byte);
+                ctx.instance_result()
+            }
+            unexpected => type_error_with_slice("a Number in the range 0..=255", unexpected),
+        }
+    }
+
+    #[koto_method]
+    fn to_base64(&self) -> KValue {
+        encode_base64(&self.0).into()
+    }
+
+    #[koto_method]
+    fn to_hex(&self) -> KValue {
+        encode_hex(&self.0).into()
+    }
+
+    #[koto_method]
+    fn to_string(&self) -> Result<KValue> {
+        match std::str::from_utf8(&self.0) {
+            Ok(result) => Ok(result.into()),
+            Err(_) => runtime_error!("Input failed UTF-8 validation"),
+        }
+    }
+
+    #[koto_method]
+    fn unpack(&self, args: &[KValue]) -> Result<KValue> {
+        let (format, offset) = match args {
+            [KValue::Str(format)] => (format, 0),
+            [KValue::Str(format), KValue::Number(offset)] if *offset >= 0.0 => {
+                (format, usize::from(offset))
+            }
+            unexpected => {
+                return type_error_with_slice(
+                    "a format String, with an optional non-negative offset",
+                    unexpected,
+                )
+            }
+        };
+
+        let format = Format::parse(format)?;
+        let data = self.0.get(offset..).unwrap_or_default();
+        format.unpack(data).map(KValue::from)
+    }
+
+    fn validate_index(&self, n: KNumber) -> Result<usize> {
+        let size = self.0.len();
+        let index = n.as_i64();
+        let index = if index < 0 {
+            index + size as i64
+        } else {
+            index
+        };
+
+        if index < 0 || index as usize >= size {
+            return runtime_error!("Index out of bounds - index: {n}, size: {size}");
+        }
+
+        Ok(index as usize)
+    }
+}
+
+impl KotoObject for Bytes {
+    fn display(&self, ctx: &mut DisplayContext) -> Result<()> {
+        ctx.append(format!("{}(", Self::type_static()));
+        for (i, byte) in self.0.iter().enumerate() {
+            if i > 0 {
+                ctx.append(' ');
+            }
+            ctx.append(format!("{byte:02x}"));
+        }
+        ctx.append(')');
+        Ok(())
+    }
+
+    fn index(&self, index: &KValue) -> Result<KValue> {
+        match index {
+            KValue::Number(n) => {
+                let index = self.validate_index(*n)?;
+                Ok(self.0[index].into())
+            }
+            KValue::Range(range) => match range.to_index_range() {
+                Some(range) => {
+                    let result = range
+                        .stepped_indices(self.0.len())
+                        .map(|i| self.0[i])
+                        .collect::<Vec<_>>();
+                    Ok(Self(result).into())
+                }
+                None => type_error("a Number or integer Range as index", index),
+            },
+            unexpected => type_error("a Number or integer Range as index", unexpected),
+        }
+    }
+
+    fn set_index(&mut self, index: &KValue, value: KValue) -> Result<()> {
+        match (index, value) {
+            (KValue::Number(n), KValue::Number(value)) => {
+                let index = self.validate_index(*n)?;
+                self.0[index] = byte_from_number(value)?;
+                Ok(())
+            }
+            (KValue::Number(_), unexpected) => {
+                type_error("a Number in the range 0..=255", &unexpected)
+            }
+            (unexpected, _) => type_error("a Number as index", unexpected),
+        }
+    }
+
+    fn size(&self) -> Option<usize> {
+        Some(self.0.len())
+    }
+
+    fn add(&self, rhs: &KValue) -> Result<KValue> {
+        match rhs {
+            KValue::Object(o) if o.is_a::<Self>() => {
+                let rhs = o.cast::<Self>()?;
+                let mut result = self.0.clone();
+                result.extend_from_slice(&rhs.0);
+                Ok(Self(result).into())
+            }
+            unexpected => type_error(Self::type_static(), unexpected),
+        }
+    }
+
+    fn add_assign(&mut self, rhs: &KValue) -> Result<()> {
+        match rhs {
+            KValue::Object(o) if o.is_a::<Self>() => {
+                let rhs = o.cast::<Self>()?;
+                self.0.extend_from_slice(&rhs.0);
+                Ok(())
+            }
+            unexpected => type_error(Self::type_static(), unexpected),
+        }
+    }
+
+    fn equal(&self, rhs: &KValue) -> Result<bool> {
+        match rhs {
+            KValue::Object(o) if o.is_a::<Self>() => Ok(self.0 == o.cast::<Self>()?.0),
+            _ => Ok(false),
+        }
+    }
+
+    fn not_equal(&self, rhs: &KValue) -> Result<bool> {
+        self.equal(rhs).map(|result| !result)
+    }
+
+    fn hash(&self) -> Option<u64> {
+        let mut hasher = KotoHasher::default();
+        self.0.hash(&mut hasher);
+        Some(hasher.finish())
+    }
+
+    fn is_iterable(&self) -> IsIterable {
+        IsIterable::Iterable
+    }
+
+    fn make_iterator(&self, _vm: &mut KotoVm) -> Result<KIterator> {
+        let bytes = self.0.clone().into_iter();
+        Ok(KIterator::with_std_iter(
+            bytes.map(|byte| Output::Value(byte.into())),
+        ))
+    }
+}
+
+impl From<Vec<u8>> for Bytes {
+    fn from(data: Vec<u8>) -> Self {
+        Self(data)
+    }
+}
+
+impl From<Bytes> for KValue {
+    fn from(bytes: Bytes) -> Self {
+        KObject::from(bytes).into()
+    }
+}
+
+// Makes a vec of bytes from a String, a Bytes object, or an iterable of Numbers
+fn make_byte_vec(vm: &mut KotoVm, value: KValue) -> Result<Vec<u8>> {
+    match &value {
+        KValue::Str(s) => return Ok(s.as_bytes().to_vec()),
+        KValue::Object(o) if o.is_a::<Bytes>() => return Ok(o.cast::<Bytes>()?.0.clone()),
+        _ if value.is_iterable() => {}
+        unexpected => return type_error("a String, Bytes, or an iterable of Numbers", unexpected),
+    }
+
+    let iterator = vm.make_iterator(value)?;
+    let (size_hint, _) = iterator.size_hint();
+    let mut result = Vec::with_capacity(size_hint);
+
+    for output in iterator.map(collect_pair) {
+        match output {
+            Output::Value(KValue::Number(n)) => result.push(byte_from_number(n)?),
+            Output::Value(unexpected) => return type_error("a Number", &unexpected),
+            Output::Error(error) => return Err(error),
+            _ => unreachable!(), // value pairs have been collected in collect_pair
+        }
+    }
+
+    Ok(result)
+}
+
+fn byte_from_number(n: KNumber) -> Result<u8> {
+    match integer_from_number(n).and_then(|n| u8::try_from(n).ok()) {
+        Some(byte) => Ok(byte),
+        None => runtime_error!("'{n}' is out of the valid byte range"),
+    }
+}
+
+// Returns the number as an integer, or None if the number has a fractional part
+fn integer_from_number(n: KNumber) -> Option<i128> {
+    match n {
+        KNumber::I64(n) => Some(n.into()),
+        KNumber::F64(n) if n.is_finite() && n.fract() == 0.0 => Some(n as i128),
+        KNumber::F64(_) => None,
+    }
+}
+
+#[derive(Clone, Copy)]
+enum ByteOrder {
+    Little,
+    Big,
+}
+
+impl ByteOrder {
+    fn native() -> Self {
+        if cfg!(target_endian = "little") {
+            Self::Little
+        } else {
+            Self::Big
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+enum FormatCode {
+    Pad,
+    Bool,
+    I8,
+    U8,
+    I16,
+    U16,
+    I32,
+    U32,
+    I64,
+    U64,
+    F32,
+    F64,
+}
+
+impl FormatCode {
+    fn from_char(c: char) -> Option<Self> {
+        use FormatCode::*;
+
+        let result = match c {
+            'x' => Pad,
+            '?' => Bool,
+            'b' => I8,
+            'B' => U8,
+            'h' => I16,
+            'H' => U16,
+            'i' => I32,
+            'I' => U32,
+            'q' => I64,
+            'Q' => U64,
+            'f' => F32,
+            'd' => F64,
+            _ => return None,
+        };
+
+        Some(result)
+    }
+
+    fn size(self) -> usize {
+        use FormatCode::*;
+
+        match self {
+            Pad | Bool | I8 | U8 => 1,
+            I16 | U16 => 2,
+            I32 | U32 | F32 => 4,
+            I64 | U64 | F64 => 8,
+        }
+    }
+}
+
+// A parsed format string, as used by `bytes.pack` and `Bytes.unpack`
+//
+// The format string starts with an optional byte order character, followed by a series of format
+// codes, each of which can be preceded by a repeat count. Whitespace is ignored.
+//
+// Repeated codes are stored as (count, code) runs, so that large repeat counts can be validated
+// before any data is allocated.
+struct Format {
+    byte_order: ByteOrder,
+    runs: Vec<(usize, FormatCode)>,
+    // The number of bytes described by the format
+    size: usize,
+    // The number of values described by the format
+    value_count: usize,
+}
+
+impl Format {
+    fn parse(format: &str) -> Result<Self> {
+        let mut chars = format.chars().filter(|c| !c.is_whitespace()).peekable();
+
+        let byte_order = match chars.peek() {
+            Some('<') => Some(ByteOrder::Little),
+            Some('>' | '!') => Some(ByteOrder::Big),
+            Some('=' | '@') => Some(ByteOrder::native()),
+            _ => None,
+        };
+        if byte_order.is_some() {
+            chars.next();
+        }
+
+        let mut runs = Vec::new();
+        let mut size = 0usize;
+        let mut value_count = 0usize;
+        let mut count: Option<usize> = None;
+
+        for c in chars {
+            if let Some(digit) = c.to_digit(10) {
+                let new_count = count
+                    .unwrap_or(0)
+                    .checked_mul(10)
+                    .and_then(|n| n.checked_add(digit as usize));
+                match new_count {
+                    Some(n) => count = Some(n),
+                    None => return runtime_error!("Repeat count too large in format '{format}'"),
+                }
+            } else if let Some(code) = FormatCode::from_char(c) {
+                let run_count = count.take().unwrap_or(1);
+                let run_values = if matches!(code, FormatCode::Pad) {
+                    0
+                } else {
+                    run_count
+                };
+                let new_size = run_count
+                    .checked_mul(code.size())
+                    .and_then(|run_size| size.checked_add(run_size));
+                match (new_size, value_count.checked_add(run_values)) {
+                    (Some(new_size), Some(new_value_count)) => {
+                        size = new_size;
+                        value_count = new_value_count;
+                    }
+                    _ => return runtime_error!("Format '{format}' is too large"),
+                }
+                runs.push((run_count, code));
+            } else {
+                return runtime_error!("Invalid character '{c}' in format '{format}'");
+            }
+        }
+
+        if count.is_some() {
+            return runtime_error!("Missing format code after repeat count in format '{format}'");
+        }
+
+        Ok(Self {
+            byte_order: byte_order.unwrap_or_else(ByteOrder::native),
+            runs,
+            size,
+            value_count,
+        })
+    }
+
+    fn pack(&self, values: &[KValue]) -> Result<Vec<u8>> {
+        use FormatCode::*;
+
+        let expected_count = self.value_count;
+        if values.len() != expected_count {
+            return runtime_error!(
+                "Expected {expected_count} values to pack, found {}",
+                values.len()
+            );
+        }
+
+        let mut result = Vec::with_capacity(self.size);
+        let mut values = values.iter();
+
+        macro_rules! push_bytes {
+            ($value:expr) => {{
+                match self.byte_order {
+                    ByteOrder::Little => result.extend_from_slice(&$value.to_le_bytes()),
+                    ByteOrder::Big => result.extend_from_slice(&$value.to_be_bytes()),
+                }
+            }};
+        }
+
+        macro_rules! push_integer {
+            ($value:expr, $type:ty) => {{
+                let n = expect_number($value)?;
+                match integer_from_number(n).and_then(|n| <$type>::try_from(n).ok()) {
+                    Some(n) => push_bytes!(n),
+                    None => {
+                        return runtime_error!("'{n}' can't be packed as {}", stringify!($type))
+                    }
+                }
+            }};
+        }
+
+        for &(count, code) in self.runs.iter() {
+            if matches!(code, Pad) {
+                result.resize(result.len() + count, 0);
+                continue;
+            }
+
+            for _ in 0..count {
+                // Safety: the number of values has been checked against the format's value count
+                let value = values.next().unwrap();
+
+                match code {
+                    Pad => unreachable!(),
+                    Bool => match value {
+                        KValue::Bool(b) => result.push(*b as u8),
+                        unexpected => return type_error("a Bool", unexpected),
+                    },
+                    I8 => push_integer!(value, i8),
+                    U8 => push_integer!(value, u8),
+                    I16 => push_integer!(value, i16),
+                    U16 => push_integer!(value, u16),
+                    I32 => push_integer!(value, i32),
+                    U32 => push_integer!(value, u32),
+                    I64 => push_integer!(value, i64),
+                    U64 => push_integer!(value, u64),
+                    F32 => push_bytes!(f64::from(expect_number(value)?) as f32),
+                    F64 => push_bytes!(f64::from(expect_number(value)?)),
+                }
+            }
+        }
+
+        Ok(result)
+    }
+
+    fn unpack(&self, data: &[u8]) -> Result<KTuple> {
+        use FormatCode::*;
+
+        let size = self.size;
+        if data.len() < size {
+            return runtime_error!(
+                "Expected at least {size} bytes to unpack, found {}",
+                data.len()
+            );
+        }
+
+        let mut result = Vec::with_capacity(self.value_count);
+        let mut position = 0;
+
+        macro_rules! read {
+            ($type:ty) => {{
+                const SIZE: usize = std::mem::size_of::<$type>();
+                // Safety: the data's size has been checked against the format's size
+                let bytes: [u8; SIZE] = data[position..position + SIZE].try_into().unwrap();
+                match self.byte_order {
+                    ByteOrder::Little => <$type>::from_le_bytes(bytes),
+                    ByteOrder::Big => <$type>::from_be_bytes(bytes),
+                }
+            }};
+        }
+
+        for &(count, code) in self.runs.iter() {
+            if matches!(code, Pad) {
+                position += count;
+                continue;
+            }
+
+            for _ in 0..count {
+                let value = match code {
+                    Pad => unreachable!(),
+                    Bool => KValue::Bool(data[position] != 0),
+                    I8 => read!(i8).into(),
+                    U8 => read!(u8).into(),
+                    I16 => read!(i16).into(),
+                    U16 => read!(u16).into(),
+                    I32 => read!(i32).into(),
+                    U32 => read!(u32).into(),
+                    I64 => read!(i64).into(),
+                    U64 => {
+                        // Values that don't fit in an i64 are converted to floats
+                        let n = read!(u64);
+                        match i64::try_from(n) {
+                            Ok(n) => n.into(),
+                            Err(_) => (n as f64).into(),
+                        }
+                    }
+                    F32 => f64::from(read!(f32)).into(),
+                    F64 => read!(f64).into(),
+                };
+
+                result.push(value);
+                position += code.size();
+            }
+        }
+
+        Ok(result.into())
+    }
+}
+
+fn expect_number(value: &KValue) -> Result<KNumber> {
+    match value {
+        KValue::Number(n) => Ok(*n),
+        unexpected => type_error("a Number", unexpected),
+    }
+}
+
+const BASE64_CHARS: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+fn encode_base64(data: &[u8]) -> String {
+    let mut result = String::with_capacity(data.len().div_ceil(3) * 4);
+
+    for chunk in data.chunks(3) {
+        let b = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or(0),
+            chunk.get(2).copied().unwrap_or(0),
+        ];
+        let n = (b[0] as u32) << 16 | (b[1] as u32) << 8 | b[2] as u32;
+
+        for i in 0..4 {
+            if i <= chunk.len() {
+                let index = (n >> (18 - i * 6)) & 0x3f;
+                result.push(BASE64_CHARS[index as usize] as char);
+            } else {
+                result.push('=');
+            }
+        }
+    }
+
+    result
+}
+
+// Decodes a base64 string, returning None if the string is invalid
+//
+// Padding characters are optional, and whitespace is ignored.
+fn decode_base64(s: &str) -> Option<Vec<u8>> {
+    let s = s.trim_end_matches(|c: char| c == '=' || c.is_whitespace());
+    let mut result = Vec::with_capacity(s.len() * 3 / 4);
+    let mut buffer = 0u32;
+    let mut bits = 0;
+
+    for c in s.bytes().filter(|c| !c.is_ascii_whitespace()) {
+        let value = match c {
+            b'A'..=b'Z' => c - b'A',
+            b'a'..=b'z' => c - b'a' + 26,
+            b'0'..=b'9' => c - b'0' + 52,
+            b'+' => 62,
+            b'/' => 63,
+            _ => return None,
+        };
+
+        buffer = buffer << 6 | value as u32;
+        bits += 6;
+
+        if bits >= 8 {
+            bits -= 8;
+            result.push((buffer >> bits) as u8);
+            buffer &= (1 << bits) - 1;
+        }
+    }
+
+    // A single leftover character can't represent a complete byte
+    if bits >= 6 {
+        return None;
+    }
+
+    Some(result)
+}
+
+fn encode_hex(data: &[u8]) -> String {
+    use std::fmt::Write;
+
+    let mut result = String::with_capacity(data.len() * 2);
+    for byte in data {
+        // Safety: writing to a String can't fail
+        write!(result, "{byte:02x}").unwrap();
+    }
+    result
+}
+
+// Decodes a hex string, returning None if the string is invalid
+//
+// Whitespace between bytes is ignored.
+fn decode_hex(s: &str) -> Option<Vec<u8>> {
+    let digits = s
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .map(|c| c.to_digit(16))
+        .collect::<Option<Vec<_>>>()?;
+
+    if digits.len() % 2 != 0 {
+        return None;
+    }
+
+    Some(
+        digits
+            .chunks(2)
+            .map(|pair| (pair[0] << 4 | pair[1]) as u8)
+            .collect(),
+    )
+}
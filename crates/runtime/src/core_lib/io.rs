@@ -1,5 +1,6 @@
 //! The `io` core library module
 
+use super::bytes::Bytes;
 use crate::{derive::*, prelude::*, BufferedFile, Error, Ptr, Result};
 use std::{
     fmt, fs,
@@ -148,6 +149,11 @@ pub struct File(Ptr<dyn KotoFile>);
 
 #[koto_impl(runtime = crate)]
 impl File {
+    /// Makes a new File that wraps the provided file implementation
+    pub fn new(file: Ptr<dyn KotoFile>) -> Self {
+        Self(file)
+    }
+
     /// Wraps a file that implements traits typical of a system file in a buffered reader/writer
     pub fn system_file<T>(file: T, path: PathBuf) -> KValue
     where
@@ -168,6 +174,11 @@ impl File {
         Self(vm.stdout().clone()).into()
     }
 
+    #[koto_method]
+    fn close(&mut self) -> Result<KValue> {
+        self.0.close().map(|_| KValue::Null)
+    }
+
     #[koto_method]
     fn flush(&mut self) -> Result<KValue> {
         self.0.flush().map(|_| KValue::Null)
@@ -178,6 +189,23 @@ impl File {
         self.0.path().map(KValue::from)
     }
 
+    #[koto_method]
+    fn read_bytes(&mut self, args: &[KValue]) -> Result<KValue> {
+        let count = match args {
+            [] => None,
+            [KValue::Number(n)] if *n >= 0.0 => Some(n.into()),
+            unexpected => {
+                return type_error_with_slice(
+                    "an optional non-negative Number as the byte count",
+                    unexpected,
+                )
+            }
+        };
+        self.0
+            .read_bytes(count)
+            .map(|bytes| Bytes::from(bytes).into())
+    }
+
     #[koto_method]
     fn read_line(&mut self) -> Result<KValue> {
         self.0.read_line().map(|result| match result {
@@ -216,6 +244,13 @@ impl File {
     #[koto_method]
     fn write(ctx: MethodContext<Self>) -> Result<KValue> {
         match ctx.args {
+            [KValue::Object(o)] if o.is_a::<Bytes>() => {
+                let bytes = o.cast::<Bytes>()?;
+                ctx.instance_mut()?
+                    .0
+                    .write(bytes.data())
+                    .map(|_| KValue::Null)
+            }
             [value] => {
                 let mut display_context = DisplayContext::with_vm(ctx.vm);
                 value.display(&mut display_context)?;
@@ -249,6 +284,10 @@ impl KotoObject for File {
         ctx.append(format!("{}({})", Self::type_static(), self.0.id()));
         Ok(())
     }
+
+    fn close(&mut self) -> Result<()> {
+        self.0.close()
+    }
 }
 
 impl From<File> for KValue {
@@ -261,7 +300,8 @@ struct BufferedSystemFile<T>
 where
     T: Write + KotoSend + KotoSync,
 {
-    file: KCell<BufferedFile<T>>,
+    // The file is set to None when the file is closed
+    file: KCell<Option<BufferedFile<T>>>,
     path: PathBuf,
 }
 
@@ -271,12 +311,25 @@ where
 {
     pub fn new(file: T, path: PathBuf) -> Self {
         Self {
-            file: BufferedFile::new(file).into(),
+            file: Some(BufferedFile::new(file)).into(),
             path,
         }
     }
 }
 
+impl<T> BufferedSystemFile<T>
+where
+    T: Write + KotoSend + KotoSync,
+{
+    // Calls the provided function with the open file, or returns an error if it's been closed
+    fn with_file<R>(&self, f: impl FnOnce(&mut BufferedFile<T>) -> io::Result<R>) -> Result<R> {
+        match self.file.borrow_mut().as_mut() {
+            Some(file) => f(file).map_err(map_io_err),
+            None => runtime_error!("The file '{}' has been closed", self.path.to_string_lossy()),
+        }
+    }
+}
+
 impl<T> KotoFile for BufferedSystemFile<T>
 where
     T: Read + Write + Seek + KotoSend + KotoSync,
@@ -290,12 +343,17 @@ where
     }
 
     fn seek(&self, position: u64) -> Result<()> {
-        self.file
-            .borrow_mut()
-            .seek(SeekFrom::Start(position))
-            .map_err(map_io_err)?;
+        self.with_file(|file| file.seek(SeekFrom::Start(position)))?;
         Ok(())
     }
+
+    fn close(&self) -> Result<()> {
+        // Taking the file out of the cell drops the underlying file handle after flushing
+        match self.file.borrow_mut().take() {
+            Some(mut file) => file.flush().map_err(map_io_err),
+            None => Ok(()),
+        }
+    }
 }
 
 impl<T> KotoRead for BufferedSystemFile<T>
@@ -304,23 +362,26 @@ where
 {
     fn read_line(&self) -> Result<Option<String>> {
         let mut buffer = String::new();
-        match self
-            .file
-            .borrow_mut()
-            .read_line(&mut buffer)
-            .map_err(map_io_err)?
-        {
+        match self.with_file(|file| file.read_line(&mut buffer))? {
             0 => Ok(None),
             _ => Ok(Some(buffer)),
         }
     }
 
+    fn read_bytes(&self, count: Option<usize>) -> Result<Vec<u8>> {
+        let mut buffer = Vec::new();
+        match count {
+            Some(count) => {
+                self.with_file(|file| Read::take(file, count as u64).read_to_end(&mut buffer))?
+            }
+            None => self.with_file(|file| file.read_to_end(&mut buffer))?,
+        };
+        Ok(buffer)
+    }
+
     fn read_to_string(&self) -> Result<String> {
         let mut buffer = String::new();
-        self.file
-            .borrow_mut()
-            .read_to_string(&mut buffer)
-            .map_err(map_io_err)?;
+        self.with_file(|file| file.read_to_string(&mut buffer))?;
         Ok(buffer)
     }
 }
@@ -330,19 +391,19 @@ where
     T: Read + Write + KotoSend + KotoSync,
 {
     fn write(&self, bytes: &[u8]) -> Result<()> {
-        self.file.borrow_mut().write(bytes).map_err(map_io_err)?;
+        self.with_file(|file| file.write(bytes))?;
         Ok(())
     }
 
     fn write_line(&self, text: &str) -> Result<()> {
-        let mut borrowed = self.file.borrow_mut();
-        borrowed.write(text.as_bytes()).map_err(map_io_err)?;
-        borrowed.write("\n".as_bytes()).map_err(map_io_err)?;
-        Ok(())
+        self.with_file(|file| {
+            file.write_all(text.as_bytes())?;
+            file.write_all("\n".as_bytes())
+        })
     }
 
     fn flush(&self) -> Result<()> {
-        self.file.borrow_mut().flush().map_err(map_io_err)
+        self.with_file(|file| file.flush())
     }
 }
 
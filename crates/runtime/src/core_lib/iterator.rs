@@ -4,6 +4,10 @@ pub mod adaptors;
 pub mod generators;
 pub mod peekable;
 
+use super::{
+    set::make_value_set,
+    value_sort::{sort_values_with_options, SortOptions},
+};
 use crate::{derive::*, prelude::*, KIteratorOutput as Output, Result};
 
 /// Initializes the `iterator` core library module
@@ -105,6 +109,26 @@ pub fn make_module() -> KMap {
         }
     });
 
+    result.add_fn("chunk_by", |ctx| {
+        let expected_error = "an iterable and an optional key function";
+
+        let (iterable, key_fn) = match ctx.instance_and_args(KValue::is_iterable, expected_error)? {
+            (iterable, []) => (iterable.clone(), None),
+            (iterable, [key_fn]) if key_fn.is_callable() => {
+                (iterable.clone(), Some(key_fn.clone()))
+            }
+            (_, unexpected) => return type_error_with_slice(expected_error, unexpected),
+        };
+
+        let result = adaptors::ChunkBy::new(
+            ctx.vm.make_iterator(iterable)?,
+            key_fn,
+            ctx.vm.spawn_shared_vm(),
+        );
+
+        Ok(KIterator::new(result).into())
+    });
+
     result.add_fn("chunks", |ctx| {
         let expected_error = "an iterable and a chunk size greater than zero";
 
@@ -121,6 +145,22 @@ pub fn make_module() -> KMap {
         }
     });
 
+    result.add_fn("chunks_exact", |ctx| {
+        let expected_error = "an iterable and a chunk size greater than zero";
+
+        match ctx.instance_and_args(KValue::is_iterable, expected_error)? {
+            (iterable, [KValue::Number(n)]) => {
+                let iterable = iterable.clone();
+                let n = *n;
+                match adaptors::Chunks::exact(ctx.vm.make_iterator(iterable)?, n.into()) {
+                    Ok(result) => Ok(KIterator::new(result).into()),
+                    Err(e) => runtime_error!("iterator.chunks_exact: {}", e),
+                }
+            }
+            (_, unexpected) => type_error_with_slice(expected_error, unexpected),
+        }
+    });
+
     result.add_fn("consume", |ctx| {
         let expected_error = "an iterable value (and optional consumer function)";
 
@@ -174,6 +214,26 @@ pub fn make_module() -> KMap {
         }
     });
 
+    result.add_fn("dedup", |ctx| {
+        let expected_error = "an iterable and an optional key function";
+
+        let (iterable, key_fn) = match ctx.instance_and_args(KValue::is_iterable, expected_error)? {
+            (iterable, []) => (iterable.clone(), None),
+            (iterable, [key_fn]) if key_fn.is_callable() => {
+                (iterable.clone(), Some(key_fn.clone()))
+            }
+            (_, unexpected) => return type_error_with_slice(expected_error, unexpected),
+        };
+
+        let result = adaptors::Dedup::new(
+            ctx.vm.make_iterator(iterable)?,
+            key_fn,
+            ctx.vm.spawn_shared_vm(),
+        );
+
+        Ok(KIterator::new(result).into())
+    });
+
     result.add_fn("each", |ctx| {
         let expected_error = "an iterable and function";
 
@@ -257,6 +317,26 @@ pub fn make_module() -> KMap {
         }
     });
 
+    result.add_fn("flat_map", |ctx| {
+        let expected_error = "an iterable and function";
+
+        match ctx.instance_and_args(KValue::is_iterable, expected_error)? {
+            (iterable, [f]) if f.is_callable() => {
+                let iterable = iterable.clone();
+                let f = f.clone();
+                let each = adaptors::Each::new(
+                    ctx.vm.make_iterator(iterable)?,
+                    f,
+                    ctx.vm.spawn_shared_vm(),
+                );
+                let result = adaptors::Flatten::new(KIterator::new(each), ctx.vm.spawn_shared_vm());
+
+                Ok(KIterator::new(result).into())
+            }
+            (_, unexpected) => type_error_with_slice(expected_error, unexpected),
+        }
+    });
+
     result.add_fn("flatten", |ctx| {
         let expected_error = "an iterable";
 
@@ -326,6 +406,23 @@ pub fn make_module() -> KMap {
         unexpected => type_error_with_slice("(Function), or (Number, Function)", unexpected),
     });
 
+    result.add_fn("interleave", |ctx| {
+        let expected_error = "two iterables";
+
+        match ctx.instance_and_args(KValue::is_iterable, expected_error)? {
+            (iterable_a, [iterable_b]) if iterable_b.is_iterable() => {
+                let iterable_a = iterable_a.clone();
+                let iterable_b = iterable_b.clone();
+                let result = adaptors::Interleave::new(
+                    ctx.vm.make_iterator(iterable_a)?,
+                    ctx.vm.make_iterator(iterable_b)?,
+                );
+                Ok(KIterator::new(result).into())
+            }
+            (_, unexpected) => type_error_with_slice(expected_error, unexpected),
+        }
+    });
+
     result.add_fn("intersperse", |ctx| {
         let expected_error = "an iterable and a separator";
 
@@ -548,6 +645,47 @@ pub fn make_module() -> KMap {
         unexpected => type_error_with_slice("a single value", unexpected),
     });
 
+    result.add_fn("partition", |ctx| {
+        let expected_error = "an iterable and a predicate function";
+
+        match ctx.instance_and_args(KValue::is_iterable, expected_error)? {
+            (iterable, [predicate]) if predicate.is_callable() => {
+                let iterable = iterable.clone();
+                let predicate = predicate.clone();
+                let mut passed = ValueVec::new();
+                let mut failed = ValueVec::new();
+
+                for output in ctx.vm.make_iterator(iterable)?.map(collect_pair) {
+                    match output {
+                        Output::Value(value) => {
+                            match ctx.vm.call_function(predicate.clone(), value.clone())? {
+                                KValue::Bool(true) => passed.push(value),
+                                KValue::Bool(false) => failed.push(value),
+                                unexpected => {
+                                    return type_error(
+                                        "a Bool to be returned from the predicate",
+                                        &unexpected,
+                                    )
+                                }
+                            }
+                        }
+                        Output::Error(error) => return Err(error),
+                        _ => unreachable!(), // value pairs have been collected in collect_pair
+                    }
+                }
+
+                Ok(KValue::Tuple(
+                    vec![
+                        KList::with_data(passed).into(),
+                        KList::with_data(failed).into(),
+                    ]
+                    .into(),
+                ))
+            }
+            (_, unexpected) => type_error_with_slice(expected_error, unexpected),
+        }
+    });
+
     result.add_fn("peekable", |ctx| {
         let expected_error = "an iterable";
 
@@ -642,6 +780,27 @@ pub fn make_module() -> KMap {
         }
     });
 
+    result.add_fn("scan", |ctx| {
+        let expected_error = "an iterable, initial value, and accumulating function";
+
+        match ctx.instance_and_args(KValue::is_iterable, expected_error)? {
+            (iterable, [initial_value, f]) if f.is_callable() => {
+                let iterable = iterable.clone();
+                let initial_value = initial_value.clone();
+                let f = f.clone();
+                let result = adaptors::Scan::new(
+                    ctx.vm.make_iterator(iterable)?,
+                    initial_value,
+                    f,
+                    ctx.vm.spawn_shared_vm(),
+                );
+
+                Ok(KIterator::new(result).into())
+            }
+            (_, unexpected) => type_error_with_slice(expected_error, unexpected),
+        }
+    });
+
     result.add_fn("skip", |ctx| {
         let expected_error = "an iterable and non-negative number";
 
@@ -663,6 +822,33 @@ pub fn make_module() -> KMap {
         }
     });
 
+    result.add_fn("sorted", |ctx| {
+        let expected_error = "an iterable, an optional key function, and an optional Bool";
+
+        let (iterable, args) = ctx.instance_and_args(KValue::is_iterable, expected_error)?;
+        let Some(options) = SortOptions::from_args(args) else {
+            return type_error_with_slice(expected_error, args);
+        };
+        let iterable = iterable.clone();
+
+        let mut values = ValueVec::new();
+        for output in ctx.vm.make_iterator(iterable)?.map(collect_pair) {
+            match output {
+                Output::Value(value) => values.push(value),
+                Output::Error(error) => return Err(error),
+                _ => unreachable!(), // value pairs have been collected in collect_pair
+            }
+        }
+
+        sort_values_with_options(ctx.vm, &mut values, options, true)?;
+
+        // Iterating over the sorted values as a list allows the result to be double-ended
+        Ok(ctx
+            .vm
+            .make_iterator(KList::with_data(values).into())?
+            .into())
+    });
+
     result.add_fn("step", |ctx| {
         let expected_error = "an iterable and positive step size";
 
@@ -763,7 +949,7 @@ pub fn make_module() -> KMap {
                         Output::Error(error) => return Err(error),
                     };
 
-                    result.insert(ValueKey::try_from(key)?, value);
+                    result.insert(ctx.vm.make_value_key(key)?, value)?;
                 }
 
                 Ok(KValue::Map(KMap::with_data(result)))
@@ -772,6 +958,19 @@ pub fn make_module() -> KMap {
         }
     });
 
+    result.add_fn("to_set", |ctx| {
+        let expected_error = "an iterable";
+
+        match ctx.instance_and_args(KValue::is_iterable, expected_error)? {
+            (iterable, []) => {
+                let iterable = iterable.clone();
+                let result = make_value_set(ctx.vm, iterable)?;
+                Ok(KSet::with_data(result).into())
+            }
+            (_, unexpected) => type_error_with_slice(expected_error, unexpected),
+        }
+    });
+
     result.add_fn("to_string", |ctx| {
         let expected_error = "an iterable";
 
@@ -820,6 +1019,26 @@ pub fn make_module() -> KMap {
         }
     });
 
+    result.add_fn("unique", |ctx| {
+        let expected_error = "an iterable and an optional key function";
+
+        let (iterable, key_fn) = match ctx.instance_and_args(KValue::is_iterable, expected_error)? {
+            (iterable, []) => (iterable.clone(), None),
+            (iterable, [key_fn]) if key_fn.is_callable() => {
+                (iterable.clone(), Some(key_fn.clone()))
+            }
+            (_, unexpected) => return type_error_with_slice(expected_error, unexpected),
+        };
+
+        let result = adaptors::Unique::new(
+            ctx.vm.make_iterator(iterable)?,
+            key_fn,
+            ctx.vm.spawn_shared_vm(),
+        );
+
+        Ok(KIterator::new(result).into())
+    });
+
     result.add_fn("windows", |ctx| {
         let expected_error = "an iterable and a chunnk size greater than zero";
 
@@ -853,6 +1072,25 @@ pub fn make_module() -> KMap {
         }
     });
 
+    result.add_fn("zip_longest", |ctx| {
+        let expected_error = "two iterables, and an optional fill value";
+
+        match ctx.instance_and_args(KValue::is_iterable, expected_error)? {
+            (iterable_a, [iterable_b, fill @ ..]) if iterable_b.is_iterable() && fill.len() < 2 => {
+                let iterable_a = iterable_a.clone();
+                let iterable_b = iterable_b.clone();
+                let fill = fill.first().cloned().unwrap_or_default();
+                let result = adaptors::ZipLongest::new(
+                    ctx.vm.make_iterator(iterable_a)?,
+                    ctx.vm.make_iterator(iterable_b)?,
+                    fill,
+                );
+                Ok(KIterator::new(result).into())
+            }
+            (_, unexpected) => type_error_with_slice(expected_error, unexpected),
+        }
+    });
+
     result
 }
 
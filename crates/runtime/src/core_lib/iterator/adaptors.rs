@@ -2,7 +2,11 @@
 
 use super::collect_pair;
 use crate::{prelude::*, Error, KIteratorOutput as Output, Result};
-use std::{collections::VecDeque, result::Result as StdResult};
+use std::{
+    collections::{HashSet, VecDeque},
+    hash::BuildHasherDefault,
+    result::Result as StdResult,
+};
 use thiserror::Error;
 
 /// An iterator that links the output of two iterators together in a chained sequence
@@ -69,19 +73,188 @@ impl Iterator for Chain {
     }
 }
 
+/// An iterator that groups consecutive values that share the same key
+pub struct ChunkBy {
+    iter: KIterator,
+    key_fn: Option<KValue>,
+    vm: KotoVm,
+    // The value (and its key) that ended the most recent group from the front
+    peeked_front: Option<(KValue, KValue)>,
+    // The value (and its key) that ended the most recent group from the back
+    peeked_back: Option<(KValue, KValue)>,
+}
+
+impl ChunkBy {
+    /// Creates a new [ChunkBy] adaptor
+    ///
+    /// If no key function is provided then the values themselves are compared.
+    pub fn new(iter: KIterator, key_fn: Option<KValue>, vm: KotoVm) -> Self {
+        Self {
+            iter,
+            key_fn,
+            vm,
+            peeked_front: None,
+            peeked_back: None,
+        }
+    }
+
+    fn key(&mut self, value: &KValue) -> Result<KValue> {
+        match &self.key_fn {
+            Some(f) => self.vm.call_function(f.clone(), value.clone()),
+            None => Ok(value.clone()),
+        }
+    }
+
+    fn keys_match(&mut self, a: &KValue, b: &KValue) -> Result<bool> {
+        match self
+            .vm
+            .run_binary_op(BinaryOp::Equal, a.clone(), b.clone())?
+        {
+            KValue::Bool(result) => Ok(result),
+            unexpected => runtime_error!(
+                "Expected Bool from == comparison, found '{}'",
+                unexpected.type_as_string()
+            ),
+        }
+    }
+
+    // Returns the next value and its key, either from the front or the back of the iterator
+    fn next_entry(&mut self, forward: bool) -> Option<Result<(KValue, KValue)>> {
+        let peeked = if forward {
+            self.peeked_front.take()
+        } else {
+            self.peeked_back.take()
+        };
+        if let Some(entry) = peeked {
+            return Some(Ok(entry));
+        }
+
+        let output = if forward {
+            self.iter.next()
+        } else {
+            self.iter.next_back()
+        };
+
+        match output.map(collect_pair) {
+            Some(Output::Value(value)) => Some(self.key(&value).map(|key| (key, value))),
+            Some(Output::Error(error)) => Some(Err(error)),
+            Some(_) => unreachable!(), // value pairs have been collected in collect_pair
+            None => {
+                // The adapted iterator is exhausted,
+                // so the only remaining value is the one peeked from the other end.
+                let other = if forward {
+                    self.peeked_back.take()
+                } else {
+                    self.peeked_front.take()
+                };
+                other.map(Ok)
+            }
+        }
+    }
+
+    fn next_group(&mut self, forward: bool) -> Option<Output> {
+        let (key, first) = match self.next_entry(forward)? {
+            Ok(entry) => entry,
+            Err(error) => return Some(Output::Error(error)),
+        };
+
+        let mut group = vec![first];
+
+        while let Some(entry) = self.next_entry(forward) {
+            let (next_key, value) = match entry {
+                Ok(entry) => entry,
+                Err(error) => return Some(Output::Error(error)),
+            };
+
+            match self.keys_match(&key, &next_key) {
+                Ok(true) => group.push(value),
+                Ok(false) => {
+                    if forward {
+                        self.peeked_front = Some((next_key, value));
+                    } else {
+                        self.peeked_back = Some((next_key, value));
+                    }
+                    break;
+                }
+                Err(error) => return Some(Output::Error(error)),
+            }
+        }
+
+        if !forward {
+            group.reverse();
+        }
+
+        Some(Output::Value(KValue::Tuple(group.into())))
+    }
+}
+
+impl KotoIterator for ChunkBy {
+    fn make_copy(&self) -> Result<KIterator> {
+        let result = Self {
+            iter: self.iter.make_copy()?,
+            key_fn: self.key_fn.clone(),
+            vm: self.vm.spawn_shared_vm(),
+            peeked_front: self.peeked_front.clone(),
+            peeked_back: self.peeked_back.clone(),
+        };
+        Ok(KIterator::new(result))
+    }
+
+    fn is_bidirectional(&self) -> bool {
+        self.iter.is_bidirectional()
+    }
+
+    fn next_back(&mut self) -> Option<Output> {
+        self.next_group(false)
+    }
+}
+
+impl Iterator for ChunkBy {
+    type Item = Output;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_group(true)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let peeked =
+            usize::from(self.peeked_front.is_some()) + usize::from(self.peeked_back.is_some());
+        let (lower, upper) = self.iter.size_hint();
+        let lower = usize::from(lower.saturating_add(peeked) > 0);
+        (lower, upper.map(|upper| upper.saturating_add(peeked)))
+    }
+}
+
 /// An iterator that splits the incoming iterator into iterators of size N
 pub struct Chunks {
     iter: KIterator,
     chunk_size: usize,
+    // True if a final chunk with fewer than N elements should be skipped
+    exact: bool,
 }
 
 impl Chunks {
     /// Creates a [Chunks] adapator
     pub fn new(iter: KIterator, chunk_size: usize) -> StdResult<Self, ChunksError> {
+        Self::with_exact(iter, chunk_size, false)
+    }
+
+    /// Creates a [Chunks] adapator that only produces chunks of exactly size N
+    ///
+    /// Any remaining values that don't fill a complete chunk are skipped.
+    pub fn exact(iter: KIterator, chunk_size: usize) -> StdResult<Self, ChunksError> {
+        Self::with_exact(iter, chunk_size, true)
+    }
+
+    fn with_exact(iter: KIterator, chunk_size: usize, exact: bool) -> StdResult<Self, ChunksError> {
         if chunk_size < 1 {
             Err(ChunksError::ChunkSizeMustBeAtLeastOne)
         } else {
-            Ok(Self { iter, chunk_size })
+            Ok(Self {
+                iter,
+                chunk_size,
+                exact,
+            })
         }
     }
 }
@@ -91,6 +264,7 @@ impl KotoIterator for Chunks {
         let result = Self {
             iter: self.iter.make_copy()?,
             chunk_size: self.chunk_size,
+            exact: self.exact,
         };
         Ok(KIterator::new(result))
     }
@@ -111,29 +285,24 @@ impl Iterator for Chunks {
             }
         }
 
-        chunk.map(|chunk| KTuple::from(chunk).into())
+        match chunk {
+            Some(chunk) if self.exact && chunk.len() < self.chunk_size => None,
+            _ => chunk.map(|chunk| KTuple::from(chunk).into()),
+        }
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
         let (lower, upper) = self.iter.size_hint();
 
-        let lower = {
-            let mut chunk_count = lower / self.chunk_size;
-            if lower % self.chunk_size > 0 {
-                chunk_count += 1;
+        let chunk_count = |value_count: usize| {
+            if self.exact {
+                value_count / self.chunk_size
+            } else {
+                value_count.div_ceil(self.chunk_size)
             }
-            chunk_count
         };
 
-        let upper = upper.map(|upper| {
-            let mut chunk_count = upper / self.chunk_size;
-            if upper % self.chunk_size > 0 {
-                chunk_count += 1;
-            }
-            chunk_count
-        });
-
-        (lower, upper)
+        (chunk_count(lower), upper.map(chunk_count))
     }
 }
 
@@ -220,6 +389,65 @@ impl Iterator for Cycle {
     }
 }
 
+/// An iterator that skips over consecutive duplicate values
+pub struct Dedup {
+    groups: ChunkBy,
+}
+
+impl Dedup {
+    /// Creates a new [Dedup] adaptor
+    ///
+    /// If a key function is provided, then values are considered to be duplicates when their keys
+    /// are equal.
+    pub fn new(iter: KIterator, key_fn: Option<KValue>, vm: KotoVm) -> Self {
+        Self {
+            groups: ChunkBy::new(iter, key_fn, vm),
+        }
+    }
+
+    fn first_in_group(output: Output) -> Output {
+        match output {
+            Output::Value(KValue::Tuple(group)) => Output::Value(group[0].clone()),
+            other => other,
+        }
+    }
+}
+
+impl KotoIterator for Dedup {
+    fn make_copy(&self) -> Result<KIterator> {
+        let result = Self {
+            groups: ChunkBy {
+                iter: self.groups.iter.make_copy()?,
+                key_fn: self.groups.key_fn.clone(),
+                vm: self.groups.vm.spawn_shared_vm(),
+                peeked_front: self.groups.peeked_front.clone(),
+                peeked_back: self.groups.peeked_back.clone(),
+            },
+        };
+        Ok(KIterator::new(result))
+    }
+
+    fn is_bidirectional(&self) -> bool {
+        self.groups.is_bidirectional()
+    }
+
+    fn next_back(&mut self) -> Option<Output> {
+        self.groups.next_back().map(Self::first_in_group)
+    }
+}
+
+impl Iterator for Dedup {
+    type Item = Output;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.groups.next().map(Self::first_in_group)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.groups.size_hint()
+    }
+}
+
 /// An iterator that runs a function on each output value from the adapted iterator
 pub struct Each {
     iter: KIterator,
@@ -324,10 +552,14 @@ impl Iterator for Enumerate {
 }
 
 /// An iterator that flattens the output of nested iterators
+///
+/// Reversed iteration is supported when the adapted iterator is bidirectional, with an error
+/// being produced if a nested iterator isn't bidirectional.
 pub struct Flatten {
     vm: KotoVm,
     iter: KIterator,
     nested: Option<KIterator>,
+    nested_back: Option<KIterator>,
 }
 
 impl Flatten {
@@ -337,22 +569,68 @@ impl Flatten {
             vm,
             iter,
             nested: None,
+            nested_back: None,
+        }
+    }
+
+    // Makes a nested iterator from the adapted iterator's output
+    //
+    // If the output isn't iterable then it's returned as an error, to be passed on directly.
+    fn make_nested(&mut self, output: Output, forward: bool) -> StdResult<KIterator, Output> {
+        match collect_pair(output) {
+            Output::Value(iterable) if iterable.is_iterable() => {
+                match self.vm.make_iterator(iterable) {
+                    Ok(nested) if forward || nested.is_bidirectional() => Ok(nested),
+                    Ok(_) => Err(Output::Error(Error::from(
+                        "flatten: a nested iterator isn't bidirectional",
+                    ))),
+                    Err(error) => Err(Output::Error(error)),
+                }
+            }
+            other => Err(other),
         }
     }
 }
 
 impl KotoIterator for Flatten {
     fn make_copy(&self) -> Result<KIterator> {
+        let copy_nested = |nested: &Option<KIterator>| match nested {
+            Some(nested) => nested.make_copy().map(Some),
+            None => Ok(None),
+        };
+
         let result = Self {
             vm: self.vm.spawn_shared_vm(),
             iter: self.iter.make_copy()?,
-            nested: match &self.nested {
-                Some(nested) => Some(nested.make_copy()?),
-                None => None,
-            },
+            nested: copy_nested(&self.nested)?,
+            nested_back: copy_nested(&self.nested_back)?,
         };
         Ok(KIterator::new(result))
     }
+
+    fn is_bidirectional(&self) -> bool {
+        self.iter.is_bidirectional()
+    }
+
+    fn next_back(&mut self) -> Option<Output> {
+        loop {
+            if let Some(nested) = &mut self.nested_back {
+                if let result @ Some(_) = nested.next_back() {
+                    return result;
+                }
+                self.nested_back = None;
+            }
+
+            match self.iter.next_back() {
+                Some(output) => match self.make_nested(output, false) {
+                    Ok(nested) => self.nested_back = Some(nested),
+                    Err(output) => return Some(output),
+                },
+                // Once the adapted iterator is exhausted, the front's nested iterator is drained
+                None => return self.nested.as_mut().and_then(|nested| nested.next_back()),
+            }
+        }
+    }
 }
 
 impl Iterator for Flatten {
@@ -364,24 +642,84 @@ impl Iterator for Flatten {
                 if let result @ Some(_) = nested.next() {
                     return result;
                 }
+                self.nested = None;
             }
 
-            match self.iter.next().map(collect_pair) {
-                Some(Output::Value(iterable)) if iterable.is_iterable() => {
-                    match self.vm.make_iterator(iterable) {
-                        Ok(nested) => {
-                            self.nested = Some(nested);
-                            continue;
-                        }
-                        Err(error) => return Some(Output::Error(error)),
-                    }
-                }
-                other => return other,
+            match self.iter.next() {
+                Some(output) => match self.make_nested(output, true) {
+                    Ok(nested) => self.nested = Some(nested),
+                    Err(output) => return Some(output),
+                },
+                // Once the adapted iterator is exhausted, the back's nested iterator is drained
+                None => return self.nested_back.as_mut().and_then(|nested| nested.next()),
             }
         }
     }
 }
 
+/// An iterator that alternates between the output of two iterators
+///
+/// Once either iterator is exhausted, the remaining output from the other iterator is produced.
+pub struct Interleave {
+    iter_a: KIterator,
+    iter_b: KIterator,
+    next_is_b: bool,
+}
+
+impl Interleave {
+    /// Creates a new [Interleave] adaptor
+    pub fn new(iter_a: KIterator, iter_b: KIterator) -> Self {
+        Self {
+            iter_a,
+            iter_b,
+            next_is_b: false,
+        }
+    }
+}
+
+impl KotoIterator for Interleave {
+    fn make_copy(&self) -> Result<KIterator> {
+        let result = Self {
+            iter_a: self.iter_a.make_copy()?,
+            iter_b: self.iter_b.make_copy()?,
+            next_is_b: self.next_is_b,
+        };
+        Ok(KIterator::new(result))
+    }
+}
+
+impl Iterator for Interleave {
+    type Item = Output;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (first, second) = if self.next_is_b {
+            (&mut self.iter_b, &mut self.iter_a)
+        } else {
+            (&mut self.iter_a, &mut self.iter_b)
+        };
+
+        self.next_is_b = !self.next_is_b;
+
+        match first.next() {
+            output @ Some(_) => output,
+            None => second.next(),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower_a, upper_a) = self.iter_a.size_hint();
+        let (lower_b, upper_b) = self.iter_b.size_hint();
+
+        let lower = lower_a.saturating_add(lower_b);
+        let upper = match (upper_a, upper_b) {
+            (Some(upper_a), Some(upper_b)) => upper_a.checked_add(upper_b),
+            _ => None,
+        };
+
+        (lower, upper)
+    }
+}
+
 /// An iterator that inserts a separator value between each output value from the adapted iterator
 pub struct Intersperse {
     iter: KIterator,
@@ -715,6 +1053,68 @@ pub enum ReversedError {
     CopyError(Error),
 }
 
+/// An iterator that outputs the running result of an accumulating function
+pub struct Scan {
+    iter: KIterator,
+    accumulator: KValue,
+    function: KValue,
+    vm: KotoVm,
+}
+
+impl Scan {
+    /// Creates a new [Scan] adaptor
+    pub fn new(iter: KIterator, initial_value: KValue, function: KValue, vm: KotoVm) -> Self {
+        Self {
+            iter,
+            accumulator: initial_value,
+            function,
+            vm,
+        }
+    }
+}
+
+impl KotoIterator for Scan {
+    fn make_copy(&self) -> Result<KIterator> {
+        let result = Self {
+            iter: self.iter.make_copy()?,
+            accumulator: self.accumulator.clone(),
+            function: self.function.clone(),
+            vm: self.vm.spawn_shared_vm(),
+        };
+        Ok(KIterator::new(result))
+    }
+}
+
+impl Iterator for Scan {
+    type Item = Output;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let result = match self.iter.next().map(collect_pair)? {
+            Output::Value(value) => {
+                let accumulator = self.accumulator.clone();
+                match self
+                    .vm
+                    .call_function(self.function.clone(), &[accumulator, value])
+                {
+                    Ok(result) => {
+                        self.accumulator = result.clone();
+                        Output::Value(result)
+                    }
+                    Err(error) => Output::Error(error),
+                }
+            }
+            error @ Output::Error(_) => error,
+            _ => unreachable!(), // value pairs have been collected in collect_pair
+        };
+
+        Some(result)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
 /// An iterator that yields the next value from the input, and then steps forward by
 pub struct Step {
     iter: KIterator,
@@ -890,6 +1290,75 @@ impl Iterator for TakeWhile {
     }
 }
 
+/// An iterator that skips over any values that have already been produced
+pub struct Unique {
+    iter: KIterator,
+    key_fn: Option<KValue>,
+    vm: KotoVm,
+    seen: HashSet<ValueKey, BuildHasherDefault<KotoHasher>>,
+}
+
+impl Unique {
+    /// Creates a new [Unique] adaptor
+    ///
+    /// If a key function is provided, then values are considered to be duplicates when their keys
+    /// are equal.
+    pub fn new(iter: KIterator, key_fn: Option<KValue>, vm: KotoVm) -> Self {
+        Self {
+            iter,
+            key_fn,
+            vm,
+            seen: HashSet::default(),
+        }
+    }
+
+    fn is_new_value(&mut self, value: &KValue) -> Result<bool> {
+        let key = match &self.key_fn {
+            Some(f) => self.vm.call_function(f.clone(), value.clone())?,
+            None => value.clone(),
+        };
+        let key = self.vm.make_value_key(key)?;
+        Ok(self.seen.insert(key))
+    }
+}
+
+impl KotoIterator for Unique {
+    fn make_copy(&self) -> Result<KIterator> {
+        let result = Self {
+            iter: self.iter.make_copy()?,
+            key_fn: self.key_fn.clone(),
+            vm: self.vm.spawn_shared_vm(),
+            seen: self.seen.clone(),
+        };
+        Ok(KIterator::new(result))
+    }
+}
+
+impl Iterator for Unique {
+    type Item = Output;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(output) = self.iter.next().map(collect_pair) {
+            match output {
+                Output::Value(value) => match self.is_new_value(&value) {
+                    Ok(true) => return Some(Output::Value(value)),
+                    Ok(false) => continue,
+                    Err(error) => return Some(Output::Error(error)),
+                },
+                error @ Output::Error(_) => return Some(error),
+                _ => unreachable!(), // value pairs have been collected in collect_pair
+            }
+        }
+
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.iter.size_hint();
+        (usize::from(lower > 0), upper)
+    }
+}
+
 /// An iterator that splits the incoming iterator into overlapping iterators of size N
 pub struct Windows {
     iter: KIterator,
@@ -1016,4 +1485,73 @@ impl Iterator for Zip {
     }
 }
 
+/// An iterator that zips the output of two iterators together until both are exhausted
+///
+/// Once either iterator is exhausted, its side of the output pairs is filled with the fill value.
+pub struct ZipLongest {
+    iter_a: KIterator,
+    iter_b: KIterator,
+    fill: KValue,
+}
+
+impl ZipLongest {
+    /// Creates a new [ZipLongest] adaptor
+    pub fn new(iter_a: KIterator, iter_b: KIterator, fill: KValue) -> Self {
+        Self {
+            iter_a,
+            iter_b,
+            fill,
+        }
+    }
+}
+
+impl KotoIterator for ZipLongest {
+    fn make_copy(&self) -> Result<KIterator> {
+        let result = Self {
+            iter_a: self.iter_a.make_copy()?,
+            iter_b: self.iter_b.make_copy()?,
+            fill: self.fill.clone(),
+        };
+        Ok(KIterator::new(result))
+    }
+}
+
+impl Iterator for ZipLongest {
+    type Item = Output;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let value_a = match self.iter_a.next().map(collect_pair) {
+            Some(Output::Value(value)) => Some(value),
+            error @ Some(Output::Error(_)) => return error,
+            _ => None,
+        };
+        let value_b = match self.iter_b.next().map(collect_pair) {
+            Some(Output::Value(value)) => Some(value),
+            error @ Some(Output::Error(_)) => return error,
+            _ => None,
+        };
+
+        match (value_a, value_b) {
+            (None, None) => None,
+            (a, b) => Some(Output::ValuePair(
+                a.unwrap_or_else(|| self.fill.clone()),
+                b.unwrap_or_else(|| self.fill.clone()),
+            )),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower_a, upper_a) = self.iter_a.size_hint();
+        let (lower_b, upper_b) = self.iter_b.size_hint();
+
+        let lower = lower_a.max(lower_b);
+        let upper = match (upper_a, upper_b) {
+            (Some(upper_a), Some(upper_b)) => Some(upper_a.max(upper_b)),
+            _ => None,
+        };
+
+        (lower, upper)
+    }
+}
+
 // For tests, see runtime/tests/iterator_tests.rs
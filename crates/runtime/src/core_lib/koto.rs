@@ -16,6 +16,7 @@ pub fn make_module() -> KMap {
     result.add_fn("copy", |ctx| match ctx.args() {
         [KValue::Iterator(iter)] => Ok(iter.make_copy()?.into()),
         [KValue::List(l)] => Ok(KList::with_data(l.data().clone()).into()),
+        [KValue::Set(s)] => Ok(KSet::with_data(s.data().clone()).into()),
         [KValue::Map(m)] => {
             let result = KMap::with_contents(
                 m.data().clone(),
@@ -36,7 +37,7 @@ pub fn make_module() -> KMap {
     result.add_fn("exports", |ctx| Ok(KValue::Map(ctx.vm.exports().clone())));
 
     result.add_fn("hash", |ctx| match ctx.args() {
-        [value] => match ValueKey::try_from(value.clone()) {
+        [value] => match ctx.vm.make_value_key(value.clone()) {
             Ok(key) => {
                 let mut hasher = KotoHasher::default();
                 key.hash(&mut hasher);
@@ -2,15 +2,37 @@
 
 use super::{
     iterator::collect_pair,
-    value_sort::{compare_values, sort_values},
+    value_sort::{self, call_bool_fn, sort_values_with_options, SortOptions},
 };
-use crate::prelude::*;
+use crate::{prelude::*, Result};
 use std::{cmp::Ordering, ops::DerefMut};
 
 /// Initializes the `list` core library module
 pub fn make_module() -> KMap {
     let result = KMap::with_type("core.list");
 
+    result.add_fn("binary_search", |ctx| {
+        let expected_error = "a List, a Value, and an optional key function";
+
+        let (l, value, key_fn) = match ctx.instance_and_args(is_list, expected_error)? {
+            (KValue::List(l), [value]) => (l.clone(), value.clone(), None),
+            (KValue::List(l), [value, key_fn]) if key_fn.is_callable() => {
+                (l.clone(), value.clone(), Some(key_fn.clone()))
+            }
+            (_, unexpected) => return type_error_with_slice(expected_error, unexpected),
+        };
+
+        let result = value_sort::binary_search(
+            ctx.vm,
+            l.len(),
+            |index| list_value(&l, index),
+            &value,
+            key_fn.as_ref(),
+        )?;
+
+        Ok(result.map_or(KValue::Null, KValue::from))
+    });
+
     result.add_fn("clear", |ctx| {
         let expected_error = "a List";
 
@@ -52,6 +74,53 @@ pub fn make_module() -> KMap {
         }
     });
 
+    result.add_fn("dedup", |ctx| {
+        let expected_error = "a List and an optional key function";
+
+        let (l, key_fn) = match ctx.instance_and_args(is_list, expected_error)? {
+            (KValue::List(l), []) => (l.clone(), None),
+            (KValue::List(l), [key_fn]) if key_fn.is_callable() => {
+                (l.clone(), Some(key_fn.clone()))
+            }
+            (_, unexpected) => return type_error_with_slice(expected_error, unexpected),
+        };
+
+        // A copy of the data is deduplicated so that the list isn't borrowed while the key
+        // function and equality operators are called.
+        let mut data = l.data().clone();
+        let mut previous_key = None;
+        let mut write_index = 0;
+        let mut read_index = 0;
+        let mut result = Ok(());
+
+        while read_index < data.len() {
+            match dedup_key(
+                ctx.vm,
+                &data[read_index],
+                key_fn.as_ref(),
+                previous_key.as_ref(),
+            ) {
+                Ok(Some(key)) => {
+                    data.swap(write_index, read_index);
+                    write_index += 1;
+                    previous_key = Some(key);
+                }
+                Ok(None) => {}
+                Err(error) => {
+                    result = Err(error);
+                    break;
+                }
+            }
+            read_index += 1;
+        }
+
+        // Remove the duplicates, keeping any values that weren't reached due to an error
+        data.drain(write_index..read_index);
+        *l.data_mut() = data;
+
+        result.map(|_| KValue::List(l))
+    });
+
     result.add_fn("extend", |ctx| {
         let expected_error = "a List and iterable";
 
@@ -170,6 +239,22 @@ pub fn make_module() -> KMap {
         }
     });
 
+    result.add_fn("partition_point", |ctx| {
+        let expected_error = "a List and a predicate function";
+
+        let (l, f) = match ctx.instance_and_args(is_list, expected_error)? {
+            (KValue::List(l), [f]) if f.is_callable() => (l.clone(), f.clone()),
+            (_, unexpected) => return type_error_with_slice(expected_error, unexpected),
+        };
+
+        let result = value_sort::partition_point(l.len(), |index| {
+            let value = list_value(&l, index)?;
+            call_bool_fn(ctx.vm, &f, &[value], "predicate")
+        })?;
+
+        Ok(result.into())
+    });
+
     result.add_fn("pop", |ctx| {
         let expected_error = "a List";
 
@@ -341,65 +426,79 @@ pub fn make_module() -> KMap {
         }
     });
 
-    result.add_fn("sort", |ctx| {
-        let expected_error = "a List, and an optional key function";
+    result.add_fn("rotate", |ctx| {
+        let expected_error = "a List and a Number";
 
         match ctx.instance_and_args(is_list, expected_error)? {
-            (KValue::List(l), []) => {
-                let l = l.clone();
-                let mut data = l.data_mut();
-                sort_values(ctx.vm, &mut data)?;
+            (KValue::List(l), [KValue::Number(n)]) => {
+                {
+                    let mut data = l.data_mut();
+                    let len = data.len();
+                    if len > 0 {
+                        let n = i64::from(n).rem_euclid(len as i64) as usize;
+                        data.rotate_right(n);
+                    }
+                }
                 Ok(KValue::List(l.clone()))
             }
-            (KValue::List(l), [f]) if f.is_callable() => {
-                let l = l.clone();
-                let f = f.clone();
-
-                // apply function and construct a vec of (key, value)
-                let mut pairs = l
-                    .data()
-                    .iter()
-                    .map(
-                        |value| match ctx.vm.call_function(f.clone(), value.clone()) {
-                            Ok(key) => Ok((key, value.clone())),
-                            Err(e) => Err(e),
-                        },
-                    )
-                    .collect::<Result<Vec<_>, _>>()?;
-
-                let mut error = None;
-
-                // sort array by key (i.e. from [key, value])
-                pairs.sort_by(|a, b| {
-                    if error.is_some() {
-                        return Ordering::Equal;
-                    }
+            (_, unexpected) => type_error_with_slice(expected_error, unexpected),
+        }
+    });
 
-                    match compare_values(ctx.vm, &a.0, &b.0) {
-                        Ok(ordering) => ordering,
-                        Err(e) => {
-                            error.get_or_insert(e);
-                            Ordering::Equal
-                        }
-                    }
-                });
+    result.add_fn("sort", |ctx| {
+        let expected_error = "a List, an optional key function, and an optional Bool";
 
-                if let Some(error) = error {
-                    return Err(error);
+        match ctx.instance_and_args(is_list, expected_error)? {
+            (KValue::List(l), args) => match SortOptions::from_args(args) {
+                Some(options) => {
+                    let l = l.clone();
+                    sort_list(&l, |data| {
+                        sort_values_with_options(ctx.vm, data, options, true)
+                    })?;
+                    Ok(KValue::List(l))
                 }
+                None => type_error_with_slice(expected_error, args),
+            },
+            (_, unexpected) => type_error_with_slice(expected_error, unexpected),
+        }
+    });
 
-                // collect values
-                *l.data_mut() = pairs
-                    .iter()
-                    .map(|(_key, value)| value.clone())
-                    .collect::<_>();
+    result.add_fn("sort_by", |ctx| {
+        let expected_error = "a List and a comparator function";
 
+        match ctx.instance_and_args(is_list, expected_error)? {
+            (KValue::List(l), [f]) if f.is_callable() => {
+                let l = l.clone();
+                let f = f.clone();
+                sort_list(&l, |data| {
+                    value_sort::sort_by(data, |a, b| {
+                        call_bool_fn(ctx.vm, &f, &[a.clone(), b.clone()], "comparator")
+                    })
+                })?;
                 Ok(KValue::List(l))
             }
             (_, unexpected) => type_error_with_slice(expected_error, unexpected),
         }
     });
 
+    result.add_fn("sort_unstable", |ctx| {
+        let expected_error = "a List, an optional key function, and an optional Bool";
+
+        match ctx.instance_and_args(is_list, expected_error)? {
+            (KValue::List(l), args) => match SortOptions::from_args(args) {
+                Some(options) => {
+                    let l = l.clone();
+                    sort_list(&l, |data| {
+                        sort_values_with_options(ctx.vm, data, options, false)
+                    })?;
+                    Ok(KValue::List(l))
+                }
+                None => type_error_with_slice(expected_error, args),
+            },
+            (_, unexpected) => type_error_with_slice(expected_error, unexpected),
+        }
+    });
+
     result.add_fn("swap", |ctx| {
         let expected_error = "two Lists";
 
@@ -448,3 +547,48 @@ pub fn make_module() -> KMap {
 fn is_list(value: &KValue) -> bool {
     matches!(value, KValue::List(_))
 }
+
+// Returns a copy of the list's value at the given index
+//
+// An error is returned if the index is no longer valid, e.g. if the list has been modified
+// by a function that was called during a search.
+fn list_value(list: &KList, index: usize) -> Result<KValue> {
+    match list.data().get(index) {
+        Some(value) => Ok(value.clone()),
+        None => runtime_error!("the List was modified during the operation"),
+    }
+}
+
+// Sorts the list's data
+//
+// A copy of the data is sorted so that the list isn't borrowed while comparisons are made,
+// with the result written back to the list even if an error occurs.
+fn sort_list(list: &KList, sort: impl FnOnce(&mut [KValue]) -> Result<()>) -> Result<()> {
+    let mut data = list.data().clone();
+    let result = sort(&mut data);
+    *list.data_mut() = data;
+    result
+}
+
+// Returns the value's key if it isn't a duplicate of the previous key, otherwise None
+fn dedup_key(
+    vm: &mut KotoVm,
+    value: &KValue,
+    key_fn: Option<&KValue>,
+    previous_key: Option<&KValue>,
+) -> Result<Option<KValue>> {
+    let key = match key_fn {
+        Some(f) => vm.call_function(f.clone(), value.clone())?,
+        None => value.clone(),
+    };
+
+    let Some(previous_key) = previous_key else {
+        return Ok(Some(key));
+    };
+
+    match vm.run_binary_op(BinaryOp::Equal, previous_key.clone(), key.clone())? {
+        KValue::Bool(true) => Ok(None),
+        KValue::Bool(false) => Ok(Some(key)),
+        unexpected => type_error("a Bool from the equality comparison", &unexpected),
+    }
+}
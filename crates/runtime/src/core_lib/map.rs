@@ -13,7 +13,7 @@ pub fn make_module() -> KMap {
 
         match map_instance_and_args(ctx, expected_error)? {
             (KValue::Map(m), []) => {
-                m.data_mut().clear();
+                m.data_mut().clear()?;
                 Ok(KValue::Map(m.clone()))
             }
             (_, unexpected) => type_error_with_slice(expected_error, unexpected),
@@ -25,7 +25,9 @@ pub fn make_module() -> KMap {
 
         match map_instance_and_args(ctx, expected_error)? {
             (KValue::Map(m), [key]) => {
-                let result = m.data().contains_key(&ValueKey::try_from(key.clone())?);
+                let m = m.clone();
+                let key = ctx.vm.make_value_key(key.clone())?;
+                let result = m.data().contains_key(&key);
                 Ok(result.into())
             }
             (_, unexpected) => type_error_with_slice(expected_error, unexpected),
@@ -42,7 +44,7 @@ pub fn make_module() -> KMap {
                         .data()
                         .iter()
                         .map(|(key, value)| (key.clone(), value.clone())),
-                );
+                )?;
                 Ok(KValue::Map(m.clone()))
             }
             (KValue::Map(m), [iterable]) if iterable.is_iterable() => {
@@ -68,7 +70,7 @@ pub fn make_module() -> KMap {
                             Output::Error(error) => return Err(error),
                         };
 
-                        map_data.insert(ValueKey::try_from(key.clone())?, value);
+                        map_data.insert(ctx.vm.make_value_key(key)?, value)?;
                     }
                 }
 
@@ -83,15 +85,13 @@ pub fn make_module() -> KMap {
             let expected_error = "a Map and a key, with an optional default value";
 
             match map_instance_and_args(ctx, expected_error)? {
-                (KValue::Map(map), [key]) => (map, key, &KValue::Null),
-                (KValue::Map(map), [key, default]) => (map, key, default),
+                (KValue::Map(map), [key]) => (map.clone(), key.clone(), KValue::Null),
+                (KValue::Map(map), [key, default]) => (map.clone(), key.clone(), default.clone()),
                 (_, unexpected) => return type_error_with_slice(expected_error, unexpected),
             }
         };
 
-        let result = map
-            .get(&ValueKey::try_from(key.clone())?)
-            .unwrap_or_else(|| default.clone());
+        let result = map.get(&ctx.vm.make_value_key(key)?).unwrap_or(default);
 
         Ok(result)
     });
@@ -137,21 +137,17 @@ pub fn make_module() -> KMap {
         let expected_error = "a Map and key (with optional Value to insert)";
 
         match map_instance_and_args(ctx, expected_error)? {
-            (KValue::Map(m), [key]) => match m
-                .data_mut()
-                .insert(ValueKey::try_from(key.clone())?, KValue::Null)
-            {
-                Some(old_value) => Ok(old_value),
-                None => Ok(KValue::Null),
-            },
+            (KValue::Map(m), [key]) => {
+                let (m, key) = (m.clone(), key.clone());
+                let key = ctx.vm.make_value_key(key)?;
+                let old_value = m.data_mut().insert(key, KValue::Null)?;
+                Ok(old_value.unwrap_or_default())
+            }
             (KValue::Map(m), [key, value]) => {
-                match m
-                    .data_mut()
-                    .insert(ValueKey::try_from(key.clone())?, value.clone())
-                {
-                    Some(old_value) => Ok(old_value),
-                    None => Ok(KValue::Null),
-                }
+                let (m, key, value) = (m.clone(), key.clone(), value.clone());
+                let key = ctx.vm.make_value_key(key)?;
+                let old_value = m.data_mut().insert(key, value)?;
+                Ok(old_value.unwrap_or_default())
             }
             (_, unexpected) => type_error_with_slice(expected_error, unexpected),
         }
@@ -183,10 +179,10 @@ pub fn make_module() -> KMap {
 
         match map_instance_and_args(ctx, expected_error)? {
             (KValue::Map(m), [key]) => {
-                match m.data_mut().shift_remove(&ValueKey::try_from(key.clone())?) {
-                    Some(old_value) => Ok(old_value),
-                    None => Ok(KValue::Null),
-                }
+                let (m, key) = (m.clone(), key.clone());
+                let key = ctx.vm.make_value_key(key)?;
+                let old_value = m.data_mut().shift_remove(&key)?;
+                Ok(old_value.unwrap_or_default())
             }
             (_, unexpected) => type_error_with_slice(expected_error, unexpected),
         }
@@ -211,7 +207,7 @@ pub fn make_module() -> KMap {
                             Ordering::Equal
                         }
                     }
-                });
+                })?;
 
                 if let Some(error) = error {
                     error
@@ -231,7 +227,7 @@ pub fn make_module() -> KMap {
                  -> Result<KValue> {
                     let value =
                         vm.call_function(f.clone(), &[key.value().clone(), value.clone()])?;
-                    cache.insert(key.clone(), value.clone());
+                    cache.insert(key.clone(), value.clone())?;
                     Ok(value)
                 };
 
@@ -269,7 +265,7 @@ pub fn make_module() -> KMap {
                             Ordering::Equal
                         }
                     }
-                });
+                })?;
 
                 if let Some(error) = error {
                     error
@@ -285,20 +281,12 @@ pub fn make_module() -> KMap {
         let expected_error = "a Map, key, optional default Value, and update function";
 
         match map_instance_and_args(ctx, expected_error)? {
-            (KValue::Map(m), [key, f]) if f.is_callable() => do_map_update(
-                m.clone(),
-                ValueKey::try_from(key.clone())?,
-                KValue::Null,
-                f.clone(),
-                ctx.vm,
-            ),
-            (KValue::Map(m), [key, default, f]) if f.is_callable() => do_map_update(
-                m.clone(),
-                ValueKey::try_from(key.clone())?,
-                default.clone(),
-                f.clone(),
-                ctx.vm,
-            ),
+            (KValue::Map(m), [key, f]) if f.is_callable() => {
+                do_map_update(m.clone(), key.clone(), KValue::Null, f.clone(), ctx.vm)
+            }
+            (KValue::Map(m), [key, default, f]) if f.is_callable() => {
+                do_map_update(m.clone(), key.clone(), default.clone(), f.clone(), ctx.vm)
+            }
             (_, unexpected) => type_error_with_slice(expected_error, unexpected),
         }
     });
@@ -333,18 +321,19 @@ pub fn make_module() -> KMap {
 
 fn do_map_update(
     map: KMap,
-    key: ValueKey,
+    key: KValue,
     default: KValue,
     f: KValue,
     vm: &mut KotoVm,
 ) -> Result<KValue> {
+    let key = vm.make_value_key(key)?;
     if !map.data().contains_key(&key) {
-        map.data_mut().insert(key.clone(), default);
+        map.data_mut().insert(key.clone(), default)?;
     }
     let value = map.get(&key).unwrap();
     match vm.call_function(f, value) {
         Ok(new_value) => {
-            map.data_mut().insert(key, new_value.clone());
+            map.data_mut().insert(key, new_value.clone())?;
             Ok(new_value)
         }
         Err(error) => Err(error),
@@ -176,6 +176,14 @@ pub fn make_module() -> KMap {
         let expected_error = "two Numbers";
 
         match ctx.instance_and_args(is_number, expected_error)? {
+            (Number(a), [Number(b)]) if ctx.vm.checked_integer_overflow() => {
+                match a.checked_pow(*b) {
+                    Some(result) => Ok(Number(result)),
+                    None => {
+                        runtime_error!("Integer overflow while raising '{a}' to the power '{b}'")
+                    }
+                }
+            }
             (Number(a), [Number(b)]) => Ok(Number(a.pow(*b))),
             (_, unexpected) => type_error_with_slice(expected_error, unexpected),
         }
@@ -1,17 +1,39 @@
 //! The `os` core library module
 
 use crate::{derive::*, prelude::*, Result};
-use chrono::prelude::*;
+use chrono::{
+    format::{Item, StrftimeItems},
+    prelude::*,
+    ParseError, TimeDelta,
+};
 use instant::Instant;
+use std::cmp::Ordering;
 
 /// Initializes the `os` core library module
 pub fn make_module() -> KMap {
-    use KValue::Number;
+    use KValue::{Number, Str};
 
     let result = KMap::with_type("core.os");
 
+    result.add_fn("duration", |ctx| match ctx.args() {
+        [Number(seconds)] => Duration::from_seconds(seconds.into()).map(KValue::from),
+        unexpected => type_error_with_slice("a Number of seconds", unexpected),
+    });
+
     result.add_fn("name", |_| Ok(std::env::consts::OS.into()));
 
+    result.add_fn("parse_time", |ctx| match ctx.args() {
+        [Str(s)] => match chrono::DateTime::parse_from_rfc3339(s) {
+            Ok(time) => Ok(DateTime::with_chrono_datetime(time)),
+            Err(error) => runtime_error!("Failed to parse '{s}' as an RFC 3339 time: {error}"),
+        },
+        [Str(s), Str(format)] => match DateTime::parse_with_format(s, format) {
+            Ok(time) => Ok(DateTime::with_chrono_datetime(time)),
+            Err(error) => runtime_error!("Failed to parse '{s}' with format '{format}': {error}"),
+        },
+        unexpected => type_error_with_slice("a String, with an optional format String", unexpected),
+    });
+
     result.add_fn("start_timer", |_| Ok(Timer::now()));
 
     result.add_fn("time", |ctx| match ctx.args() {
@@ -43,16 +65,44 @@ impl DateTime {
         Self::with_chrono_datetime(Local::now().fixed_offset())
     }
 
+    // Parses a time using a strftime-style format
+    //
+    // If the format doesn't include a timezone then the local timezone is used,
+    // and formats that only contain a date will return a time at midnight.
+    fn parse_with_format(
+        s: &str,
+        format: &str,
+    ) -> std::result::Result<chrono::DateTime<FixedOffset>, ParseError> {
+        use chrono::format::ParseErrorKind::NotEnough;
+
+        let naive = match chrono::DateTime::parse_from_str(s, format) {
+            Ok(time) => return Ok(time),
+            Err(error) if error.kind() == NotEnough => {
+                match NaiveDateTime::parse_from_str(s, format) {
+                    Ok(naive) => naive,
+                    Err(error) if error.kind() == NotEnough => {
+                        NaiveDate::parse_from_str(s, format)?.and_time(NaiveTime::MIN)
+                    }
+                    Err(error) => return Err(error),
+                }
+            }
+            Err(error) => return Err(error),
+        };
+
+        let local = Local
+            .from_local_datetime(&naive)
+            .earliest()
+            .unwrap_or_else(|| naive.and_utc().with_timezone(&Local));
+        Ok(local.fixed_offset())
+    }
+
     fn from_seconds(seconds: f64, maybe_offset: Option<i64>) -> Result<KValue> {
         let seconds_i64 = seconds as i64;
         let sub_nanos = (seconds.fract() * 1.0e9) as u32;
         match chrono::DateTime::from_timestamp(seconds_i64, sub_nanos) {
             Some(utc) => {
                 let offset = match maybe_offset {
-                    Some(offset) => match FixedOffset::east_opt(offset as i32) {
-                        Some(offset) => offset,
-                        None => return runtime_error!("time offset is out of range: {offset}"),
-                    },
+                    Some(offset) => make_offset(offset)?,
                     None => *Local::now().offset(),
                 };
                 let local = utc.with_timezone(&offset);
@@ -67,6 +117,24 @@ impl DateTime {
         self.0.day().into()
     }
 
+    #[koto_method]
+    fn format(&self, args: &[KValue]) -> Result<KValue> {
+        match args {
+            [KValue::Str(format)] => {
+                let items = StrftimeItems::new(format).collect::<Vec<_>>();
+                if items.iter().any(|item| matches!(item, Item::Error)) {
+                    return runtime_error!("Invalid format string: '{format}'");
+                }
+                Ok(self
+                    .0
+                    .format_with_items(items.into_iter())
+                    .to_string()
+                    .into())
+            }
+            unexpected => type_error_with_slice("a format String", unexpected),
+        }
+    }
+
     #[koto_method]
     fn hour(&self) -> KValue {
         self.0.hour().into()
@@ -82,6 +150,11 @@ impl DateTime {
         self.0.month().into()
     }
 
+    #[koto_method]
+    fn ordinal(&self) -> KValue {
+        self.0.ordinal().into()
+    }
+
     #[koto_method]
     fn second(&self) -> KValue {
         self.0.second().into()
@@ -109,10 +182,48 @@ impl DateTime {
         self.0.format("%z").to_string().into()
     }
 
+    #[koto_method]
+    fn to_local(&self) -> KValue {
+        Self::with_chrono_datetime(self.0.with_timezone(&Local).fixed_offset())
+    }
+
+    #[koto_method]
+    fn to_rfc3339(&self) -> KValue {
+        self.0.to_rfc3339().into()
+    }
+
+    #[koto_method]
+    fn to_utc(&self) -> KValue {
+        Self::with_chrono_datetime(self.0.to_utc().fixed_offset())
+    }
+
+    #[koto_method]
+    fn weekday(&self) -> KValue {
+        self.0.weekday().number_from_monday().into()
+    }
+
+    #[koto_method]
+    fn with_timezone_offset(&self, args: &[KValue]) -> Result<KValue> {
+        match args {
+            [KValue::Number(offset)] => {
+                let offset = make_offset(offset.into())?;
+                Ok(Self::with_chrono_datetime(self.0.with_timezone(&offset)))
+            }
+            unexpected => type_error_with_slice("a timezone offset in seconds", unexpected),
+        }
+    }
+
     #[koto_method]
     fn year(&self) -> KValue {
         self.0.year().into()
     }
+
+    fn compare(&self, rhs: &KValue) -> Result<Ordering> {
+        match rhs {
+            KValue::Object(o) if o.is_a::<Self>() => Ok(self.0.cmp(&o.cast::<Self>()?.0)),
+            unexpected => type_error(Self::type_static(), unexpected),
+        }
+    }
 }
 
 impl KotoObject for DateTime {
@@ -120,6 +231,224 @@ impl KotoObject for DateTime {
         ctx.append(self.0.format("%F %T").to_string());
         Ok(())
     }
+
+    fn add(&self, rhs: &KValue) -> Result<KValue> {
+        match rhs {
+            KValue::Object(o) if o.is_a::<Duration>() => {
+                match self.0.checked_add_signed(o.cast::<Duration>()?.0) {
+                    Some(result) => Ok(Self::with_chrono_datetime(result)),
+                    None => runtime_error!("The resulting time is out of range"),
+                }
+            }
+            unexpected => type_error(Duration::type_static(), unexpected),
+        }
+    }
+
+    fn subtract(&self, rhs: &KValue) -> Result<KValue> {
+        match rhs {
+            KValue::Object(o) if o.is_a::<Duration>() => {
+                match self.0.checked_sub_signed(o.cast::<Duration>()?.0) {
+                    Some(result) => Ok(Self::with_chrono_datetime(result)),
+                    None => runtime_error!("The resulting time is out of range"),
+                }
+            }
+            KValue::Object(o) if o.is_a::<Self>() => {
+                let rhs = o.cast::<Self>()?;
+                Ok(Duration(self.0.signed_duration_since(rhs.0)).into())
+            }
+            unexpected => type_error("a DateTime or Duration", unexpected),
+        }
+    }
+
+    fn less(&self, rhs: &KValue) -> Result<bool> {
+        self.compare(rhs).map(Ordering::is_lt)
+    }
+
+    fn less_or_equal(&self, rhs: &KValue) -> Result<bool> {
+        self.compare(rhs).map(Ordering::is_le)
+    }
+
+    fn greater(&self, rhs: &KValue) -> Result<bool> {
+        self.compare(rhs).map(Ordering::is_gt)
+    }
+
+    fn greater_or_equal(&self, rhs: &KValue) -> Result<bool> {
+        self.compare(rhs).map(Ordering::is_ge)
+    }
+
+    fn equal(&self, rhs: &KValue) -> Result<bool> {
+        match rhs {
+            KValue::Object(o) if o.is_a::<Self>() => Ok(self.0 == o.cast::<Self>()?.0),
+            _ => Ok(false),
+        }
+    }
+
+    fn not_equal(&self, rhs: &KValue) -> Result<bool> {
+        self.equal(rhs).map(|result| !result)
+    }
+}
+
+/// The underlying data type returned by `os.duration()`
+///
+/// Durations are produced when subtracting one DateTime from another,
+/// and can be added to or subtracted from a DateTime.
+#[derive(Clone, Debug, KotoCopy, KotoType)]
+pub struct Duration(TimeDelta);
+
+#[koto_impl(runtime = crate)]
+impl Duration {
+    fn from_seconds(seconds: f64) -> Result<Self> {
+        let whole = seconds.trunc();
+        let nanos = TimeDelta::nanoseconds((seconds.fract() * 1.0e9).round() as i64);
+        let result = if whole.is_finite() && whole.abs() < i64::MAX as f64 {
+            TimeDelta::try_seconds(whole as i64).and_then(|delta| delta.checked_add(&nanos))
+        } else {
+            None
+        };
+
+        match result {
+            Some(result) => Ok(Self(result)),
+            None => runtime_error!("Duration is out of range: {seconds}s"),
+        }
+    }
+
+    fn as_seconds(&self) -> f64 {
+        self.0.num_seconds() as f64 + self.0.subsec_nanos() as f64 / 1.0e9
+    }
+
+    #[koto_method]
+    fn days(&self) -> KValue {
+        (self.as_seconds() / 86400.0).into()
+    }
+
+    #[koto_method]
+    fn hours(&self) -> KValue {
+        (self.as_seconds() / 3600.0).into()
+    }
+
+    #[koto_method]
+    fn milliseconds(&self) -> KValue {
+        (self.as_seconds() * 1000.0).into()
+    }
+
+    #[koto_method]
+    fn minutes(&self) -> KValue {
+        (self.as_seconds() / 60.0).into()
+    }
+
+    #[koto_method]
+    fn seconds(&self) -> KValue {
+        self.as_seconds().into()
+    }
+
+    fn compare(&self, rhs: &KValue) -> Result<Ordering> {
+        match rhs {
+            KValue::Object(o) if o.is_a::<Self>() => Ok(self.0.cmp(&o.cast::<Self>()?.0)),
+            unexpected => type_error(Self::type_static(), unexpected),
+        }
+    }
+}
+
+impl KotoObject for Duration {
+    fn display(&self, ctx: &mut DisplayContext) -> Result<()> {
+        let seconds = self.as_seconds();
+        if seconds.fract() == 0.0 {
+            ctx.append(format!("Duration({}s)", seconds as i64));
+        } else {
+            ctx.append(format!("Duration({seconds}s)"));
+        }
+        Ok(())
+    }
+
+    fn negate(&self, _vm: &mut KotoVm) -> Result<KValue> {
+        Ok(Self(-self.0).into())
+    }
+
+    fn add(&self, rhs: &KValue) -> Result<KValue> {
+        match rhs {
+            KValue::Object(o) if o.is_a::<Self>() => {
+                match self.0.checked_add(&o.cast::<Self>()?.0) {
+                    Some(result) => Ok(Self(result).into()),
+                    None => runtime_error!("The resulting duration is out of range"),
+                }
+            }
+            unexpected => type_error(Self::type_static(), unexpected),
+        }
+    }
+
+    fn subtract(&self, rhs: &KValue) -> Result<KValue> {
+        match rhs {
+            KValue::Object(o) if o.is_a::<Self>() => {
+                match self.0.checked_sub(&o.cast::<Self>()?.0) {
+                    Some(result) => Ok(Self(result).into()),
+                    None => runtime_error!("The resulting duration is out of range"),
+                }
+            }
+            unexpected => type_error(Self::type_static(), unexpected),
+        }
+    }
+
+    fn multiply(&self, rhs: &KValue) -> Result<KValue> {
+        match rhs {
+            KValue::Number(n) => {
+                Self::from_seconds(self.as_seconds() * f64::from(n)).map(KValue::from)
+            }
+            unexpected => type_error("a Number", unexpected),
+        }
+    }
+
+    fn divide(&self, rhs: &KValue) -> Result<KValue> {
+        match rhs {
+            KValue::Number(n) => {
+                Self::from_seconds(self.as_seconds() / f64::from(n)).map(KValue::from)
+            }
+            KValue::Object(o) if o.is_a::<Self>() => {
+                let rhs = o.cast::<Self>()?;
+                Ok((self.as_seconds() / rhs.as_seconds()).into())
+            }
+            unexpected => type_error("a Number or Duration", unexpected),
+        }
+    }
+
+    fn less(&self, rhs: &KValue) -> Result<bool> {
+        self.compare(rhs).map(Ordering::is_lt)
+    }
+
+    fn less_or_equal(&self, rhs: &KValue) -> Result<bool> {
+        self.compare(rhs).map(Ordering::is_le)
+    }
+
+    fn greater(&self, rhs: &KValue) -> Result<bool> {
+        self.compare(rhs).map(Ordering::is_gt)
+    }
+
+    fn greater_or_equal(&self, rhs: &KValue) -> Result<bool> {
+        self.compare(rhs).map(Ordering::is_ge)
+    }
+
+    fn equal(&self, rhs: &KValue) -> Result<bool> {
+        match rhs {
+            KValue::Object(o) if o.is_a::<Self>() => Ok(self.0 == o.cast::<Self>()?.0),
+            _ => Ok(false),
+        }
+    }
+
+    fn not_equal(&self, rhs: &KValue) -> Result<bool> {
+        self.equal(rhs).map(|result| !result)
+    }
+}
+
+impl From<Duration> for KValue {
+    fn from(duration: Duration) -> Self {
+        KObject::from(duration).into()
+    }
+}
+
+fn make_offset(offset: i64) -> Result<FixedOffset> {
+    match i32::try_from(offset).ok().and_then(FixedOffset::east_opt) {
+        Some(offset) => Ok(offset),
+        None => runtime_error!("time offset is out of range: {offset}"),
+    }
 }
 
 /// The underlying data type returned by `os.start_timer()`
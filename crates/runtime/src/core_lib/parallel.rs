@@ -0,0 +1,441 @@
+//! The `parallel` core library module
+
+use super::iterator::collect_pair;
+use crate::{derive::*, prelude::*, KIteratorOutput as Output, Ptr, Result};
+use koto_memory::Address;
+use std::{
+    collections::VecDeque,
+    sync::{Condvar, Mutex, MutexGuard, PoisonError},
+};
+
+/// Initializes the `parallel` core library module
+pub fn make_module() -> KMap {
+    let result = KMap::with_type("core.parallel");
+
+    result.add_fn("channel", |ctx| match ctx.args() {
+        [] => Ok(Channel::unbounded().into()),
+        [KValue::Number(n)] if *n > 0 => Ok(Channel::bounded(n.into()).into()),
+        unexpected => type_error_with_slice("an optional capacity greater than zero", unexpected),
+    });
+
+    result.add_fn("map", |ctx| {
+        let expected_error = "an iterable and function";
+
+        match ctx.args() {
+            [iterable, f] if iterable.is_iterable() && f.is_callable() => {
+                let iterable = iterable.clone();
+                let f = f.clone();
+
+                check_shareable(&f, &mut Vec::new())?;
+
+                let iterator = ctx.vm.make_iterator(iterable)?;
+                let (size_hint, _) = iterator.size_hint();
+                let mut items = Vec::with_capacity(size_hint);
+                for output in iterator.map(collect_pair) {
+                    match output {
+                        Output::Value(value) => items.push(value),
+                        Output::Error(error) => return Err(error),
+                        _ => unreachable!(),
+                    }
+                }
+
+                let results = parallel_map(ctx.vm, &items, &f)?;
+                Ok(KList::from_slice(&results).into())
+            }
+            unexpected => type_error_with_slice(expected_error, unexpected),
+        }
+    });
+
+    result.add_fn("thread_count", |ctx| match ctx.args() {
+        [] => Ok(thread_count().into()),
+        unexpected => type_error_with_slice("no arguments", unexpected),
+    });
+
+    result
+}
+
+// The number of threads that are available for parallel execution
+fn thread_count() -> usize {
+    if cfg!(feature = "rc") {
+        1
+    } else {
+        std::thread::available_parallelism().map_or(1, |n| n.get())
+    }
+}
+
+// Checks that a function can be safely called from multiple threads
+//
+// Iterators can't be shared between threads, so an error is returned if the function has captured
+// an iterator, either directly or nested in a captured container or function.
+//
+// The contents of objects aren't visible to the check, so objects that contain iterators
+// aren't detected.
+fn check_shareable(value: &KValue, visited: &mut Vec<Address>) -> Result<()> {
+    // Containers can contain themselves, so avoid checking their contents more than once
+    let mut first_visit = |address: Address| {
+        let first_visit = !visited.contains(&address);
+        if first_visit {
+            visited.push(address);
+        }
+        first_visit
+    };
+
+    match value {
+        KValue::Iterator(_) => {
+            runtime_error!("iterators can't be shared between threads, consider capturing a list")
+        }
+        KValue::Tuple(t) => t
+            .iter()
+            .try_for_each(|value| check_shareable(value, visited)),
+        KValue::List(l) => {
+            if !first_visit(l.address()) {
+                return Ok(());
+            }
+            l.data()
+                .iter()
+                .try_for_each(|value| check_shareable(value, visited))
+        }
+        KValue::Map(m) => {
+            if !first_visit(m.address()) {
+                return Ok(());
+            }
+            m.data()
+                .values()
+                .try_for_each(|value| check_shareable(value, visited))?;
+            match m.meta_map() {
+                Some(meta) => meta
+                    .borrow()
+                    .values()
+                    .try_for_each(|value| check_shareable(value, visited)),
+                None => Ok(()),
+            }
+        }
+        KValue::CaptureFunction(f) => {
+            // Recursive functions capture themselves, so their captures are only checked once
+            if !first_visit(f.captures.address()) {
+                return Ok(());
+            }
+            f.captures
+                .data()
+                .iter()
+                .try_for_each(|value| check_shareable(value, visited))
+        }
+        _ => Ok(()),
+    }
+}
+
+#[cfg(feature = "rc")]
+fn parallel_map(vm: &mut KotoVm, items: &[KValue], f: &KValue) -> Result<Vec<KValue>> {
+    // Values can't be shared between threads with the rc backend, so run on the current thread
+    items
+        .iter()
+        .map(|item| vm.call_function(f.clone(), item.clone()))
+        .collect()
+}
+
+#[cfg(not(feature = "rc"))]
+fn parallel_map(vm: &mut KotoVm, items: &[KValue], f: &KValue) -> Result<Vec<KValue>> {
+    use crate::Error;
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+    let worker_count = thread_count().min(items.len());
+    if worker_count <= 1 {
+        return items
+            .iter()
+            .map(|item| vm.call_function(f.clone(), item.clone()))
+            .collect();
+    }
+
+    // Items are claimed by workers one at a time, which keeps the workers busy when the function
+    // takes varying amounts of time to run.
+    let next_item = AtomicUsize::new(0);
+    let failed = AtomicBool::new(false);
+
+    let worker_results = std::thread::scope(|scope| {
+        let workers = (0..worker_count)
+            .map(|_| {
+                let mut worker_vm = vm.spawn_shared_vm();
+                let next_item = &next_item;
+                let failed = &failed;
+
+                scope.spawn(move || {
+                    let mut results = Vec::new();
+
+                    while !failed.load(Ordering::Relaxed) {
+                        let index = next_item.fetch_add(1, Ordering::Relaxed);
+                        let Some(item) = items.get(index) else {
+                            break;
+                        };
+
+                        let result = worker_vm.call_function(f.clone(), item.clone());
+                        if result.is_err() {
+                            failed.store(true, Ordering::Relaxed);
+                        }
+                        results.push((index, result));
+                    }
+
+                    results
+                })
+            })
+            .collect::<Vec<_>>();
+
+        workers
+            .into_iter()
+            .flat_map(|worker| match worker.join() {
+                Ok(results) => results,
+                Err(panic) => std::panic::resume_unwind(panic),
+            })
+            .collect::<Vec<_>>()
+    });
+
+    // Place the results in the order of the input items, reporting the first error that occurred
+    let mut results = vec![KValue::Null; items.len()];
+    let mut first_error: Option<(usize, Error)> = None;
+    for (index, result) in worker_results {
+        match result {
+            Ok(value) => results[index] = value,
+            Err(error) => {
+                if first_error.as_ref().is_none_or(|(i, _)| index < *i) {
+                    first_error = Some((index, error));
+                }
+            }
+        }
+    }
+
+    match first_error {
+        Some((_, error)) => Err(error),
+        None => Ok(results),
+    }
+}
+
+/// A channel for passing values between VMs, returned by `parallel.channel`
+///
+/// Values sent to the channel are received in the order that they were sent.
+///
+/// Clones of the channel share the same queue, so a host can keep a clone of the channel for
+/// sending values to (or receiving values from) a script that's running on another thread.
+#[derive(Clone, KotoCopy, KotoType)]
+pub struct Channel(Ptr<ChannelInner>);
+
+struct ChannelInner {
+    state: Mutex<ChannelState>,
+    // The maximum number of values that can be queued, or None for unbounded channels
+    capacity: Option<usize>,
+    // Notified when a value is sent, or when the channel is closed
+    value_sent: Condvar,
+    // Notified when a value is received, or when the channel is closed
+    value_received: Condvar,
+}
+
+#[derive(Default)]
+struct ChannelState {
+    queue: VecDeque<KValue>,
+    closed: bool,
+}
+
+impl Channel {
+    /// Makes a channel that can hold any number of values
+    pub fn unbounded() -> Self {
+        Self::with_capacity(None)
+    }
+
+    /// Makes a channel that can hold up to `capacity` values
+    ///
+    /// Sending a value to a full channel will block until a value has been received.
+    ///
+    /// A capacity of 0 is treated as 1.
+    pub fn bounded(capacity: usize) -> Self {
+        Self::with_capacity(Some(capacity.max(1)))
+    }
+
+    fn with_capacity(capacity: Option<usize>) -> Self {
+        Self(Ptr::from(ChannelInner {
+            state: Mutex::default(),
+            capacity,
+            value_sent: Condvar::new(),
+            value_received: Condvar::new(),
+        }))
+    }
+
+    /// Sends a value to the channel
+    ///
+    /// If the channel is bounded and full, then this will block until space is available.
+    ///
+    /// An error is returned if the channel has been closed.
+    pub fn send_value(&self, value: KValue) -> Result<()> {
+        let mut state = self.lock_state();
+
+        if let Some(capacity) = self.0.capacity {
+            while !state.closed && state.queue.len() >= capacity {
+                if cfg!(feature = "rc") {
+                    // Values can't be received on other threads with the rc backend
+                    return runtime_error!("the channel is full and would block forever");
+                }
+                state = self
+                    .0
+                    .value_received
+                    .wait(state)
+                    .unwrap_or_else(PoisonError::into_inner);
+            }
+        }
+
+        if state.closed {
+            return runtime_error!("the channel is closed");
+        }
+
+        state.queue.push_back(value);
+        self.0.value_sent.notify_one();
+        Ok(())
+    }
+
+    /// Receives the next value from the channel
+    ///
+    /// If the channel is empty, then this will block until a value is sent or the channel is
+    /// closed. `None` is returned once the channel has been closed and all values have been
+    /// received.
+    pub fn recv_value(&self) -> Result<Option<KValue>> {
+        let mut state = self.lock_state();
+
+        loop {
+            if let Some(value) = state.queue.pop_front() {
+                self.0.value_received.notify_one();
+                return Ok(Some(value));
+            }
+
+            if state.closed {
+                return Ok(None);
+            }
+
+            if cfg!(feature = "rc") {
+                // Values can't be sent from other threads with the rc backend
+                return runtime_error!("the channel is empty and would block forever");
+            }
+
+            state = self
+                .0
+                .value_sent
+                .wait(state)
+                .unwrap_or_else(PoisonError::into_inner);
+        }
+    }
+
+    /// Receives the next value from the channel if one is available, without blocking
+    pub fn try_recv_value(&self) -> Option<KValue> {
+        let value = self.lock_state().queue.pop_front();
+        if value.is_some() {
+            self.0.value_received.notify_one();
+        }
+        value
+    }
+
+    /// Closes the channel
+    ///
+    /// Values that have already been sent can still be received, but sending further values will
+    /// produce an error.
+    pub fn close_channel(&self) {
+        self.lock_state().closed = true;
+        self.0.value_sent.notify_all();
+        self.0.value_received.notify_all();
+    }
+
+    /// Returns true if the channel has been closed
+    pub fn is_channel_closed(&self) -> bool {
+        self.lock_state().closed
+    }
+
+    /// Returns the number of values that are waiting to be received
+    pub fn len(&self) -> usize {
+        self.lock_state().queue.len()
+    }
+
+    /// Returns true if there are no values waiting to be received
+    pub fn is_empty(&self) -> bool {
+        self.lock_state().queue.is_empty()
+    }
+
+    fn lock_state(&self) -> MutexGuard<'_, ChannelState> {
+        self.0.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+#[koto_impl(runtime = crate)]
+impl Channel {
+    // The channel is cloned before blocking so that the object isn't borrowed while waiting for
+    // other threads.
+    fn instance(ctx: &MethodContext<Self>) -> Result<Self> {
+        ctx.instance().map(|channel| channel.clone())
+    }
+
+    #[koto_method]
+    fn send(ctx: MethodContext<Self>) -> Result<KValue> {
+        match ctx.args {
+            [value] => {
+                let value = value.clone();
+                Self::instance(&ctx)?.send_value(value)?;
+                Ok(KValue::Null)
+            }
+            unexpected => type_error_with_slice("a single value", unexpected),
+        }
+    }
+
+    #[koto_method]
+    fn recv(ctx: MethodContext<Self>) -> Result<KValue> {
+        match ctx.args {
+            [] => Ok(Self::instance(&ctx)?.recv_value()?.unwrap_or_default()),
+            unexpected => type_error_with_slice("no arguments", unexpected),
+        }
+    }
+
+    #[koto_method]
+    fn try_recv(&self) -> KValue {
+        self.try_recv_value().unwrap_or_default()
+    }
+
+    #[koto_method]
+    fn close(&self) {
+        self.close_channel()
+    }
+
+    #[koto_method]
+    fn is_closed(&self) -> KValue {
+        self.is_channel_closed().into()
+    }
+}
+
+impl KotoObject for Channel {
+    fn size(&self) -> Option<usize> {
+        Some(self.len())
+    }
+
+    fn is_iterable(&self) -> IsIterable {
+        IsIterable::Iterable
+    }
+
+    fn make_iterator(&self, _vm: &mut KotoVm) -> Result<KIterator> {
+        Ok(KIterator::with_std_forward_iter(ChannelIterator(
+            self.clone(),
+        )))
+    }
+}
+
+impl From<Channel> for KValue {
+    fn from(channel: Channel) -> Self {
+        KObject::from(channel).into()
+    }
+}
+
+// Receives values from a channel until it's closed
+#[derive(Clone)]
+struct ChannelIterator(Channel);
+
+impl Iterator for ChannelIterator {
+    type Item = Output;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.0.recv_value() {
+            Ok(Some(value)) => Some(Output::Value(value)),
+            Ok(None) => None,
+            Err(error) => Some(Output::Error(error)),
+        }
+    }
+}
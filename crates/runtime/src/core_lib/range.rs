@@ -1,22 +1,20 @@
 //! The `range` core library module
 
-use crate::prelude::*;
+use crate::{prelude::*, Result};
 
 /// Initializes the `range` core library module
 pub fn make_module() -> KMap {
     let result = KMap::with_type("core.range");
 
     result.add_fn("contains", |ctx| {
-        let expected_error = "a Range, and a Number or another Range";
+        let expected_error = "a Range, and a Number, String, or another Range";
 
         match ctx.instance_and_args(is_range, expected_error)? {
             (KValue::Range(r), [KValue::Number(n)]) => Ok(r.contains(*n).into()),
-            (KValue::Range(a), [KValue::Range(b)]) => {
-                let r_a = a.as_sorted_range();
-                let r_b = b.as_sorted_range();
-                let result = r_b.start >= r_a.start && r_b.end <= r_a.end;
-                Ok(result.into())
+            (KValue::Range(r), [KValue::Str(s)]) => {
+                Ok(single_char(s).is_some_and(|c| r.contains_char(c)).into())
             }
+            (KValue::Range(a), [KValue::Range(b)]) => Ok(a.contains_range(b).into()),
             (_, unexpected) => type_error_with_slice(expected_error, unexpected),
         }
     });
@@ -26,7 +24,14 @@ pub fn make_module() -> KMap {
 
         match ctx.instance_and_args(is_range, expected_error)? {
             (KValue::Range(r), []) => {
-                Ok(r.end().map_or(KValue::Null, |(end, _inclusive)| end.into()))
+                let result = if let Some((_, end, _)) = r.char_bounds() {
+                    end.to_string().into()
+                } else if let Some((_, end, _)) = r.float_bounds() {
+                    end.into()
+                } else {
+                    r.end().map_or(KValue::Null, |(end, _inclusive)| end.into())
+                };
+                Ok(result)
             }
             (_, unexpected) => type_error_with_slice(expected_error, unexpected),
         }
@@ -68,7 +73,14 @@ pub fn make_module() -> KMap {
 
         match ctx.instance_and_args(is_range, expected_error)? {
             (KValue::Range(r), []) => {
-                Ok(r.end().map_or(false, |(_end, inclusive)| inclusive).into())
+                let result = if let Some((_, _, inclusive)) = r.char_bounds() {
+                    inclusive
+                } else if let Some((_, _, inclusive)) = r.float_bounds() {
+                    inclusive
+                } else {
+                    r.end().map_or(false, |(_end, inclusive)| inclusive)
+                };
+                Ok(result.into())
             }
             (_, unexpected) => type_error_with_slice(expected_error, unexpected),
         }
@@ -78,15 +90,45 @@ pub fn make_module() -> KMap {
         let expected_error = "a Range";
 
         match ctx.instance_and_args(is_range, expected_error)? {
-            (KValue::Range(r), []) => Ok(r.start().map_or(KValue::Null, KValue::from)),
+            (KValue::Range(r), []) => {
+                let result = if let Some((start, _, _)) = r.char_bounds() {
+                    start.to_string().into()
+                } else if let Some((start, _, _)) = r.float_bounds() {
+                    start.into()
+                } else {
+                    r.start().map_or(KValue::Null, KValue::from)
+                };
+                Ok(result)
+            }
+            (_, unexpected) => type_error_with_slice(expected_error, unexpected),
+        }
+    });
+
+    result.add_fn("step", |ctx| {
+        let expected_error = "a Range, and an optional Number";
+
+        match ctx.instance_and_args(is_range, expected_error)? {
+            (KValue::Range(r), []) => Ok(r.step().into()),
+            (KValue::Range(r), [KValue::Number(KNumber::I64(step))]) => {
+                Ok(r.with_step(*step)?.into())
+            }
+            (KValue::Range(r), [KValue::Number(KNumber::F64(step))]) => {
+                Ok(r.with_float_step(*step)?.into())
+            }
             (_, unexpected) => type_error_with_slice(expected_error, unexpected),
         }
     });
 
     result.add_fn("union", |ctx| {
-        let expected_error = "a Range, and a Number or another Range";
+        let expected_error = "a Range, and a Number, String, or another Range";
 
         match ctx.instance_and_args(is_range, expected_error)? {
+            (KValue::Range(r), [arg]) if r.char_bounds().is_some() => char_union(r, arg),
+            (KValue::Range(r), [arg]) if r.float_bounds().is_some() => float_union(r, arg),
+            (KValue::Range(r), [arg @ KValue::Range(b)]) if b.float_bounds().is_some() => {
+                // Integer ranges are converted into float ranges when combined with float ranges
+                float_union(&as_float_range(r)?, arg)
+            }
             (KValue::Range(r), [KValue::Number(n)]) => {
                 let n = i64::from(n);
                 match (r.start(), r.end()) {
@@ -101,6 +143,9 @@ pub fn make_module() -> KMap {
                     _ => runtime_error!("range.union can't be used with '{r}'"),
                 }
             }
+            (KValue::Range(a), [KValue::Range(b)]) if !b.is_integer() => {
+                runtime_error!("range.union can't be used with '{a}' and '{b}'")
+            }
             (KValue::Range(a), [KValue::Range(b)]) => match (a.start(), a.end()) {
                 (Some(start), Some((end, inclusive))) => {
                     let r_b = b.as_sorted_range();
@@ -129,3 +174,115 @@ pub fn make_module() -> KMap {
 fn is_range(value: &KValue) -> bool {
     matches!(value, KValue::Range(_))
 }
+
+// Returns the string's character if it contains a single character
+fn single_char(s: &str) -> Option<char> {
+    let mut chars = s.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) => Some(c),
+        _ => None,
+    }
+}
+
+// The union of a character range with a character or another character range
+//
+// The result is an inclusive range that has the same direction as the input range.
+fn char_union(range: &KRange, arg: &KValue) -> Result<KValue> {
+    let (arg_min, arg_max) = match arg {
+        KValue::Str(s) => match single_char(s) {
+            Some(c) => (c, c),
+            None => return runtime_error!("expected a single character, found '{s}'"),
+        },
+        KValue::Range(other) if other.char_bounds().is_some() => match char_extent(other)? {
+            Some(extent) => extent,
+            None => return Ok(range.clone().into()),
+        },
+        unexpected => return type_error("a String or a character Range", unexpected),
+    };
+
+    let result = match char_extent(range)? {
+        Some((min, max)) => {
+            let (min, max) = (min.min(arg_min), max.max(arg_max));
+            if range.is_ascending() {
+                KRange::with_chars(min, max, true)
+            } else {
+                KRange::with_chars(max, min, true)
+            }
+        }
+        None => KRange::with_chars(arg_min, arg_max, true),
+    };
+    Ok(result.into())
+}
+
+// Returns the smallest and largest characters produced by a character range
+//
+// None is returned if the range is empty.
+fn char_extent(range: &KRange) -> Result<Option<(char, char)>> {
+    let mut values = range.clone();
+    let to_char = |value: Option<KValue>| match value {
+        Some(KValue::Str(s)) => s.chars().next(),
+        _ => None,
+    };
+    let first = to_char(values.pop_front()?);
+    let last = to_char(values.pop_back()?);
+
+    let result = match (first, last) {
+        (Some(first), Some(last)) => Some((first.min(last), first.max(last))),
+        (Some(first), None) => Some((first, first)),
+        _ => None,
+    };
+    Ok(result)
+}
+
+// Converts a bounded integer range into a float range with the same bounds and step
+fn as_float_range(range: &KRange) -> Result<KRange> {
+    if range.is_integer() {
+        range.with_float_step(range.step().into())
+    } else {
+        Ok(range.clone())
+    }
+}
+
+// The union of a float range with a number, or with another integer or float range
+//
+// The result has the same direction and step as the input range.
+fn float_union(range: &KRange, arg: &KValue) -> Result<KValue> {
+    // Unwrap: only called with float ranges
+    let (start, end, inclusive) = range.float_bounds().unwrap();
+
+    // The smallest and largest values of the argument, along with their inclusivity
+    let (arg_min, arg_max) = match arg {
+        KValue::Number(n) => ((f64::from(n), true), (f64::from(n), true)),
+        KValue::Range(other) => match as_float_range(other)?.float_bounds() {
+            Some((other_start, other_end, other_inclusive)) if other_start <= other_end => {
+                ((other_start, true), (other_end, other_inclusive))
+            }
+            Some((other_start, other_end, other_inclusive)) => {
+                ((other_end, other_inclusive), (other_start, true))
+            }
+            None => {
+                return runtime_error!("range.union can't be used with '{range}' and '{other}'")
+            }
+        },
+        unexpected => return type_error("a Number or a float Range", unexpected),
+    };
+
+    // Extends the range's end if the argument goes beyond it
+    let extend_end = |(arg_end, arg_inclusive): (f64, bool), beyond_end: bool| {
+        if beyond_end {
+            (arg_end, arg_inclusive)
+        } else if arg_end == end {
+            (end, inclusive || arg_inclusive)
+        } else {
+            (end, inclusive)
+        }
+    };
+
+    let (start, (end, inclusive)) = if start <= end {
+        (start.min(arg_min.0), extend_end(arg_max, arg_max.0 > end))
+    } else {
+        (start.max(arg_max.0), extend_end(arg_min, arg_min.0 < end))
+    };
+
+    Ok(KRange::with_floats(start, end, inclusive, range.step().into())?.into())
+}
@@ -0,0 +1,196 @@
+//! The `set` core library module
+
+use super::iterator::collect_pair;
+use crate::{prelude::*, KIteratorOutput as Output, Result};
+
+/// Initializes the `set` core library module
+pub fn make_module() -> KMap {
+    let result = KMap::with_type("core.set");
+
+    result.add_fn("clear", |ctx| {
+        let expected_error = "a Set";
+
+        match ctx.instance_and_args(is_set, expected_error)? {
+            (KValue::Set(s), []) => {
+                s.data_mut().clear();
+                Ok(KValue::Set(s.clone()))
+            }
+            (_, unexpected) => type_error_with_slice(expected_error, unexpected),
+        }
+    });
+
+    result.add_fn("contains", |ctx| {
+        let expected_error = "a Set and a Value";
+
+        match ctx.instance_and_args(is_set, expected_error)? {
+            (KValue::Set(s), [value]) => {
+                let (s, value) = (s.clone(), value.clone());
+                // Unhashable values can't be contained in a set
+                if !value.is_hashable() {
+                    return Ok(false.into());
+                }
+                let key = ctx.vm.make_value_key(value)?;
+                Ok(s.contains(&key).into())
+            }
+            (_, unexpected) => type_error_with_slice(expected_error, unexpected),
+        }
+    });
+
+    result.add_fn("difference", |ctx| {
+        let expected_error = "a Set and an iterable";
+
+        match ctx.instance_and_args(is_set, expected_error)? {
+            (KValue::Set(s), [other]) if other.is_iterable() => {
+                let (s, other) = (s.clone(), other.clone());
+                let other = make_value_set(ctx.vm, other)?;
+                let result = s
+                    .data()
+                    .iter()
+                    .filter(|key| !other.contains(*key))
+                    .cloned()
+                    .collect();
+                Ok(KSet::with_data(result).into())
+            }
+            (_, unexpected) => type_error_with_slice(expected_error, unexpected),
+        }
+    });
+
+    result.add_fn("insert", |ctx| {
+        let expected_error = "a Set and a Value";
+
+        match ctx.instance_and_args(is_set, expected_error)? {
+            (KValue::Set(s), [value]) => {
+                let (s, value) = (s.clone(), value.clone());
+                let key = ctx.vm.make_value_key(value)?;
+                Ok(s.insert(key).into())
+            }
+            (_, unexpected) => type_error_with_slice(expected_error, unexpected),
+        }
+    });
+
+    result.add_fn("intersection", |ctx| {
+        let expected_error = "a Set and an iterable";
+
+        match ctx.instance_and_args(is_set, expected_error)? {
+            (KValue::Set(s), [other]) if other.is_iterable() => {
+                let (s, other) = (s.clone(), other.clone());
+                let other = make_value_set(ctx.vm, other)?;
+                let result = s
+                    .data()
+                    .iter()
+                    .filter(|key| other.contains(*key))
+                    .cloned()
+                    .collect();
+                Ok(KSet::with_data(result).into())
+            }
+            (_, unexpected) => type_error_with_slice(expected_error, unexpected),
+        }
+    });
+
+    result.add_fn("is_empty", |ctx| {
+        let expected_error = "a Set";
+
+        match ctx.instance_and_args(is_set, expected_error)? {
+            (KValue::Set(s), []) => Ok(s.is_empty().into()),
+            (_, unexpected) => type_error_with_slice(expected_error, unexpected),
+        }
+    });
+
+    result.add_fn("is_subset", |ctx| {
+        let expected_error = "a Set and an iterable";
+
+        match ctx.instance_and_args(is_set, expected_error)? {
+            (KValue::Set(s), [other]) if other.is_iterable() => {
+                let (s, other) = (s.clone(), other.clone());
+                let other = make_value_set(ctx.vm, other)?;
+                let result = s.data().iter().all(|key| other.contains(key));
+                Ok(result.into())
+            }
+            (_, unexpected) => type_error_with_slice(expected_error, unexpected),
+        }
+    });
+
+    result.add_fn("new", |ctx| match ctx.args() {
+        [] => Ok(KSet::new().into()),
+        [iterable] if iterable.is_iterable() => {
+            let iterable = iterable.clone();
+            Ok(KSet::with_data(make_value_set(ctx.vm, iterable)?).into())
+        }
+        unexpected => type_error_with_slice("no arguments, or an iterable", unexpected),
+    });
+
+    result.add_fn("remove", |ctx| {
+        let expected_error = "a Set and a Value";
+
+        match ctx.instance_and_args(is_set, expected_error)? {
+            (KValue::Set(s), [value]) => {
+                let (s, value) = (s.clone(), value.clone());
+                if !value.is_hashable() {
+                    return Ok(false.into());
+                }
+                let key = ctx.vm.make_value_key(value)?;
+                let result = s.data_mut().shift_remove(&key);
+                Ok(result.into())
+            }
+            (_, unexpected) => type_error_with_slice(expected_error, unexpected),
+        }
+    });
+
+    result.add_fn("symmetric_difference", |ctx| {
+        let expected_error = "a Set and an iterable";
+
+        match ctx.instance_and_args(is_set, expected_error)? {
+            (KValue::Set(s), [other]) if other.is_iterable() => {
+                let (s, other) = (s.clone(), other.clone());
+                let other = make_value_set(ctx.vm, other)?;
+                let result = s.data().symmetric_difference(&other).cloned().collect();
+                Ok(KSet::with_data(result).into())
+            }
+            (_, unexpected) => type_error_with_slice(expected_error, unexpected),
+        }
+    });
+
+    result.add_fn("union", |ctx| {
+        let expected_error = "a Set and an iterable";
+
+        match ctx.instance_and_args(is_set, expected_error)? {
+            (KValue::Set(s), [other]) if other.is_iterable() => {
+                let (s, other) = (s.clone(), other.clone());
+                let other = make_value_set(ctx.vm, other)?;
+                let mut result = s.data().clone();
+                result.extend(other.iter().cloned());
+                Ok(KSet::with_data(result).into())
+            }
+            (_, unexpected) => type_error_with_slice(expected_error, unexpected),
+        }
+    });
+
+    result
+}
+
+/// Collects the values produced by an iterable into a [ValueSet]
+pub(crate) fn make_value_set(vm: &mut KotoVm, iterable: KValue) -> Result<ValueSet> {
+    if let KValue::Set(s) = &iterable {
+        return Ok(s.data().clone());
+    }
+
+    let iterator = vm.make_iterator(iterable)?;
+    let (size_hint, _) = iterator.size_hint();
+    let mut result = ValueSet::with_capacity(size_hint);
+
+    for output in iterator.map(collect_pair) {
+        match output {
+            Output::Value(value) => {
+                result.insert(vm.make_value_key(value)?);
+            }
+            Output::Error(error) => return Err(error),
+            _ => unreachable!(), // value pairs have been collected in collect_pair
+        }
+    }
+
+    Ok(result)
+}
+
+fn is_set(value: &KValue) -> bool {
+    matches!(value, KValue::Set(_))
+}
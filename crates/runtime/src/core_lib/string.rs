@@ -1,14 +1,23 @@
 //! The `string` core library module
 
+pub mod builder;
+pub mod format;
 pub mod iterators;
 
 use super::iterator::collect_pair;
-use crate::prelude::*;
+use crate::{prelude::*, Result};
+use unicode_segmentation::{GraphemeCursor, UnicodeSegmentation};
 
 /// Initializes the `string` core library module
 pub fn make_module() -> KMap {
     let result = KMap::with_type("core.string");
 
+    result.add_fn("builder", |ctx| match ctx.args() {
+        [] => Ok(builder::StringBuilder::default().into()),
+        [KValue::Str(s)] => Ok(builder::StringBuilder::new(s.to_string()).into()),
+        unexpected => type_error_with_slice("an optional String", unexpected),
+    });
+
     result.add_fn("bytes", |ctx| {
         let expected_error = "a String";
 
@@ -71,6 +80,53 @@ pub fn make_module() -> KMap {
         }
     });
 
+    result.add_fn("find", |ctx| {
+        let expected_error = "a String, and either a String or a predicate function";
+
+        match ctx.instance_and_args(is_string, expected_error)? {
+            (KValue::Str(s), [KValue::Str(pattern)]) => match find(s, pattern) {
+                Some(index) => Ok(index.into()),
+                None => Ok(KValue::Null),
+            },
+            (KValue::Str(s), [predicate]) if predicate.is_callable() => {
+                // Matches the behaviour of `iterator.find`, which is overridden for strings
+                let s = s.clone();
+                let predicate = predicate.clone();
+
+                for (start, grapheme) in s.grapheme_indices(true) {
+                    let grapheme = s.with_bounds(start..start + grapheme.len()).unwrap();
+                    match ctx.vm.call_function(predicate.clone(), grapheme.clone())? {
+                        KValue::Bool(true) => return Ok(grapheme.into()),
+                        KValue::Bool(false) => {}
+                        unexpected => {
+                            return type_error(
+                                "a Bool to be returned from the predicate",
+                                &unexpected,
+                            )
+                        }
+                    }
+                }
+
+                Ok(KValue::Null)
+            }
+            (_, unexpected) => type_error_with_slice(expected_error, unexpected),
+        }
+    });
+
+    result.add_fn("format", |ctx| {
+        let expected_error = "a String as the format template";
+
+        match ctx.instance_and_args(is_string, expected_error)? {
+            (KValue::Str(template), args) => {
+                let template = template.clone();
+                let args = args.to_vec();
+                let result = format::format_template(ctx.vm, &template, &args)?;
+                Ok(result.into())
+            }
+            (_, unexpected) => type_error_with_slice(expected_error, unexpected),
+        }
+    });
+
     result.add_fn("from_bytes", |ctx| match ctx.args() {
         [iterable] if iterable.is_iterable() => {
             let iterable = iterable.clone();
@@ -99,6 +155,24 @@ pub fn make_module() -> KMap {
         unexpected => type_error_with_slice("an iterable", unexpected),
     });
 
+    result.add_fn("is_alphabetic", |ctx| {
+        let expected_error = "a String";
+
+        match ctx.instance_and_args(is_string, expected_error)? {
+            (KValue::Str(s), []) => Ok(all_chars(s, char::is_alphabetic).into()),
+            (_, unexpected) => type_error_with_slice(expected_error, unexpected),
+        }
+    });
+
+    result.add_fn("is_alphanumeric", |ctx| {
+        let expected_error = "a String";
+
+        match ctx.instance_and_args(is_string, expected_error)? {
+            (KValue::Str(s), []) => Ok(all_chars(s, char::is_alphanumeric).into()),
+            (_, unexpected) => type_error_with_slice(expected_error, unexpected),
+        }
+    });
+
     result.add_fn("is_empty", |ctx| {
         let expected_error = "a String";
 
@@ -108,6 +182,42 @@ pub fn make_module() -> KMap {
         }
     });
 
+    result.add_fn("is_lowercase", |ctx| {
+        let expected_error = "a String";
+
+        match ctx.instance_and_args(is_string, expected_error)? {
+            (KValue::Str(s), []) => Ok(is_cased(s, char::is_lowercase, char::is_uppercase).into()),
+            (_, unexpected) => type_error_with_slice(expected_error, unexpected),
+        }
+    });
+
+    result.add_fn("is_numeric", |ctx| {
+        let expected_error = "a String";
+
+        match ctx.instance_and_args(is_string, expected_error)? {
+            (KValue::Str(s), []) => Ok(all_chars(s, char::is_numeric).into()),
+            (_, unexpected) => type_error_with_slice(expected_error, unexpected),
+        }
+    });
+
+    result.add_fn("is_uppercase", |ctx| {
+        let expected_error = "a String";
+
+        match ctx.instance_and_args(is_string, expected_error)? {
+            (KValue::Str(s), []) => Ok(is_cased(s, char::is_uppercase, char::is_lowercase).into()),
+            (_, unexpected) => type_error_with_slice(expected_error, unexpected),
+        }
+    });
+
+    result.add_fn("is_whitespace", |ctx| {
+        let expected_error = "a String";
+
+        match ctx.instance_and_args(is_string, expected_error)? {
+            (KValue::Str(s), []) => Ok(all_chars(s, char::is_whitespace).into()),
+            (_, unexpected) => type_error_with_slice(expected_error, unexpected),
+        }
+    });
+
     result.add_fn("lines", |ctx| {
         let expected_error = "a String";
 
@@ -120,6 +230,41 @@ pub fn make_module() -> KMap {
         }
     });
 
+    result.add_fn("pad_end", |ctx| {
+        let expected_error = "a String, a Number, and an optional fill String";
+
+        match ctx.instance_and_args(is_string, expected_error)? {
+            (KValue::Str(s), [KValue::Number(width)]) => pad(s, width, " ", false),
+            (KValue::Str(s), [KValue::Number(width), KValue::Str(fill)]) => {
+                pad(s, width, fill, false)
+            }
+            (_, unexpected) => type_error_with_slice(expected_error, unexpected),
+        }
+    });
+
+    result.add_fn("pad_start", |ctx| {
+        let expected_error = "a String, a Number, and an optional fill String";
+
+        match ctx.instance_and_args(is_string, expected_error)? {
+            (KValue::Str(s), [KValue::Number(width)]) => pad(s, width, " ", true),
+            (KValue::Str(s), [KValue::Number(width), KValue::Str(fill)]) => {
+                pad(s, width, fill, true)
+            }
+            (_, unexpected) => type_error_with_slice(expected_error, unexpected),
+        }
+    });
+
+    result.add_fn("repeat", |ctx| {
+        let expected_error = "a String and a non-negative Integer";
+
+        match ctx.instance_and_args(is_string, expected_error)? {
+            (KValue::Str(s), [KValue::Number(KNumber::I64(n))]) if *n >= 0 => {
+                repeat(s, usize::try_from(*n).unwrap_or(usize::MAX))
+            }
+            (_, unexpected) => type_error_with_slice(expected_error, unexpected),
+        }
+    });
+
     result.add_fn("replace", |ctx| {
         let expected_error = "a String, followed by pattern and replacement Strings";
 
@@ -131,6 +276,41 @@ pub fn make_module() -> KMap {
         }
     });
 
+    result.add_fn("reversed", |ctx| {
+        let expected_error = "a String";
+
+        match ctx.instance_and_args(is_string, expected_error)? {
+            (KValue::Str(s), []) => Ok(s.graphemes(true).rev().collect::<String>().into()),
+            (_, unexpected) => type_error_with_slice(expected_error, unexpected),
+        }
+    });
+
+    result.add_fn("rfind", |ctx| {
+        let expected_error = "two Strings";
+
+        match ctx.instance_and_args(is_string, expected_error)? {
+            (KValue::Str(s), [KValue::Str(pattern)]) => match rfind(s, pattern) {
+                Some(index) => Ok(index.into()),
+                None => Ok(KValue::Null),
+            },
+            (_, unexpected) => type_error_with_slice(expected_error, unexpected),
+        }
+    });
+
+    result.add_fn("rsplit", |ctx| {
+        let expected_error = "two Strings, and an optional maximum number of pieces";
+
+        match ctx.instance_and_args(is_string, expected_error)? {
+            (KValue::Str(s), [KValue::Str(pattern)]) => {
+                Ok(pieces_iterator(s.rsplit(pattern.as_str())).into())
+            }
+            (KValue::Str(s), [KValue::Str(pattern), KValue::Number(n)]) if *n >= 0 => {
+                Ok(pieces_iterator(s.rsplitn(n.into(), pattern.as_str())).into())
+            }
+            (_, unexpected) => type_error_with_slice(expected_error, unexpected),
+        }
+    });
+
     result.add_fn("split", |ctx| {
         let iterator = {
             let expected_error = "a String, and either a String or a predicate function";
@@ -155,6 +335,17 @@ pub fn make_module() -> KMap {
         Ok(KValue::Iterator(iterator))
     });
 
+    result.add_fn("split_n", |ctx| {
+        let expected_error = "two Strings, and the maximum number of pieces";
+
+        match ctx.instance_and_args(is_string, expected_error)? {
+            (KValue::Str(s), [KValue::Str(pattern), KValue::Number(n)]) if *n >= 0 => {
+                Ok(pieces_iterator(s.splitn(n.into(), pattern.as_str())).into())
+            }
+            (_, unexpected) => type_error_with_slice(expected_error, unexpected),
+        }
+    });
+
     result.add_fn("starts_with", |ctx| {
         let expected_error = "two Strings";
 
@@ -166,6 +357,35 @@ pub fn make_module() -> KMap {
         }
     });
 
+    result.add_fn("strip_prefix", |ctx| {
+        let expected_error = "two Strings";
+
+        match ctx.instance_and_args(is_string, expected_error)? {
+            (KValue::Str(s), [KValue::Str(prefix)])
+                if s.starts_with(prefix.as_str()) && is_grapheme_boundary(s, prefix.len()) =>
+            {
+                Ok(s.with_bounds(prefix.len()..s.len()).unwrap().into())
+            }
+            (KValue::Str(_), [KValue::Str(_)]) => Ok(KValue::Null),
+            (_, unexpected) => type_error_with_slice(expected_error, unexpected),
+        }
+    });
+
+    result.add_fn("strip_suffix", |ctx| {
+        let expected_error = "two Strings";
+
+        match ctx.instance_and_args(is_string, expected_error)? {
+            (KValue::Str(s), [KValue::Str(suffix)])
+                if s.ends_with(suffix.as_str())
+                    && is_grapheme_boundary(s, s.len() - suffix.len()) =>
+            {
+                Ok(s.with_bounds(0..s.len() - suffix.len()).unwrap().into())
+            }
+            (KValue::Str(_), [KValue::Str(_)]) => Ok(KValue::Null),
+            (_, unexpected) => type_error_with_slice(expected_error, unexpected),
+        }
+    });
+
     result.add_fn("to_lowercase", |ctx| {
         let expected_error = "a String";
 
@@ -217,40 +437,288 @@ pub fn make_module() -> KMap {
         }
     });
 
-    result.add_fn("to_uppercase", |ctx| {
+    result.add_fn("to_snake_case", |ctx| {
         let expected_error = "a String";
 
         match ctx.instance_and_args(is_string, expected_error)? {
             (KValue::Str(s), []) => {
-                let result = s.chars().flat_map(|c| c.to_uppercase()).collect::<String>();
+                let result = words(s)
+                    .iter()
+                    .map(|word| word.to_lowercase())
+                    .collect::<Vec<_>>()
+                    .join("_");
                 Ok(result.into())
             }
             (_, unexpected) => type_error_with_slice(expected_error, unexpected),
         }
     });
 
-    result.add_fn("trim", |ctx| {
+    result.add_fn("to_title_case", |ctx| {
         let expected_error = "a String";
 
         match ctx.instance_and_args(is_string, expected_error)? {
-            (KValue::Str(s), []) => {
-                let result = match s.find(|c: char| !c.is_whitespace()) {
-                    Some(start) => {
-                        let end = s.rfind(|c: char| !c.is_whitespace()).unwrap();
-                        s.with_bounds(start..(end + 1)).unwrap()
-                    }
-                    None => s.with_bounds(0..0).unwrap(),
-                };
+            (KValue::Str(s), []) => Ok(title_case(s).into()),
+            (_, unexpected) => type_error_with_slice(expected_error, unexpected),
+        }
+    });
 
+    result.add_fn("to_uppercase", |ctx| {
+        let expected_error = "a String";
+
+        match ctx.instance_and_args(is_string, expected_error)? {
+            (KValue::Str(s), []) => {
+                let result = s.chars().flat_map(|c| c.to_uppercase()).collect::<String>();
                 Ok(result.into())
             }
             (_, unexpected) => type_error_with_slice(expected_error, unexpected),
         }
     });
 
+    result.add_fn("trim", |ctx| {
+        let expected_error = "a String, and an optional String containing the characters to trim";
+
+        match ctx.instance_and_args(is_string, expected_error)? {
+            (KValue::Str(s), []) => Ok(trim(s, None, true, true).into()),
+            (KValue::Str(s), [KValue::Str(chars)]) => Ok(trim(s, Some(chars), true, true).into()),
+            (_, unexpected) => type_error_with_slice(expected_error, unexpected),
+        }
+    });
+
+    result.add_fn("trim_end", |ctx| {
+        let expected_error = "a String, and an optional String containing the characters to trim";
+
+        match ctx.instance_and_args(is_string, expected_error)? {
+            (KValue::Str(s), []) => Ok(trim(s, None, false, true).into()),
+            (KValue::Str(s), [KValue::Str(chars)]) => Ok(trim(s, Some(chars), false, true).into()),
+            (_, unexpected) => type_error_with_slice(expected_error, unexpected),
+        }
+    });
+
+    result.add_fn("trim_start", |ctx| {
+        let expected_error = "a String, and an optional String containing the characters to trim";
+
+        match ctx.instance_and_args(is_string, expected_error)? {
+            (KValue::Str(s), []) => Ok(trim(s, None, true, false).into()),
+            (KValue::Str(s), [KValue::Str(chars)]) => Ok(trim(s, Some(chars), true, false).into()),
+            (_, unexpected) => type_error_with_slice(expected_error, unexpected),
+        }
+    });
+
     result
 }
 
 fn is_string(value: &KValue) -> bool {
     matches!(value, KValue::Str(_))
 }
+
+// Returns true if the string is non-empty and all of its characters match the predicate
+fn all_chars(s: &str, predicate: impl Fn(char) -> bool) -> bool {
+    !s.is_empty() && s.chars().all(predicate)
+}
+
+// Returns true if the string contains at least one character with the given case,
+// and none with the opposite case
+fn is_cased(s: &str, is_case: fn(char) -> bool, is_opposite_case: fn(char) -> bool) -> bool {
+    s.chars().any(is_case) && !s.chars().any(is_opposite_case)
+}
+
+fn is_grapheme_boundary(s: &str, index: usize) -> bool {
+    GraphemeCursor::new(index, s.len(), true)
+        .is_boundary(s, 0)
+        .unwrap_or(false)
+}
+
+// Returns true if the range starts and ends on grapheme cluster boundaries
+fn is_grapheme_aligned(s: &str, start: usize, end: usize) -> bool {
+    is_grapheme_boundary(s, start) && is_grapheme_boundary(s, end)
+}
+
+// Finds the byte index of the first match of the pattern that doesn't split a grapheme cluster
+fn find(s: &str, pattern: &str) -> Option<usize> {
+    let mut search_start = 0;
+
+    while let Some(index) = s[search_start..].find(pattern) {
+        let index = search_start + index;
+        if is_grapheme_aligned(s, index, index + pattern.len()) {
+            return Some(index);
+        }
+        search_start = index + s[index..].chars().next()?.len_utf8();
+    }
+
+    None
+}
+
+// Finds the byte index of the last match of the pattern that doesn't split a grapheme cluster
+fn rfind(s: &str, pattern: &str) -> Option<usize> {
+    let mut search_end = s.len();
+
+    while let Some(index) = s[..search_end].rfind(pattern) {
+        let match_end = index + pattern.len();
+        if is_grapheme_aligned(s, index, match_end) {
+            return Some(index);
+        }
+        search_end = match_end - s[..match_end].chars().next_back()?.len_utf8();
+    }
+
+    None
+}
+
+fn pad(s: &KString, width: &KNumber, fill: &str, at_start: bool) -> Result<KValue> {
+    if fill.is_empty() {
+        return runtime_error!("the fill string must not be empty");
+    }
+
+    let len = s.graphemes(true).count();
+    let width = usize::from(width);
+    if len >= width {
+        return Ok(s.clone().into());
+    }
+
+    // Calculate the size of the result before allocating, guarding against huge widths
+    let fill_graphemes = fill.graphemes(true).collect::<Vec<_>>();
+    let pad_count = width - len;
+    let (full_fills, partial_fill) = (
+        pad_count / fill_graphemes.len(),
+        &fill_graphemes[..pad_count % fill_graphemes.len()],
+    );
+    let size = full_fills
+        .checked_mul(fill.len())
+        .and_then(|size| size.checked_add(partial_fill.iter().map(|g| g.len()).sum()))
+        .and_then(|size| size.checked_add(s.len()));
+
+    let mut result = string_with_capacity(size)?;
+    if !at_start {
+        result.push_str(s);
+    }
+    for _ in 0..full_fills {
+        result.push_str(fill);
+    }
+    result.extend(partial_fill.iter().copied());
+    if at_start {
+        result.push_str(s);
+    }
+
+    Ok(result.into())
+}
+
+fn repeat(s: &KString, n: usize) -> Result<KValue> {
+    if s.is_empty() {
+        return Ok(s.clone().into());
+    }
+
+    let mut result = string_with_capacity(s.len().checked_mul(n))?;
+    for _ in 0..n {
+        result.push_str(s);
+    }
+
+    Ok(result.into())
+}
+
+// Makes an empty string with the given capacity
+//
+// An error is returned if the capacity overflowed while being calculated (indicated by `None`),
+// or if the string couldn't be allocated.
+fn string_with_capacity(capacity: Option<usize>) -> Result<String> {
+    let mut result = String::new();
+    match capacity {
+        Some(capacity) if result.try_reserve_exact(capacity).is_ok() => Ok(result),
+        _ => runtime_error!("the resulting string is too large"),
+    }
+}
+
+// Makes an iterator that yields the pieces of a split string
+fn pieces_iterator<'a>(pieces: impl Iterator<Item = &'a str>) -> KIterator {
+    let pieces = pieces
+        .map(|piece| KIteratorOutput::Value(piece.into()))
+        .collect::<Vec<_>>();
+    KIterator::with_std_iter(pieces.into_iter())
+}
+
+// Trims graphemes from the start and/or end of the string
+//
+// If no characters are provided then whitespace is trimmed.
+fn trim(s: &KString, chars: Option<&str>, start: bool, end: bool) -> KString {
+    let should_trim = |grapheme: &str| match chars {
+        Some(chars) => chars.graphemes(true).any(|c| c == grapheme),
+        None => grapheme.chars().all(char::is_whitespace),
+    };
+
+    let trimmed_start = if start {
+        s.grapheme_indices(true)
+            .find(|(_, grapheme)| !should_trim(grapheme))
+            .map_or(s.len(), |(index, _)| index)
+    } else {
+        0
+    };
+
+    let trimmed_end = if end {
+        s[trimmed_start..]
+            .grapheme_indices(true)
+            .rfind(|(_, grapheme)| !should_trim(grapheme))
+            .map_or(0, |(index, grapheme)| index + grapheme.len())
+            + trimmed_start
+    } else {
+        s.len()
+    };
+
+    s.with_bounds(trimmed_start..trimmed_end).unwrap()
+}
+
+// Splits a string into words, for case conversion
+//
+// Words are separated by non-alphanumeric characters, and by changes in case,
+// e.g. `fooBar` and `HTTPServer` are each split into two words.
+fn words(s: &str) -> Vec<String> {
+    let chars = s.chars().collect::<Vec<_>>();
+    let mut result = Vec::new();
+    let mut word = String::new();
+
+    for (i, c) in chars.iter().enumerate() {
+        if !c.is_alphanumeric() {
+            if !word.is_empty() {
+                result.push(std::mem::take(&mut word));
+            }
+            continue;
+        }
+
+        if c.is_uppercase() && !word.is_empty() {
+            let previous = chars[i - 1];
+            let next_is_lowercase = chars.get(i + 1).is_some_and(|next| next.is_lowercase());
+            if previous.is_lowercase()
+                || previous.is_numeric()
+                || (previous.is_uppercase() && next_is_lowercase)
+            {
+                result.push(std::mem::take(&mut word));
+            }
+        }
+
+        word.push(*c);
+    }
+
+    if !word.is_empty() {
+        result.push(word);
+    }
+
+    result
+}
+
+// Capitalizes the first character of each whitespace-separated word,
+// with the remaining characters converted to lowercase
+fn title_case(s: &str) -> String {
+    let mut result = String::with_capacity(s.len());
+    let mut at_word_start = true;
+
+    for c in s.chars() {
+        if c.is_whitespace() {
+            result.push(c);
+            at_word_start = true;
+        } else if at_word_start {
+            result.extend(c.to_uppercase());
+            at_word_start = false;
+        } else {
+            result.extend(c.to_lowercase());
+        }
+    }
+
+    result
+}
@@ -0,0 +1,171 @@
+//! A mutable string buffer for building strings incrementally
+
+use crate::{
+    core_lib::{io::File, iterator::collect_pair},
+    derive::*,
+    prelude::*,
+    KIteratorOutput as Output, Ptr, PtrMut, Result,
+};
+
+/// A mutable string buffer, returned by `string.builder`
+///
+/// Appending to a builder avoids the repeated copying of data that occurs when strings are
+/// built up with `+`.
+#[derive(Clone, Default, KotoType)]
+#[koto(type_name = "StringBuilder")]
+pub struct StringBuilder(PtrMut<String>);
+
+#[koto_impl(runtime = crate)]
+impl StringBuilder {
+    /// Makes a new builder containing the provided string
+    pub fn new(initial: String) -> Self {
+        Self(initial.into())
+    }
+
+    /// Returns a copy of the builder's contents
+    pub fn contents(&self) -> String {
+        self.0.borrow().clone()
+    }
+
+    #[koto_method]
+    fn as_file(&self) -> KValue {
+        File::new(make_ptr!(StringBuilderFile(self.0.clone()))).into()
+    }
+
+    #[koto_method]
+    fn clear(ctx: MethodContext<Self>) -> Result<KValue> {
+        ctx.instance()?.0.borrow_mut().clear();
+        ctx.instance_result()
+    }
+
+    #[koto_method]
+    fn extend(ctx: MethodContext<Self>) -> Result<KValue> {
+        match ctx.args {
+            [iterable] if iterable.is_iterable() => {
+                // A VM is only spawned for iterables that need one to make their iterator,
+                // e.g. maps that implement `@iterator`.
+                let iterator = match KIterator::with_core_value(iterable) {
+                    Some(iterator) => iterator?,
+                    None => ctx.vm.spawn_shared_vm().make_iterator(iterable.clone())?,
+                };
+
+                // The new contents are prepared before borrowing the buffer,
+                // so that values containing the builder can be displayed.
+                let mut display_context = DisplayContext::with_vm(ctx.vm);
+
+                for output in iterator.map(collect_pair) {
+                    match output {
+                        Output::Value(value) => value.display(&mut display_context)?,
+                        Output::Error(error) => return Err(error),
+                        _ => unreachable!(), // value pairs have been collected in collect_pair
+                    }
+                }
+
+                let new_contents = display_context.result();
+                ctx.instance()?.0.borrow_mut().push_str(&new_contents);
+                ctx.instance_result()
+            }
+            unexpected => type_error_with_slice("an iterable", unexpected),
+        }
+    }
+
+    #[koto_method]
+    fn is_empty(&self) -> KValue {
+        self.0.borrow().is_empty().into()
+    }
+
+    #[koto_method]
+    fn len(&self) -> KValue {
+        self.0.borrow().len().into()
+    }
+
+    #[koto_method]
+    fn push(ctx: MethodContext<Self>) -> Result<KValue> {
+        match ctx.args {
+            [value] => {
+                let mut display_context = DisplayContext::with_vm(ctx.vm);
+                value.display(&mut display_context)?;
+                let new_contents = display_context.result();
+                ctx.instance()?.0.borrow_mut().push_str(&new_contents);
+                ctx.instance_result()
+            }
+            unexpected => type_error_with_slice("a single argument", unexpected),
+        }
+    }
+
+    #[koto_method]
+    fn push_line(ctx: MethodContext<Self>) -> Result<KValue> {
+        let mut display_context = DisplayContext::with_vm(ctx.vm);
+        match ctx.args {
+            [] => {}
+            [value] => value.display(&mut display_context)?,
+            unexpected => return type_error_with_slice("an optional value", unexpected),
+        }
+        display_context.append('\n');
+        let new_contents = display_context.result();
+        ctx.instance()?.0.borrow_mut().push_str(&new_contents);
+        ctx.instance_result()
+    }
+
+    #[koto_method]
+    fn to_string(&self) -> KValue {
+        self.contents().into()
+    }
+}
+
+impl KotoCopy for StringBuilder {
+    fn copy(&self) -> KObject {
+        Self::new(self.contents()).into()
+    }
+}
+
+impl KotoObject for StringBuilder {
+    fn display(&self, ctx: &mut DisplayContext) -> Result<()> {
+        ctx.append(self.0.borrow().as_str());
+        Ok(())
+    }
+
+    fn size(&self) -> Option<usize> {
+        Some(self.0.borrow().len())
+    }
+}
+
+impl From<StringBuilder> for KValue {
+    fn from(builder: StringBuilder) -> Self {
+        KObject::from(builder).into()
+    }
+}
+
+// A file that appends written data to a string builder's buffer
+struct StringBuilderFile(PtrMut<String>);
+
+impl KotoFile for StringBuilderFile {
+    fn id(&self) -> KString {
+        StringBuilder::type_static().into()
+    }
+}
+
+impl KotoRead for StringBuilderFile {}
+
+impl KotoWrite for StringBuilderFile {
+    fn write(&self, bytes: &[u8]) -> Result<()> {
+        match std::str::from_utf8(bytes) {
+            Ok(s) => {
+                self.0.borrow_mut().push_str(s);
+                Ok(())
+            }
+            Err(_) => runtime_error!("the data written to a StringBuilder must be valid UTF-8"),
+        }
+    }
+
+    fn write_line(&self, text: &str) -> Result<()> {
+        let mut buffer = self.0.borrow_mut();
+        buffer.push_str(text);
+        buffer.push('\n');
+        Ok(())
+    }
+
+    fn flush(&self) -> Result<()> {
+        Ok(())
+    }
+}
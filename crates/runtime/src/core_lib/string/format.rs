@@ -0,0 +1,302 @@
+//! Helpers for formatting values with [StringFormatOptions]
+
+use crate::{prelude::*, Result};
+use koto_parser::{StringAlignment, StringFormatOptions, StringFormatRepresentation};
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Renders a value as a string, applying the provided format options
+///
+/// `fill` is the string that's used when padding the result, defaulting to a space.
+pub fn format_value(
+    vm: &mut KotoVm,
+    value: KValue,
+    options: &StringFormatOptions,
+    fill: Option<&str>,
+) -> Result<String> {
+    let (sign, rendered, is_number) = match value {
+        KValue::Number(n) => {
+            let (sign, rendered) = format_number(n, options)?;
+            (sign, rendered, true)
+        }
+        other => {
+            if let Some(representation) = options.representation {
+                return runtime_error!(
+                    "'{}' formatting is only available for numbers, found '{}'",
+                    representation_char(representation),
+                    other.type_as_string()
+                );
+            }
+
+            if let (KValue::Object(o), Some(precision)) = (&other, options.precision) {
+                if let Some(rendered) = o.try_borrow()?.display_with_precision(precision)? {
+                    return Ok(pad_rendered("", rendered, false, options, fill));
+                }
+            }
+
+            let rendered = match vm.run_unary_op(UnaryOp::Display, other)? {
+                KValue::Str(rendered) => match options.precision {
+                    Some(precision) => {
+                        // precision acts as a maximum width for non-number values
+                        let mut truncated =
+                            String::with_capacity((precision as usize).min(rendered.len()));
+                        for grapheme in rendered.graphemes(true).take(precision as usize) {
+                            truncated.push_str(grapheme);
+                        }
+                        truncated
+                    }
+                    None => rendered.to_string(),
+                },
+                other => return type_error("String", &other),
+            };
+
+            ("", rendered, false)
+        }
+    };
+
+    Ok(pad_rendered(sign, rendered, is_number, options, fill))
+}
+
+// Pads a rendered value to the minimum width in the format options
+fn pad_rendered(
+    sign: &str,
+    rendered: String,
+    is_number: bool,
+    options: &StringFormatOptions,
+    fill: Option<&str>,
+) -> String {
+    let len = sign.len() + rendered.graphemes(true).count();
+    let min_width = options.min_width.unwrap_or(0) as usize;
+    if len >= min_width {
+        return format!("{sign}{rendered}");
+    }
+
+    let fill = fill.unwrap_or(" ");
+    let fill_chars = min_width - len;
+
+    match options.alignment {
+        // Zero-padded numbers place the padding after the sign
+        StringAlignment::Default if is_number && fill == "0" => {
+            format!("{sign}{}{rendered}", fill.repeat(fill_chars))
+        }
+        // Right-alignment by default for numbers
+        StringAlignment::Default if is_number => {
+            format!("{}{sign}{rendered}", fill.repeat(fill_chars))
+        }
+        // Left alignment by default for non-numbers
+        StringAlignment::Default | StringAlignment::Left => {
+            format!("{sign}{rendered}{}", fill.repeat(fill_chars))
+        }
+        StringAlignment::Center => {
+            let half_fill_chars = fill_chars as f32 / 2.0;
+            format!(
+                "{}{sign}{rendered}{}",
+                fill.repeat(half_fill_chars.floor() as usize),
+                fill.repeat(half_fill_chars.ceil() as usize),
+            )
+        }
+        StringAlignment::Right => format!("{}{sign}{rendered}", fill.repeat(fill_chars)),
+    }
+}
+
+/// Formats a template string, replacing `{}` placeholders with the provided arguments
+///
+/// Placeholders can refer to arguments by position (`{0}`), or to the entries of a map when it's
+/// the only argument (`{name}`). Each placeholder can include format options after a `:`.
+/// `{{` and `}}` are used to include braces in the output.
+pub fn format_template(vm: &mut KotoVm, template: &str, args: &[KValue]) -> Result<String> {
+    let mut result = String::with_capacity(template.len());
+    let mut remaining = template;
+    let mut next_arg = 0;
+
+    while let Some(brace_index) = remaining.find(['{', '}']) {
+        result.push_str(&remaining[..brace_index]);
+        remaining = &remaining[brace_index..];
+
+        if let Some(rest) = remaining.strip_prefix("{{") {
+            result.push('{');
+            remaining = rest;
+            continue;
+        }
+        if let Some(rest) = remaining.strip_prefix("}}") {
+            result.push('}');
+            remaining = rest;
+            continue;
+        }
+        if remaining.starts_with('}') {
+            return runtime_error!("Unexpected '}}' in format template, use '}}}}' to escape it");
+        }
+
+        // Parse the placeholder's id and optional format options
+        let placeholder = &remaining[1..];
+        let Some(id_end) = placeholder.find([':', '}']) else {
+            return runtime_error!("Unterminated placeholder in format template");
+        };
+        let id = &placeholder[..id_end];
+        let (format_string, placeholder_end) = match placeholder[id_end..].strip_prefix(':') {
+            Some(format_string) => match format_string_end(format_string) {
+                Some(end) => (Some(&format_string[..end]), id_end + 1 + end + 1),
+                None => return runtime_error!("Unterminated placeholder in format template"),
+            },
+            None => (None, id_end + 1),
+        };
+        remaining = &placeholder[placeholder_end..];
+
+        let value = if id.is_empty() {
+            next_arg += 1;
+            args.get(next_arg - 1).cloned()
+        } else if let Ok(index) = id.parse::<usize>() {
+            args.get(index).cloned()
+        } else {
+            match args {
+                [KValue::Map(map)] => map.get(id),
+                _ => {
+                    return runtime_error!(
+                        "Named placeholders ('{{{id}}}') require a single Map argument"
+                    )
+                }
+            }
+        };
+        let Some(value) = value else {
+            return runtime_error!("Missing value for placeholder '{{{id}}}'");
+        };
+
+        let formatted = match format_string {
+            Some(format_string) => {
+                let (options, fill) = match StringFormatOptions::parse_with_fill(format_string) {
+                    Ok(result) => result,
+                    Err(error) => {
+                        return runtime_error!("Invalid format string '{format_string}': {error}")
+                    }
+                };
+                format_value(vm, value, &options, fill)?
+            }
+            None => format_value(vm, value, &StringFormatOptions::default(), None)?,
+        };
+        result.push_str(&formatted);
+    }
+
+    result.push_str(remaining);
+    Ok(result)
+}
+
+// Renders a number, returning the sign separately so that it can be placed before zero-padding
+fn format_number(n: KNumber, options: &StringFormatOptions) -> Result<(&'static str, String)> {
+    use StringFormatRepresentation::*;
+
+    let rendered = match options.representation {
+        None => match options.precision {
+            Some(precision) if n.is_f64() || n.is_i64_in_f64_range() => {
+                format!("{:.*}", precision as usize, f64::from(n))
+            }
+            _ => n.to_string(),
+        },
+        Some(representation @ (HexLower | HexUpper | Binary | Octal)) => {
+            let integer = match n {
+                KNumber::I64(i) => i,
+                KNumber::F64(f)
+                    if f.fract() == 0.0 && f >= i64::MIN as f64 && f <= i64::MAX as f64 =>
+                {
+                    f as i64
+                }
+                KNumber::F64(f) => {
+                    return runtime_error!(
+                        "'{}' formatting requires an integer, found '{f}'",
+                        representation_char(representation)
+                    )
+                }
+            };
+
+            let magnitude = integer.unsigned_abs();
+            let digits = match representation {
+                HexLower => format!("{magnitude:x}"),
+                HexUpper => format!("{magnitude:X}"),
+                Binary => format!("{magnitude:b}"),
+                _ => format!("{magnitude:o}"),
+            };
+
+            if integer < 0 {
+                format!("-{digits}")
+            } else {
+                digits
+            }
+        }
+        Some(representation) => {
+            let upper = representation == ExpUpper;
+            match (n, options.precision) {
+                (KNumber::I64(i), None) if upper => format!("{i:E}"),
+                (KNumber::I64(i), None) => format!("{i:e}"),
+                (_, Some(precision)) if upper => {
+                    format!("{:.*E}", precision as usize, f64::from(n))
+                }
+                (_, Some(precision)) => format!("{:.*e}", precision as usize, f64::from(n)),
+                (_, None) if upper => format!("{:E}", f64::from(n)),
+                (_, None) => format!("{:e}", f64::from(n)),
+            }
+        }
+    };
+
+    let (negative, digits) = match rendered.strip_prefix('-') {
+        Some(digits) => (true, digits),
+        None => (false, rendered.as_str()),
+    };
+
+    let digits = if options.thousands_separator && options.representation.is_none() {
+        add_thousands_separators(digits)
+    } else {
+        digits.to_string()
+    };
+
+    let sign = if negative {
+        "-"
+    } else if options.show_sign {
+        "+"
+    } else {
+        ""
+    };
+
+    Ok((sign, digits))
+}
+
+// Inserts a `,` between each group of three digits in the integer part of a rendered number
+fn add_thousands_separators(rendered: &str) -> String {
+    let integer_end = rendered
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(rendered.len());
+    let (integer, rest) = rendered.split_at(integer_end);
+
+    let mut result = String::with_capacity(rendered.len() + integer.len() / 3);
+    for (i, digit) in integer.chars().enumerate() {
+        if i > 0 && (integer.len() - i) % 3 == 0 {
+            result.push(',');
+        }
+        result.push(digit);
+    }
+    result.push_str(rest);
+    result
+}
+
+// Finds the end of a placeholder's format string
+//
+// `}` is a valid fill character, so a fill and alignment prefix is skipped before looking for the
+// closing brace.
+fn format_string_end(format_string: &str) -> Option<usize> {
+    let mut graphemes = format_string.graphemes(true);
+    let skip = match (graphemes.next(), graphemes.next()) {
+        (Some(fill), Some("<" | "^" | ">")) => fill.len() + 1,
+        _ => 0,
+    };
+    format_string[skip..].find('}').map(|end| end + skip)
+}
+
+fn representation_char(representation: StringFormatRepresentation) -> char {
+    use StringFormatRepresentation::*;
+
+    match representation {
+        HexLower => 'x',
+        HexUpper => 'X',
+        Binary => 'b',
+        Octal => 'o',
+        ExpLower => 'e',
+        ExpUpper => 'E',
+    }
+}
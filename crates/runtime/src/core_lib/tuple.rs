@@ -1,12 +1,34 @@
 //! The `tuple` core library module
 
-use super::value_sort::sort_values;
+use super::value_sort::{self, call_bool_fn, sort_values_with_options, SortOptions};
 use crate::prelude::*;
 
 /// Initializes the `tuple` core library module
 pub fn make_module() -> KMap {
     let result = KMap::with_type("core.tuple");
 
+    result.add_fn("binary_search", |ctx| {
+        let expected_error = "a Tuple, a Value, and an optional key function";
+
+        let (t, value, key_fn) = match ctx.instance_and_args(is_tuple, expected_error)? {
+            (KValue::Tuple(t), [value]) => (t.clone(), value.clone(), None),
+            (KValue::Tuple(t), [value, key_fn]) if key_fn.is_callable() => {
+                (t.clone(), value.clone(), Some(key_fn.clone()))
+            }
+            (_, unexpected) => return type_error_with_slice(expected_error, unexpected),
+        };
+
+        let result = value_sort::binary_search(
+            ctx.vm,
+            t.len(),
+            |index| Ok(t[index].clone()),
+            &value,
+            key_fn.as_ref(),
+        )?;
+
+        Ok(result.map_or(KValue::Null, KValue::from))
+    });
+
     result.add_fn("contains", |ctx| {
         let expected_error = "a Tuple and a Value";
 
@@ -77,17 +99,35 @@ pub fn make_module() -> KMap {
         }
     });
 
+    result.add_fn("partition_point", |ctx| {
+        let expected_error = "a Tuple and a predicate function";
+
+        let (t, f) = match ctx.instance_and_args(is_tuple, expected_error)? {
+            (KValue::Tuple(t), [f]) if f.is_callable() => (t.clone(), f.clone()),
+            (_, unexpected) => return type_error_with_slice(expected_error, unexpected),
+        };
+
+        let result = value_sort::partition_point(t.len(), |index| {
+            call_bool_fn(ctx.vm, &f, &[t[index].clone()], "predicate")
+        })?;
+
+        Ok(result.into())
+    });
+
     result.add_fn("sort_copy", |ctx| {
-        let expected_error = "a Tuple";
+        let expected_error = "a Tuple, an optional key function, and an optional Bool";
 
         match ctx.instance_and_args(is_tuple, expected_error)? {
-            (KValue::Tuple(t), []) => {
-                let mut result = t.to_vec();
+            (KValue::Tuple(t), args) => match SortOptions::from_args(args) {
+                Some(options) => {
+                    let mut result = t.to_vec();
 
-                sort_values(ctx.vm, &mut result)?;
+                    sort_values_with_options(ctx.vm, &mut result, options, true)?;
 
-                Ok(KValue::Tuple(result.into()))
-            }
+                    Ok(KValue::Tuple(result.into()))
+                }
+                None => type_error_with_slice(expected_error, args),
+            },
             (_, unexpected) => type_error_with_slice(expected_error, unexpected),
         }
     });
@@ -1,36 +1,100 @@
 //! As operators can be overridden, we can't treat values specifically (i.e. use `PartialOrd` and
 //! `Ord` for example). So we always need to call operators to compare them. This module contains
 //! helpers for comparing and sorting [Value].
+//!
+//! The standard library's sorting functions expect comparisons to be infallible, so wrappers are
+//! provided here that capture the first error that occurs during sorting, leaving the input data
+//! as a permutation of its original contents.
 
 use std::cmp::Ordering;
 
-use crate::{runtime_error, BinaryOp, Error, KValue, KotoVm};
+use crate::{runtime_error, type_error, BinaryOp, Error, KValue, KotoVm};
 
 /// Sorts values in a slice using Koto operators for comparison.
-pub fn sort_values(vm: &mut KotoVm, arr: &mut [KValue]) -> Result<(), Error> {
-    let mut error = None;
-
-    arr.sort_by(|a, b| {
-        if error.is_some() {
-            return Ordering::Equal;
-        }
+///
+/// Only the `<` operator is used, so objects that only implement `@<` can be sorted.
+///
+/// An optional key function can be provided in the options, whose results are cached and then
+/// used for comparisons, and the sort order can be reversed with the `descending` flag.
+pub fn sort_values_with_options(
+    vm: &mut KotoVm,
+    arr: &mut [KValue],
+    options: SortOptions,
+    stable: bool,
+) -> Result<(), Error> {
+    let SortOptions { key, descending } = options;
 
-        match compare_values(vm, a, b) {
-            Ok(ordering) => ordering,
-            Err(e) => {
-                error.get_or_insert(e);
-                Ordering::Equal
+    let Some(key) = key else {
+        let compare = |a: &KValue, b: &KValue| {
+            if descending {
+                is_less(vm, b, a)
+            } else {
+                is_less(vm, a, b)
             }
+        };
+        return if stable {
+            sort_by(arr, compare)
+        } else {
+            sort_unstable_by(arr, compare)
+        };
+    };
+
+    let mut keys_and_values = arr
+        .iter()
+        .map(|value| {
+            vm.call_function(key.clone(), value.clone())
+                .map(|key| (key, value.clone()))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let compare = |(a, _): &(KValue, KValue), (b, _): &(KValue, KValue)| {
+        if descending {
+            is_less(vm, b, a)
+        } else {
+            is_less(vm, a, b)
         }
-    });
+    };
+    if stable {
+        sort_by(&mut keys_and_values, compare)?;
+    } else {
+        sort_unstable_by(&mut keys_and_values, compare)?;
+    }
 
-    if let Some(err) = error {
-        return Err(err);
+    for (value, (_key, sorted)) in arr.iter_mut().zip(keys_and_values) {
+        *value = sorted;
     }
 
     Ok(())
 }
 
+/// The options that can be passed to the core library's sorting functions
+pub struct SortOptions {
+    /// An optional function that produces the keys that should be compared
+    pub key: Option<KValue>,
+    /// True if the values should be sorted in descending order
+    pub descending: bool,
+}
+
+impl SortOptions {
+    /// Parses sorting options from a function's arguments
+    ///
+    /// The expected arguments are an optional key function followed by an optional Bool.
+    /// `None` is returned if the arguments don't match.
+    pub fn from_args(args: &[KValue]) -> Option<Self> {
+        use KValue::Bool;
+
+        let (key, descending) = match args {
+            [] => (None, false),
+            [Bool(descending)] => (None, *descending),
+            [key] if key.is_callable() => (Some(key.clone()), false),
+            [key, Bool(descending)] if key.is_callable() => (Some(key.clone()), *descending),
+            _ => return None,
+        };
+
+        Some(Self { key, descending })
+    }
+}
+
 /// Compares values using Koto operators.
 pub fn compare_values(vm: &mut KotoVm, a: &KValue, b: &KValue) -> Result<Ordering, Error> {
     use KValue::Bool;
@@ -53,3 +117,143 @@ pub fn compare_values(vm: &mut KotoVm, a: &KValue, b: &KValue) -> Result<Orderin
         }
     }
 }
+
+/// Returns true if `a` is less than `b`, using the `<` operator.
+pub fn is_less(vm: &mut KotoVm, a: &KValue, b: &KValue) -> Result<bool, Error> {
+    match vm.run_binary_op(BinaryOp::Less, a.clone(), b.clone())? {
+        KValue::Bool(result) => Ok(result),
+        unexpected => runtime_error!(
+            "Expected Bool from < comparison, found '{}'",
+            unexpected.type_as_string()
+        ),
+    }
+}
+
+/// Calls a function that's expected to return a Bool, e.g. a predicate or comparator
+pub fn call_bool_fn(
+    vm: &mut KotoVm,
+    f: &KValue,
+    args: &[KValue],
+    description: &str,
+) -> Result<bool, Error> {
+    match vm.call_function(f.clone(), args)? {
+        KValue::Bool(result) => Ok(result),
+        unexpected => type_error(
+            &format!("a Bool to be returned from the {description}"),
+            &unexpected,
+        ),
+    }
+}
+
+/// Searches sorted data for a value, returning the index of the first matching value
+///
+/// `get` provides the value at a given index, and an optional key function can be provided
+/// that's called with each value before it's compared with the search value.
+pub fn binary_search<F>(
+    vm: &mut KotoVm,
+    len: usize,
+    mut get: F,
+    value: &KValue,
+    key_fn: Option<&KValue>,
+) -> Result<Option<usize>, Error>
+where
+    F: FnMut(usize) -> Result<KValue, Error>,
+{
+    let mut get_key = |vm: &mut KotoVm, index| {
+        let value = get(index)?;
+        match key_fn {
+            Some(f) => vm.call_function(f.clone(), value),
+            None => Ok(value),
+        }
+    };
+
+    let index = partition_point(len, |index| {
+        let key = get_key(vm, index)?;
+        is_less(vm, &key, value)
+    })?;
+
+    if index == len {
+        return Ok(None);
+    }
+
+    let key = get_key(vm, index)?;
+    if is_less(vm, value, &key)? {
+        Ok(None)
+    } else {
+        Ok(Some(index))
+    }
+}
+
+/// Returns the first index in `0..len` for which the predicate returns false
+///
+/// The data is expected to be partitioned, with all values that match the predicate
+/// coming before the values that don't.
+pub fn partition_point<F>(len: usize, mut predicate: F) -> Result<usize, Error>
+where
+    F: FnMut(usize) -> Result<bool, Error>,
+{
+    let (mut low, mut high) = (0, len);
+
+    while low < high {
+        let mid = low + (high - low) / 2;
+        if predicate(mid)? {
+            low = mid + 1;
+        } else {
+            high = mid;
+        }
+    }
+
+    Ok(low)
+}
+
+/// A stable sort that uses a fallible `is_less` comparison function
+///
+/// If an error occurs then the remaining comparisons are skipped, and the error is returned.
+pub fn sort_by<T, F>(arr: &mut [T], is_less: F) -> Result<(), Error>
+where
+    F: FnMut(&T, &T) -> Result<bool, Error>,
+{
+    let mut error = None;
+    arr.sort_by(compare_with_is_less(&mut error, is_less));
+    error.map_or(Ok(()), Err)
+}
+
+/// An unstable sort that uses a fallible `is_less` comparison function
+///
+/// If an error occurs then the remaining comparisons are skipped, and the error is returned.
+pub fn sort_unstable_by<T, F>(arr: &mut [T], is_less: F) -> Result<(), Error>
+where
+    F: FnMut(&T, &T) -> Result<bool, Error>,
+{
+    let mut error = None;
+    arr.sort_unstable_by(compare_with_is_less(&mut error, is_less));
+    error.map_or(Ok(()), Err)
+}
+
+// Makes a comparison function for the standard library's sorts from a fallible `is_less` function
+//
+// The standard library's sorts only check if a comparison result is `Less`, so `Greater` is used
+// when `a` isn't less than `b`. The first error is stored in `error`, with `Equal` returned for
+// every comparison after the error.
+fn compare_with_is_less<'a, T, F>(
+    error: &'a mut Option<Error>,
+    mut is_less: F,
+) -> impl FnMut(&T, &T) -> Ordering + 'a
+where
+    F: FnMut(&T, &T) -> Result<bool, Error> + 'a,
+{
+    move |a, b| {
+        if error.is_some() {
+            return Ordering::Equal;
+        }
+
+        match is_less(a, b) {
+            Ok(true) => Ordering::Less,
+            Ok(false) => Ordering::Greater,
+            Err(e) => {
+                error.get_or_insert(e);
+                Ordering::Equal
+            }
+        }
+    }
+}
@@ -14,6 +14,15 @@ pub trait KotoFile: KotoRead + KotoWrite + KotoSend + KotoSync {
     fn seek(&self, _position: u64) -> Result<()> {
         runtime_error!("unsupported for this file type")
     }
+
+    /// Closes the file, releasing any underlying resources
+    ///
+    /// This is called when a `with` block that uses the file is exited, or when `close` is called
+    /// on the file. By default this does nothing, which is suitable for files like stdout which
+    /// shouldn't be closed by scripts.
+    fn close(&self) -> Result<()> {
+        Ok(())
+    }
 }
 
 /// A trait that defines the read operations of a [KotoFile]
@@ -25,6 +34,14 @@ pub trait KotoRead {
         runtime_error!("unsupported for this file type")
     }
 
+    /// Returns bytes from the file's current position
+    ///
+    /// If a count is provided then at most `count` bytes will be read,
+    /// otherwise the remaining contents of the file will be returned.
+    fn read_bytes(&self, _count: Option<usize>) -> Result<Vec<u8>> {
+        runtime_error!("unsupported for this file type")
+    }
+
     /// Returns the contents of the file from the current position
     fn read_to_string(&self) -> Result<String> {
         runtime_error!("unsupported for this file type")
@@ -19,6 +19,17 @@ impl KotoRead for DefaultStdin {
         Ok(Some(result))
     }
 
+    fn read_bytes(&self, count: Option<usize>) -> Result<Vec<u8>, Error> {
+        let mut result = Vec::new();
+        let mut stdin = io::stdin().lock();
+        match count {
+            Some(count) => stdin.take(count as u64).read_to_end(&mut result),
+            None => stdin.read_to_end(&mut result),
+        }
+        .map_err(map_io_err)?;
+        Ok(result)
+    }
+
     fn read_to_string(&self) -> Result<String, Error> {
         let mut result = String::new();
         io::stdin()
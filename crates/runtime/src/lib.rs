@@ -18,12 +18,12 @@ pub use crate::{
     io::{BufferedFile, DefaultStderr, DefaultStdin, DefaultStdout, KotoFile, KotoRead, KotoWrite},
     send_sync::{KotoSend, KotoSync},
     types::{
-        BinaryOp, CallContext, IsIterable, KCaptureFunction, KFunction, KIterator, KIteratorOutput,
-        KList, KMap, KNativeFunction, KNumber, KObject, KRange, KString, KTuple, KValue, KotoCopy,
-        KotoEntries, KotoFunction, KotoHasher, KotoIterator, KotoObject, KotoType, MetaKey,
-        MetaMap, MethodContext, UnaryOp, ValueKey, ValueMap, ValueVec,
+        BinaryOp, CallContext, IsIterable, KCaptureFunction, KFunction, KFuture, KIterator,
+        KIteratorOutput, KList, KMap, KNativeFunction, KNumber, KObject, KRange, KSet, KString,
+        KTuple, KValue, KotoCopy, KotoEntries, KotoFunction, KotoHasher, KotoIterator, KotoObject,
+        KotoType, MetaKey, MetaMap, MethodContext, UnaryOp, ValueKey, ValueMap, ValueSet, ValueVec,
     },
-    vm::{CallArgs, KotoVm, KotoVmSettings, ModuleImportedCallback, ReturnOrYield},
+    vm::{AsyncRun, CallArgs, KotoVm, KotoVmSettings, ModuleImportedCallback, ReturnOrYield},
 };
 pub use koto_derive as derive;
 pub use koto_memory::{make_ptr, make_ptr_mut, Borrow, BorrowMut, KCell, Ptr, PtrMut};
@@ -0,0 +1,172 @@
+use crate::{derive::*, prelude::*, PtrMut, Result};
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll, Waker},
+};
+
+// The trait used for futures that are wrapped by KFuture
+trait WrappedFuture: Future<Output = Result<KValue>> + KotoSend + KotoSync {}
+
+// Implement the trait for any matching future
+impl<T> WrappedFuture for T where T: Future<Output = Result<KValue>> + KotoSend + KotoSync {}
+
+type BoxedFuture = Pin<Box<dyn WrappedFuture>>;
+
+/// A value that will become available at some point in the future
+///
+/// Native functions can return a `KFuture` to allow the host to perform asynchronous work
+/// without blocking the VM. Using `await` on a pending future suspends execution until the future
+/// has been resolved, see [KotoVm::run_async].
+///
+/// A future can either wrap a Rust [Future] which will be polled while the VM is suspended
+/// (see [KFuture::new]), or it can be resolved manually by the host (see [KFuture::pending]).
+///
+/// Clones of a `KFuture` share the same state, so a host can keep a clone of a pending future
+/// and then resolve it once the result is available.
+#[derive(Clone, KotoCopy, KotoType)]
+#[koto(type_name = "Future")]
+pub struct KFuture(PtrMut<FutureState>);
+
+enum FutureState {
+    Pending {
+        // The wrapped Rust future, or None if the future will be resolved by the host
+        future: Option<BoxedFuture>,
+        // The waker that should be notified when the future is resolved by the host
+        waker: Option<Waker>,
+    },
+    // The wrapped future is currently being polled
+    Polling,
+    Ready(Result<KValue>),
+}
+
+impl KFuture {
+    /// Makes a `KFuture` that wraps a Rust future
+    ///
+    /// The wrapped future is polled while the VM is suspended by `await`.
+    pub fn new(
+        future: impl Future<Output = Result<KValue>> + KotoSend + KotoSync + 'static,
+    ) -> Self {
+        Self::with_state(FutureState::Pending {
+            future: Some(Box::pin(future)),
+            waker: None,
+        })
+    }
+
+    /// Makes a pending `KFuture` that will be resolved by the host via [KFuture::resolve]
+    pub fn pending() -> Self {
+        Self::with_state(FutureState::Pending {
+            future: None,
+            waker: None,
+        })
+    }
+
+    /// Makes a `KFuture` that has already been resolved with the given result
+    pub fn ready(result: Result<KValue>) -> Self {
+        Self::with_state(FutureState::Ready(result))
+    }
+
+    fn with_state(state: FutureState) -> Self {
+        Self(make_ptr_mut!(state))
+    }
+
+    /// Resolves the future with the given result
+    ///
+    /// Any task that's waiting on the future will be woken.
+    pub fn resolve(&self, result: Result<KValue>) {
+        let previous = std::mem::replace(&mut *self.0.borrow_mut(), FutureState::Ready(result));
+
+        if let FutureState::Pending {
+            waker: Some(waker), ..
+        } = previous
+        {
+            waker.wake();
+        }
+    }
+
+    /// Returns true if the future has been resolved
+    pub fn is_ready(&self) -> bool {
+        matches!(&*self.0.borrow(), FutureState::Ready(_))
+    }
+
+    /// Returns the future's result if it has been resolved, otherwise `None` is returned
+    pub fn result(&self) -> Option<Result<KValue>> {
+        match &*self.0.borrow() {
+            FutureState::Ready(result) => Some(result.clone()),
+            _ => None,
+        }
+    }
+}
+
+impl KotoEntries for KFuture {}
+
+impl KotoObject for KFuture {
+    fn display(&self, ctx: &mut DisplayContext) -> Result<()> {
+        if self.is_ready() {
+            ctx.append("Future(ready)");
+        } else {
+            ctx.append("Future(pending)");
+        }
+        Ok(())
+    }
+}
+
+impl Future for KFuture {
+    type Output = Result<KValue>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        // The wrapped future is taken out of the shared state while it's being polled,
+        // allowing the future to access clones of this KFuture without conflicting borrows.
+        let mut future = {
+            let mut state = self.0.borrow_mut();
+            match std::mem::replace(&mut *state, FutureState::Polling) {
+                FutureState::Pending {
+                    future: Some(future),
+                    ..
+                } => future,
+                FutureState::Pending { future: None, .. } => {
+                    *state = FutureState::Pending {
+                        future: None,
+                        waker: Some(cx.waker().clone()),
+                    };
+                    return Poll::Pending;
+                }
+                FutureState::Ready(result) => {
+                    *state = FutureState::Ready(result.clone());
+                    return Poll::Ready(result);
+                }
+                FutureState::Polling => return Poll::Pending,
+            }
+        };
+
+        let poll_result = future.as_mut().poll(cx);
+
+        let mut state = self.0.borrow_mut();
+
+        // The future may have been resolved by the host while it was being polled,
+        // in which case the host's result takes priority.
+        if let FutureState::Ready(result) = &*state {
+            return Poll::Ready(result.clone());
+        }
+
+        match poll_result {
+            Poll::Ready(result) => {
+                *state = FutureState::Ready(result.clone());
+                Poll::Ready(result)
+            }
+            Poll::Pending => {
+                *state = FutureState::Pending {
+                    future: Some(future),
+                    waker: None,
+                };
+                Poll::Pending
+            }
+        }
+    }
+}
+
+impl From<KFuture> for KValue {
+    fn from(future: KFuture) -> Self {
+        KObject::from(future).into()
+    }
+}
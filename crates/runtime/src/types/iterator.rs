@@ -110,11 +110,42 @@ impl KIterator {
         Self::new(MapIterator::new(map))
     }
 
+    /// Creates a new KIterator from a Set
+    pub fn with_set(set: KSet) -> Self {
+        Self::new(SetIterator::new(set))
+    }
+
     /// Creates a new KIterator from a String
     pub fn with_string(s: KString) -> Self {
         Self::new(StringIterator::new(s))
     }
 
+    /// Creates a new KIterator from a core value that can be iterated without a VM
+    ///
+    /// `None` is returned for values that need a VM to make an iterator, i.e. maps that implement
+    /// `@next` or `@iterator`, objects, and non-iterable values. See [KotoVm::make_iterator].
+    pub fn with_core_value(value: &KValue) -> Option<Result<Self>> {
+        use KValue::*;
+
+        let result = match value {
+            Map(m)
+                if m.contains_meta_key(&UnaryOp::Next.into())
+                    || m.contains_meta_key(&UnaryOp::Iterator.into()) =>
+            {
+                return None;
+            }
+            Iterator(i) => Ok(i.clone()),
+            Range(r) => Self::with_range(r.clone()),
+            List(l) => Ok(Self::with_list(l.clone())),
+            Tuple(t) => Ok(Self::with_tuple(t.clone())),
+            Str(s) => Ok(Self::with_string(s.clone())),
+            Map(m) => Ok(Self::with_map(m.clone())),
+            Set(set) => Ok(Self::with_set(set.clone())),
+            _ => return None,
+        };
+        Some(result)
+    }
+
     /// Creates a new KIterator from a Vm, used to implement generators
     pub fn with_vm(vm: KotoVm) -> Self {
         Self::new(GeneratorIterator::new(vm))
@@ -214,7 +245,7 @@ impl KotoIterator for RangeIterator {
 
     fn next_back(&mut self) -> Option<KIteratorOutput> {
         match self.range.pop_back() {
-            Ok(Some(output)) => Some(KIteratorOutput::Value(output.into())),
+            Ok(Some(output)) => Some(KIteratorOutput::Value(output)),
             Ok(None) => None,
             Err(e) => Some(KIteratorOutput::Error(e)),
         }
@@ -226,7 +257,7 @@ impl Iterator for RangeIterator {
 
     fn next(&mut self) -> Option<Self::Item> {
         match self.range.pop_front() {
-            Ok(Some(output)) => Some(KIteratorOutput::Value(output.into())),
+            Ok(Some(output)) => Some(KIteratorOutput::Value(output)),
             Ok(None) => None,
             Err(e) => Some(KIteratorOutput::Error(e)),
         }
@@ -401,6 +432,69 @@ impl Iterator for MapIterator {
     }
 }
 
+#[derive(Clone)]
+struct SetIterator {
+    data: KSet,
+    index: usize,
+    end: usize,
+}
+
+impl SetIterator {
+    fn new(data: KSet) -> Self {
+        let end = data.len();
+        Self {
+            data,
+            index: 0,
+            end,
+        }
+    }
+
+    fn get_output(&self, index: usize) -> Option<KIteratorOutput> {
+        self.data
+            .data()
+            .get_index(index)
+            .map(|key| KIteratorOutput::Value(key.value().clone()))
+    }
+}
+
+impl KotoIterator for SetIterator {
+    fn make_copy(&self) -> Result<KIterator> {
+        Ok(KIterator::new(self.clone()))
+    }
+
+    fn is_bidirectional(&self) -> bool {
+        true
+    }
+
+    fn next_back(&mut self) -> Option<KIteratorOutput> {
+        if self.end > self.index {
+            self.end -= 1;
+            self.get_output(self.end)
+        } else {
+            None
+        }
+    }
+}
+
+impl Iterator for SetIterator {
+    type Item = Output;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.end > self.index {
+            let result = self.get_output(self.index);
+            self.index += 1;
+            result
+        } else {
+            None
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.data.data().len().saturating_sub(self.index);
+        (remaining, Some(remaining))
+    }
+}
+
 #[derive(Clone)]
 struct MetaIterator {
     vm: KotoVm,
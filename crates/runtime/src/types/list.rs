@@ -1,4 +1,5 @@
 use crate::{prelude::*, Borrow, BorrowMut, PtrMut, Result};
+use koto_memory::Address;
 
 /// The underlying Vec type used by [KList]
 pub type ValueVec = smallvec::SmallVec<[KValue; 4]>;
@@ -43,6 +44,11 @@ impl KList {
         self.0.borrow_mut()
     }
 
+    /// Returns the address of the list's data, which can be used to detect reference cycles
+    pub(crate) fn address(&self) -> Address {
+        PtrMut::address(&self.0)
+    }
+
     /// Renders the list to the provided display context
     pub fn display(&self, ctx: &mut DisplayContext) -> Result<()> {
         ctx.append('[');
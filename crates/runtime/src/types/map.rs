@@ -1,26 +1,34 @@
 use crate::{prelude::*, Borrow, BorrowMut, Error, PtrMut, Result};
 use indexmap::{Equivalent, IndexMap};
+use koto_memory::Address;
 use rustc_hash::FxHasher;
 use std::{
+    cmp::Ordering,
+    collections::HashSet,
     hash::{BuildHasherDefault, Hash},
-    ops::{Deref, DerefMut, RangeBounds},
+    ops::{Deref, RangeBounds},
 };
 
 /// The hasher used throughout the Koto runtime
 pub type KotoHasher = FxHasher;
 
 type ValueMapType = IndexMap<ValueKey, KValue, BuildHasherDefault<KotoHasher>>;
+type ConstKeySet = HashSet<ValueKey, BuildHasherDefault<KotoHasher>>;
 
 /// The (ValueKey -> Value) 'data' hashmap used by the Koto runtime
 ///
 /// See also: [KMap]
 #[derive(Clone, Default)]
-pub struct ValueMap(ValueMapType);
+pub struct ValueMap {
+    entries: ValueMapType,
+    // The keys of entries that can't be reassigned, see [KMap::add_const_key]
+    const_keys: Option<Box<ConstKeySet>>,
+}
 
 impl ValueMap {
     /// Creates a new DataMap with the given capacity
     pub fn with_capacity(capacity: usize) -> Self {
-        Self(ValueMapType::with_capacity_and_hasher(
+        Self::from(ValueMapType::with_capacity_and_hasher(
             capacity,
             Default::default(),
         ))
@@ -36,25 +44,130 @@ impl ValueMap {
             )
         })
     }
+
+    /// Inserts an entry into the map, returning the entry's previous value if it had one
+    ///
+    /// An error is returned if the key has been marked as constant, see [KMap::add_const_key].
+    pub fn insert(&mut self, key: ValueKey, value: KValue) -> Result<Option<KValue>> {
+        if self.is_const_key(&key) {
+            return runtime_error!("'{key}' is a constant and can't be reassigned");
+        }
+        Ok(self.entries.insert(key, value))
+    }
+
+    /// Inserts each of the provided entries into the map
+    ///
+    /// An error is returned if any of the keys have been marked as constant,
+    /// see [ValueMap::insert].
+    pub fn extend(&mut self, entries: impl IntoIterator<Item = (ValueKey, KValue)>) -> Result<()> {
+        for (key, value) in entries {
+            self.insert(key, value)?;
+        }
+        Ok(())
+    }
+
+    /// Removes an entry from the map, preserving the order of the remaining entries
+    ///
+    /// An error is returned if the key has been marked as constant, see [KMap::add_const_key].
+    pub fn shift_remove<K>(&mut self, key: &K) -> Result<Option<KValue>>
+    where
+        K: Hash + Equivalent<ValueKey> + ?Sized,
+    {
+        match self.entries.get_full(key) {
+            Some((_, key, _)) if self.is_const_key(key) => {
+                runtime_error!("'{key}' is a constant and can't be removed")
+            }
+            _ => Ok(self.entries.shift_remove(key)),
+        }
+    }
+
+    /// Removes all entries from the map
+    ///
+    /// An error is returned if the map contains constant entries.
+    pub fn clear(&mut self) -> Result<()> {
+        self.check_no_const_keys("cleared")?;
+        self.entries.clear();
+        Ok(())
+    }
+
+    /// Retains the entries for which the predicate returns true
+    ///
+    /// An error is returned if the map contains constant entries.
+    pub fn retain(&mut self, predicate: impl FnMut(&ValueKey, &mut KValue) -> bool) -> Result<()> {
+        self.check_no_const_keys("removed")?;
+        self.entries.retain(predicate);
+        Ok(())
+    }
+
+    /// Sorts the map's entries using the provided comparison function
+    ///
+    /// An error is returned if the map contains constant entries.
+    pub fn sort_by(
+        &mut self,
+        compare: impl FnMut(&ValueKey, &KValue, &ValueKey, &KValue) -> Ordering,
+    ) -> Result<()> {
+        self.check_no_const_keys("reordered")?;
+        self.entries.sort_by(compare);
+        Ok(())
+    }
+
+    /// Reserves capacity for at least `additional` more entries
+    pub fn reserve(&mut self, additional: usize) {
+        self.entries.reserve(additional);
+    }
+
+    fn is_const_key(&self, key: &ValueKey) -> bool {
+        self.const_keys
+            .as_ref()
+            .is_some_and(|const_keys| const_keys.contains(key))
+    }
+
+    fn check_no_const_keys(&self, action: &str) -> Result<()> {
+        if self.const_keys.is_some() {
+            runtime_error!("The map contains constant entries, which can't be {action}")
+        } else {
+            Ok(())
+        }
+    }
+
+    // Copies the entries and constant keys from a reloaded module's exports into the map
+    //
+    // Constant entries are replaced, so that the module's importers see the reloaded values.
+    pub(crate) fn merge_reloaded(&mut self, reloaded: &ValueMap) {
+        self.entries.extend(
+            reloaded
+                .entries
+                .iter()
+                .map(|(key, value)| (key.clone(), value.clone())),
+        );
+        if let Some(reloaded_const_keys) = &reloaded.const_keys {
+            self.const_keys
+                .get_or_insert_with(Default::default)
+                .extend(reloaded_const_keys.iter().cloned());
+        }
+    }
 }
 
 impl Deref for ValueMap {
     type Target = ValueMapType;
 
     fn deref(&self) -> &Self::Target {
-        &self.0
+        &self.entries
     }
 }
 
-impl DerefMut for ValueMap {
-    fn deref_mut(&mut self) -> &mut Self::Target {
-        &mut self.0
+impl FromIterator<(ValueKey, KValue)> for ValueMap {
+    fn from_iter<T: IntoIterator<Item = (ValueKey, KValue)>>(iter: T) -> ValueMap {
+        Self::from(ValueMapType::from_iter(iter))
     }
 }
 
-impl FromIterator<(ValueKey, KValue)> for ValueMap {
-    fn from_iter<T: IntoIterator<Item = (ValueKey, KValue)>>(iter: T) -> ValueMap {
-        Self(ValueMapType::from_iter(iter))
+impl From<ValueMapType> for ValueMap {
+    fn from(entries: ValueMapType) -> Self {
+        Self {
+            entries,
+            const_keys: None,
+        }
     }
 }
 
@@ -114,6 +227,11 @@ impl KMap {
         self.data.borrow_mut()
     }
 
+    /// Returns the address of the map's data, which can be used to detect reference cycles
+    pub(crate) fn address(&self) -> Address {
+        PtrMut::address(&self.data)
+    }
+
     /// Provides a reference to the KMap's meta map
     ///
     /// This is returned as a reference to the meta map's PtrMut to allow for cloning.
@@ -151,8 +269,12 @@ impl KMap {
     }
 
     /// Insert an entry into the KMap's data
+    ///
+    /// Entries that have been marked as constant are left unchanged,
+    /// see [KMap::add_const_key] and [ValueMap::insert].
     pub fn insert(&self, key: impl Into<ValueKey>, value: impl Into<KValue>) {
-        self.data_mut().insert(key.into(), value.into());
+        // Errors are only returned for constant keys, which are documented as being skipped
+        let _ = self.data_mut().insert(key.into(), value.into());
     }
 
     /// Inserts a value into the meta map, initializing the meta map if it doesn't yet exist
@@ -163,6 +285,24 @@ impl KMap {
             .insert(key, value);
     }
 
+    /// Marks the entry with the given key as being constant, preventing it from being reassigned
+    ///
+    /// Constant keys are stored alongside the map's data rather than in its meta map, so marking
+    /// a key as constant doesn't affect the map's type or its core library functions.
+    pub fn add_const_key(&self, key: ValueKey) {
+        self.data_mut()
+            .const_keys
+            .get_or_insert_with(Default::default)
+            .insert(key);
+    }
+
+    /// Returns true if the entry with the given key has been marked as being constant
+    ///
+    /// See [KMap::add_const_key].
+    pub fn is_const_key(&self, key: &ValueKey) -> bool {
+        self.data().is_const_key(key)
+    }
+
     /// Adds a function to the KMap's data map
     pub fn add_fn(&self, id: &str, f: impl KotoFunction) {
         self.insert(id, KValue::NativeFunction(KNativeFunction::new(f)));
@@ -182,9 +322,9 @@ impl KMap {
         self.data().is_empty()
     }
 
-    /// Removes all contents from the data map, and removes the meta map
+    /// Removes all contents from the data map, and removes the meta map and constant keys
     pub fn clear(&mut self) {
-        self.data_mut().clear();
+        *self.data_mut() = ValueMap::default();
         self.meta = None;
     }
 
@@ -221,7 +361,10 @@ impl KMap {
                         ctx.append(", ");
                     }
 
-                    let mut key_ctx = DisplayContext::default();
+                    let mut key_ctx = match ctx.vm() {
+                        Some(vm) => DisplayContext::with_vm(vm),
+                        None => DisplayContext::default(),
+                    };
                     key.value().display(&mut key_ctx)?;
                     ctx.append(key_ctx.result());
                     ctx.append(": ");
@@ -258,8 +401,33 @@ mod tests {
         assert!(m.get("test").is_some());
         assert!(matches!(
             m.data_mut().shift_remove("test"),
-            Some(KValue::Null)
+            Ok(Some(KValue::Null))
         ));
         assert!(m.get("test").is_none());
     }
+
+    #[test]
+    fn const_keys_prevent_modification() {
+        let m = KMap::default();
+        m.insert("a", 1);
+        m.insert("b", 2);
+        m.add_const_key("a".into());
+
+        let mut data = m.data_mut();
+        assert!(data.insert("a".into(), 99.into()).is_err());
+        assert!(data.shift_remove("a").is_err());
+        assert!(data.extend([("a".into(), 99.into())]).is_err());
+        assert!(data.clear().is_err());
+        assert!(data.retain(|_, _| false).is_err());
+        assert!(data
+            .sort_by(|a, _, b, _| b.partial_cmp(a).unwrap())
+            .is_err());
+
+        // Non-constant entries can still be modified
+        assert!(matches!(data.insert("b".into(), 3.into()), Ok(Some(_))));
+        assert!(matches!(data.shift_remove("b"), Ok(Some(_))));
+
+        assert_eq!(data.len(), 1);
+        assert!(matches!(data.get("a"), Some(KValue::Number(n)) if *n == 1));
+    }
 }
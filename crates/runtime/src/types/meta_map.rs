@@ -58,6 +58,26 @@ pub enum MetaKey {
     ///
     /// Defines the behaviour when performing a function call on the value.
     Call,
+    /// Index assignment - `@[]=`
+    ///
+    /// Defines the behaviour when assigning a value to an index, e.g. `x[i] = y`.
+    /// The function is called with the index and the value as arguments.
+    SetIndex,
+    /// Dynamic access - `@access`
+    ///
+    /// Called with the key as a string when a `.` access doesn't find the key in the map or its
+    /// bases, with the function's result being used as the access result.
+    Access,
+    /// Hashing - `@hash`
+    ///
+    /// Allows the map to be used as a key in other maps.
+    /// The function should return a hashable value that identifies the map,
+    /// with `@==` being used (if available) to compare maps with matching hashes.
+    Hash,
+    /// Resource cleanup - `@close`
+    ///
+    /// Called when a `with` block that uses the map as its resource is exited.
+    Close,
     /// A named key
     ///
     /// e.g. `@meta my_named_key`
@@ -85,6 +105,13 @@ pub enum MetaKey {
     ///
     /// Used to define a function that will be run when a module is first imported.
     Main,
+    /// `@reload`
+    ///
+    /// Used to define a function that will be run when a module is reloaded.
+    ///
+    /// The function is called with the module's previous exports map as its argument, allowing
+    /// state to be migrated to the reloaded version of the module.
+    Reload,
     /// `@type`
     ///
     /// Provides a [KString](crate::KString) that declares the value's type.
@@ -232,6 +259,10 @@ pub fn meta_id_to_key(id: MetaKeyId, name: Option<KString>) -> Result<MetaKey> {
         MetaKeyId::Equal => MetaKey::BinaryOp(Equal),
         MetaKeyId::NotEqual => MetaKey::BinaryOp(NotEqual),
         MetaKeyId::Index => MetaKey::BinaryOp(Index),
+        MetaKeyId::SetIndex => MetaKey::SetIndex,
+        MetaKeyId::Hash => MetaKey::Hash,
+        MetaKeyId::Access => MetaKey::Access,
+        MetaKeyId::Close => MetaKey::Close,
         MetaKeyId::Iterator => MetaKey::UnaryOp(Iterator),
         MetaKeyId::Next => MetaKey::UnaryOp(Next),
         MetaKeyId::NextBack => MetaKey::UnaryOp(NextBack),
@@ -247,6 +278,7 @@ pub fn meta_id_to_key(id: MetaKeyId, name: Option<KString>) -> Result<MetaKey> {
         MetaKeyId::PreTest => MetaKey::PreTest,
         MetaKeyId::PostTest => MetaKey::PostTest,
         MetaKeyId::Main => MetaKey::Main,
+        MetaKeyId::Reload => MetaKey::Reload,
         MetaKeyId::Type => MetaKey::Type,
         MetaKeyId::Base => MetaKey::Base,
         MetaKeyId::Invalid => return runtime_error!("Invalid MetaKeyId"),
@@ -1,6 +1,7 @@
 //! The core types used in the Koto runtime
 
 mod function;
+mod future;
 mod iterator;
 mod list;
 mod map;
@@ -9,6 +10,7 @@ mod native_function;
 mod number;
 mod object;
 mod range;
+mod set;
 mod string;
 mod tuple;
 pub mod value;
@@ -16,6 +18,7 @@ mod value_key;
 
 pub use self::{
     function::{KCaptureFunction, KFunction},
+    future::KFuture,
     iterator::{KIterator, KIteratorOutput, KotoIterator},
     list::{KList, ValueVec},
     map::{KMap, KotoHasher, ValueMap},
@@ -24,6 +27,7 @@ pub use self::{
     number::KNumber,
     object::{IsIterable, KObject, KotoCopy, KotoEntries, KotoObject, KotoType, MethodContext},
     range::KRange,
+    set::{KSet, ValueSet},
     string::KString,
     tuple::KTuple,
     value::KValue,
@@ -22,7 +22,7 @@ impl KNumber {
     pub fn abs(self) -> Self {
         match self {
             Self::F64(n) => Self::F64(n.abs()),
-            Self::I64(n) => Self::I64(n.abs()),
+            Self::I64(n) => Self::I64(n.wrapping_abs()),
         }
     }
 
@@ -99,7 +99,26 @@ impl KNumber {
             (F64(a), F64(b)) => F64(a.powf(b)),
             (F64(a), I64(b)) => F64(a.powf(b as f64)),
             (I64(a), F64(b)) => F64((a as f64).powf(b)),
-            (I64(a), I64(b)) => I64(a.pow(b as u32)),
+            (I64(a), I64(b)) => I64(a.wrapping_pow(b as u32)),
+        }
+    }
+
+    /// Returns the result of raising self to the power of `other`,
+    /// or None if integer overflow occurred
+    ///
+    /// See [KNumber::pow]
+    pub fn checked_pow(self, other: Self) -> Option<Self> {
+        match (self, other) {
+            (Self::I64(a), Self::I64(b)) => a.checked_pow(b as u32).map(Self::I64),
+            _ => Some(self.pow(other)),
+        }
+    }
+
+    /// Returns the negated number, or None if integer overflow occurred
+    pub fn checked_neg(self) -> Option<Self> {
+        match self {
+            Self::F64(n) => Some(Self::F64(-n)),
+            Self::I64(n) => n.checked_neg().map(Self::I64),
         }
     }
 
@@ -201,7 +220,7 @@ impl ops::Neg for KNumber {
 
         match self {
             F64(n) => F64(-n),
-            I64(n) => I64(-n),
+            I64(n) => I64(n.wrapping_neg()),
         }
     }
 }
@@ -214,7 +233,7 @@ impl ops::Neg for &KNumber {
 
         match *self {
             F64(n) => F64(-n),
-            I64(n) => I64(-n),
+            I64(n) => I64(n.wrapping_neg()),
         }
     }
 }
@@ -361,8 +380,9 @@ from_number!(u64);
 from_number!(isize);
 from_number!(usize);
 
+// Integer operations wrap on overflow, with checked versions of the operations available
 macro_rules! number_op {
-    ($trait:ident, $fn:ident, $op:tt) => {
+    ($trait:ident, $fn:ident, $op:tt, $wrapping_fn:ident, $checked_fn:ident) => {
         impl ops::$trait for KNumber {
             type Output = KNumber;
 
@@ -373,7 +393,7 @@ macro_rules! number_op {
                     (F64(a), F64(b)) => F64(a $op b),
                     (F64(a), I64(b)) => F64(a $op b as f64),
                     (I64(a), F64(b)) => F64(a as f64 $op b),
-                    (I64(a), I64(b)) => I64(a $op b),
+                    (I64(a), I64(b)) => I64(a.$wrapping_fn(b)),
                 }
             }
         }
@@ -382,23 +402,27 @@ macro_rules! number_op {
             type Output = KNumber;
 
             fn $fn(self, other: &KNumber) -> KNumber {
-                use KNumber::*;
+                *self $op *other
+            }
+        }
 
-                match (*self, *other) {
-                    (F64(a), F64(b)) => F64(a $op b),
-                    (F64(a), I64(b)) => F64(a $op b as f64),
-                    (I64(a), F64(b)) => F64(a as f64 $op b),
-                    (I64(a), I64(b)) => I64(a $op b),
+        impl KNumber {
+            #[doc = concat!("Performs the `", stringify!($op), "` operation, ")]
+            #[doc = "returning None if integer overflow occurred"]
+            pub fn $checked_fn(self, other: KNumber) -> Option<KNumber> {
+                match (self, other) {
+                    (KNumber::I64(a), KNumber::I64(b)) => a.$checked_fn(b).map(KNumber::I64),
+                    _ => Some(self $op other),
                 }
             }
         }
     };
 }
 
-number_op!(Add, add, +);
-number_op!(Sub, sub, -);
-number_op!(Mul, mul, *);
-number_op!(Rem, rem, %);
+number_op!(Add, add, +, wrapping_add, checked_add);
+number_op!(Sub, sub, -, wrapping_sub, checked_sub);
+number_op!(Mul, mul, *, wrapping_mul, checked_mul);
+number_op!(Rem, rem, %, wrapping_rem, checked_rem);
 
 impl ops::Div for KNumber {
     type Output = KNumber;
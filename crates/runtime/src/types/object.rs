@@ -54,6 +54,17 @@ pub trait KotoEntries {
     fn entries(&self) -> Option<KMap> {
         None
     }
+
+    /// Called when a key isn't found in the object's [entries](KotoEntries::entries)
+    ///
+    /// This allows objects to provide entries that are computed on demand.
+    /// `None` is returned by default, which results in a 'not found' error.
+    ///
+    /// When using `#[koto_impl]`, a method tagged with `#[koto_access]` will be used to implement
+    /// this function.
+    fn access(&self, _key: &KString) -> Option<KValue> {
+        None
+    }
 }
 
 /// A trait for implementing objects that can be added to the Koto runtime
@@ -123,6 +134,14 @@ pub trait KotoObject: KotoType + KotoCopy + KotoEntries + KotoSend + KotoSync +
         Ok(())
     }
 
+    /// Called when the object is displayed with a precision format option, e.g. `'{x:.2}'`
+    ///
+    /// If `None` is returned (the default), then the output of [KotoObject::display] is used,
+    /// truncated to the precision's length.
+    fn display_with_precision(&self, _precision: u32) -> Result<Option<String>> {
+        Ok(None)
+    }
+
     /// Called for indexing operations, e.g. `x[0]`
     ///
     /// See also: [KotoObject::size]
@@ -130,6 +149,13 @@ pub trait KotoObject: KotoType + KotoCopy + KotoEntries + KotoSend + KotoSync +
         unimplemented_error("@index", self.type_string())
     }
 
+    /// Called for index assignment operations, e.g. `x[0] = 1`
+    ///
+    /// See also: [KotoObject::index]
+    fn set_index(&mut self, _index: &KValue, _value: KValue) -> Result<()> {
+        unimplemented_error("@[]=", self.type_string())
+    }
+
     /// Called when checking for the number of elements contained in the object
     ///
     /// The runtime defers to this function when the 'size' of an object is needed
@@ -147,6 +173,14 @@ pub trait KotoObject: KotoType + KotoCopy + KotoEntries + KotoSend + KotoSync +
         unimplemented_error("@||", self.type_string())
     }
 
+    /// Called when a `with` block that uses the object as its resource is exited
+    ///
+    /// This is called whether the block is exited normally or due to a thrown error,
+    /// allowing resources like open files to be released deterministically.
+    fn close(&mut self) -> Result<()> {
+        unimplemented_error("@close", self.type_string())
+    }
+
     /// Defines the behavior of negation (e.g. `-x`)
     fn negate(&self, _vm: &mut KotoVm) -> Result<KValue> {
         unimplemented_error("@negate", self.type_string())
@@ -177,6 +211,41 @@ pub trait KotoObject: KotoType + KotoCopy + KotoEntries + KotoSend + KotoSync +
         unimplemented_error("@%", self.type_string())
     }
 
+    /// The `+` addition operator, when the object is on the right-hand side of the operation
+    ///
+    /// This is called when the left-hand side of the operation is a Number, e.g. `1 + x`.
+    fn add_rhs(&self, _lhs: &KValue) -> Result<KValue> {
+        unimplemented_rhs_error("+", self.type_string())
+    }
+
+    /// The `-` subtraction operator, when the object is on the right-hand side of the operation
+    ///
+    /// See [KotoObject::add_rhs]
+    fn subtract_rhs(&self, _lhs: &KValue) -> Result<KValue> {
+        unimplemented_rhs_error("-", self.type_string())
+    }
+
+    /// The `*` multiplication operator, when the object is on the right-hand side of the operation
+    ///
+    /// See [KotoObject::add_rhs]
+    fn multiply_rhs(&self, _lhs: &KValue) -> Result<KValue> {
+        unimplemented_rhs_error("*", self.type_string())
+    }
+
+    /// The `/` division operator, when the object is on the right-hand side of the operation
+    ///
+    /// See [KotoObject::add_rhs]
+    fn divide_rhs(&self, _lhs: &KValue) -> Result<KValue> {
+        unimplemented_rhs_error("/", self.type_string())
+    }
+
+    /// The `%` remainder operator, when the object is on the right-hand side of the operation
+    ///
+    /// See [KotoObject::add_rhs]
+    fn remainder_rhs(&self, _lhs: &KValue) -> Result<KValue> {
+        unimplemented_rhs_error("%", self.type_string())
+    }
+
     /// The `+=` in-place addition operator
     fn add_assign(&mut self, _rhs: &KValue) -> Result<()> {
         unimplemented_error("@+=", self.type_string())
@@ -232,6 +301,14 @@ pub trait KotoObject: KotoType + KotoCopy + KotoEntries + KotoSend + KotoSync +
         unimplemented_error("@!=", self.type_string())
     }
 
+    /// Called when the object is used as a map key
+    ///
+    /// Objects that provide a hash can be used as map keys, with [KotoObject::equal] being used
+    /// to compare keys that have matching hashes.
+    fn hash(&self) -> Option<u64> {
+        None
+    }
+
     /// Declares to the runtime whether or not the object is iterable
     fn is_iterable(&self) -> IsIterable {
         IsIterable::NotIterable
@@ -390,6 +467,11 @@ fn unimplemented_error<T>(method: &str, object_type: KString) -> Result<T> {
     runtime_error!("{method} is unimplemented for {object_type}")
 }
 
+/// Creates an error for an unimplemented operation with the object on the right-hand side
+fn unimplemented_rhs_error<T>(op: &str, object_type: KString) -> Result<T> {
+    runtime_error!("'{op}' with {object_type} as the right-hand side is unimplemented")
+}
+
 /// An enum that indicates to the runtime if a [KotoObject] is iterable
 pub enum IsIterable {
     /// The object is not iterable
@@ -2,11 +2,14 @@ use crate::{prelude::*, Error, Ptr};
 use std::{
     cmp::Ordering,
     fmt,
-    hash::Hash,
-    ops::{Range, RangeBounds},
+    hash::{Hash, Hasher},
+    ops::{Range, RangeBounds, RangeInclusive},
 };
 
-/// The integer range type used by the Koto runtime
+/// The range type used by the Koto runtime
+///
+/// Ranges contain integers by default, with character and float ranges also available,
+/// see [KRange::with_chars] and [KRange::with_floats].
 ///
 /// See [KValue::Range]
 #[derive(Clone, Debug, Hash, PartialEq, Eq)]
@@ -29,6 +32,11 @@ enum Inner {
     },
     // Placing ranges with i64 bounds to the heap allows the size of KRange to be 16 bytes
     BoundedLarge(Ptr<Bounded64>),
+    // Ranges with a step other than 1 are also placed on the heap
+    Stepped(Ptr<SteppedRange>),
+    // Character ranges are represented as an integer range of code points
+    Chars(Ptr<KRange>),
+    Floats(Ptr<FloatRange>),
 }
 
 #[derive(Clone, Debug, Hash, PartialEq, Eq)]
@@ -44,6 +52,217 @@ impl From<Bounded64> for Inner {
     }
 }
 
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+struct SteppedRange {
+    start: Option<i64>,
+    end: Option<(i64, bool)>,
+    // Never 0 or 1, negative steps produce the range's values in reverse order
+    step: i64,
+}
+
+impl SteppedRange {
+    // Returns the first and last values produced by the range along with the delta between values
+    //
+    // None is returned if the range is empty or unbounded.
+    fn stepped_bounds(&self) -> Option<(i64, i64, i64)> {
+        let (start, (end, inclusive)) = (self.start?, self.end?);
+        let (first, last) = first_and_last(start, end, inclusive)?;
+
+        let direction = if last >= first { 1 } else { -1 };
+        let step_size = self.step.abs();
+        let steps = (last - first).abs() / step_size;
+
+        // Negative steps produce the range's values in descending order
+        let result = if self.step > 0 || last < first {
+            (
+                first,
+                first + direction * step_size * steps,
+                direction * step_size,
+            )
+        } else {
+            (
+                last,
+                last - direction * step_size * steps,
+                -direction * step_size,
+            )
+        };
+
+        Some(result)
+    }
+}
+
+impl From<SteppedRange> for Inner {
+    fn from(range: SteppedRange) -> Self {
+        Self::Stepped(range.into())
+    }
+}
+
+// Steps that land within this tolerance of a float range's end (relative to the number of steps)
+// are treated as landing on the end, e.g. `0.0..=0.3..0.1` includes `0.3` despite `0.3 / 0.1`
+// being slightly less than 3.
+const FLOAT_STEP_TOLERANCE: f64 = 1.0e-9;
+
+#[derive(Clone, Debug)]
+struct FloatRange {
+    start: f64,
+    end: f64,
+    inclusive: bool,
+    // Never 0, negative steps produce the range's values in reverse order
+    step: f64,
+    // The indices of the values that haven't yet been produced during iteration
+    remaining: Range<usize>,
+}
+
+impl FloatRange {
+    fn new(start: f64, end: f64, inclusive: bool, step: f64) -> Result<Self, Error> {
+        if !(start.is_finite() && end.is_finite()) {
+            return runtime_error!("A float range's bounds must be finite");
+        }
+        if step == 0.0 || !step.is_finite() {
+            return runtime_error!("A float range's step must be finite and non-zero");
+        }
+
+        let mut result = Self {
+            start,
+            end,
+            inclusive,
+            step,
+            remaining: 0..0,
+        };
+        result.remaining = 0..result.value_count().0;
+        Ok(result)
+    }
+
+    // Returns the number of values in the range,
+    // along with a flag that's true when the final value is equal to the range's end.
+    fn value_count(&self) -> (usize, bool) {
+        let steps = (self.end - self.start).abs() / self.step.abs();
+        let nearest = steps.round();
+        let lands_on_end = (steps - nearest).abs() <= nearest.max(1.0) * FLOAT_STEP_TOLERANCE;
+
+        let whole_steps = if lands_on_end { nearest } else { steps.floor() } as usize;
+        if lands_on_end && !self.inclusive {
+            (whole_steps, false)
+        } else {
+            (whole_steps.saturating_add(1), lands_on_end)
+        }
+    }
+
+    // Returns the value at the given index, in the order in which values are produced
+    //
+    // Values are calculated from the range's start to avoid the accumulation of errors.
+    fn value(&self, index: usize) -> f64 {
+        let (count, ends_on_end) = self.value_count();
+        // Negative steps produce the range's values in descending order
+        let index = if self.step > 0.0 || self.end < self.start {
+            index
+        } else {
+            count - 1 - index
+        };
+
+        if ends_on_end && index == count - 1 {
+            self.end
+        } else if self.end >= self.start {
+            self.start + self.step.abs() * index as f64
+        } else {
+            self.start - self.step.abs() * index as f64
+        }
+    }
+
+    fn contains(&self, n: f64) -> bool {
+        match (self.inclusive, self.start <= self.end) {
+            (true, true) => self.start <= n && n <= self.end,
+            (true, false) => self.end <= n && n <= self.start,
+            (false, true) => self.start <= n && n < self.end,
+            (false, false) => self.end < n && n <= self.start,
+        }
+    }
+
+    fn sorted_bounds(&self) -> (f64, f64) {
+        (self.start.min(self.end), self.start.max(self.end))
+    }
+}
+
+// The range's values are compared and hashed using the bits of its floats,
+// allowing float ranges to be used as map keys.
+impl PartialEq for FloatRange {
+    fn eq(&self, other: &Self) -> bool {
+        self.start.to_bits() == other.start.to_bits()
+            && self.end.to_bits() == other.end.to_bits()
+            && self.inclusive == other.inclusive
+            && self.step.to_bits() == other.step.to_bits()
+            && self.remaining == other.remaining
+    }
+}
+
+impl Eq for FloatRange {}
+
+impl Hash for FloatRange {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.start.to_bits().hash(state);
+        self.end.to_bits().hash(state);
+        self.inclusive.hash(state);
+        self.step.to_bits().hash(state);
+        self.remaining.hash(state);
+    }
+}
+
+impl From<FloatRange> for Inner {
+    fn from(range: FloatRange) -> Self {
+        Self::Floats(range.into())
+    }
+}
+
+// Surrogate code points aren't valid Unicode scalar values, and are skipped in character ranges
+const SURROGATES: RangeInclusive<i64> = 0xD800..=0xDFFF;
+
+fn code_to_char(code: i64) -> Option<char> {
+    u32::try_from(code).ok().and_then(char::from_u32)
+}
+
+// Returns how many of the values in an arithmetic progression are within the given interval
+fn count_in_interval(first: i64, last: i64, delta: i64, interval: &RangeInclusive<i64>) -> i64 {
+    // Values are counted in ascending order
+    let (first, last, delta) = if delta > 0 {
+        (first, last, delta)
+    } else {
+        (last, first, -delta)
+    };
+
+    let steps = (last - first) / delta;
+    let first_step = (interval.start() - first + delta - 1)
+        .div_euclid(delta)
+        .max(0);
+    let last_step = (interval.end() - first).div_euclid(delta).min(steps);
+
+    (last_step - first_step + 1).max(0)
+}
+
+// Returns the first and last values contained in a bounded range, or None if the range is empty
+fn first_and_last(start: i64, end: i64, inclusive: bool) -> Option<(i64, i64)> {
+    match start.cmp(&end) {
+        Ordering::Less if inclusive => Some((start, end)),
+        Ordering::Less => Some((start, end - 1)),
+        Ordering::Greater if inclusive => Some((start, end)),
+        Ordering::Greater => Some((start, end + 1)),
+        Ordering::Equal if inclusive => Some((start, start)),
+        Ordering::Equal => None,
+    }
+}
+
+// Returns the last value contained in a bounded range,
+// along with the end of the range that remains when the last value is removed.
+fn split_last(start: i64, end: i64, inclusive: bool) -> Option<(i64, i64, bool)> {
+    let (first, last) = first_and_last(start, end, inclusive)?;
+    let result = match last.cmp(&first) {
+        Ordering::Greater => (last, last - 1, true),
+        Ordering::Less => (last, last + 1, true),
+        // Leave an empty range when the last value has been removed
+        Ordering::Equal => (last, first, false),
+    };
+    Some(result)
+}
+
 impl KRange {
     /// Initializes a range with the given start and end bounds
     ///
@@ -77,36 +296,192 @@ impl KRange {
         }
     }
 
-    /// Returns the start of the range
+    /// Initializes a range of characters
+    ///
+    /// The range produces the Unicode scalar values between `start` and `end`.
+    pub fn with_chars(start: char, end: char, inclusive: bool) -> Self {
+        let codes = Self::new(Some(start as i64), Some((end as i64, inclusive)));
+        Self(Inner::Chars(codes.into()))
+    }
+
+    /// Initializes a range of floats with the given step between its values
+    ///
+    /// The range's values are calculated by adding multiples of the step to the range's start,
+    /// with the final value being equal to `end` if the last step lands on it (within a small
+    /// tolerance that allows for rounding errors). Exclusive ranges don't include `end`.
+    ///
+    /// A negative step causes the range's values to be produced in descending order.
+    ///
+    /// An error is returned if the bounds or the step aren't finite, or if the step is zero.
+    pub fn with_floats(start: f64, end: f64, inclusive: bool, step: f64) -> Result<Self, Error> {
+        FloatRange::new(start, end, inclusive, step).map(|range| Self(range.into()))
+    }
+
+    /// Returns a copy of the range with the given step between its values
+    ///
+    /// A negative step causes the range's values to be produced in descending order,
+    /// e.g. `0..10..-3` produces `9, 6, 3, 0`, and `10..0..-3` produces `10, 7, 4, 1`.
+    ///
+    /// Any existing step in the range is replaced. An error is returned if the step is zero.
+    pub fn with_step(&self, step: i64) -> Result<Self, Error> {
+        match &self.0 {
+            Inner::Chars(codes) => return Ok(Self(Inner::Chars(codes.with_step(step)?.into()))),
+            Inner::Floats(r) => return Self::with_floats(r.start, r.end, r.inclusive, step as f64),
+            _ => {}
+        }
+
+        match step {
+            0 => runtime_error!("A range's step can't be zero"),
+            1 => Ok(Self::new(self.start(), self.end())),
+            _ => Ok(Self(
+                SteppedRange {
+                    start: self.start(),
+                    end: self.end(),
+                    step,
+                }
+                .into(),
+            )),
+        }
+    }
+
+    /// Returns a copy of the range as a float range with the given step between its values
+    ///
+    /// The range must either be a bounded integer range, or a float range.
+    ///
+    /// See [KRange::with_floats].
+    pub fn with_float_step(&self, step: f64) -> Result<Self, Error> {
+        match (&self.0, self.start(), self.end()) {
+            (Inner::Floats(r), _, _) => Self::with_floats(r.start, r.end, r.inclusive, step),
+            (_, Some(start), Some((end, inclusive))) if self.is_integer() => {
+                Self::with_floats(start as f64, end as f64, inclusive, step)
+            }
+            _ => runtime_error!("A float step can't be used with '{self}'"),
+        }
+    }
+
+    // Returns the range's bounds and step as a float range,
+    // allowing integer ranges to be compared with float ranges.
+    //
+    // Missing bounds are replaced with infinities, so the result is only suitable for comparisons.
+    // None is returned for character ranges.
+    fn to_float_range(&self) -> Option<FloatRange> {
+        match &self.0 {
+            Inner::Chars(_) => None,
+            Inner::Floats(r) => Some((**r).clone()),
+            _ => {
+                let start = self.start().map_or(f64::NEG_INFINITY, |start| start as f64);
+                let (end, inclusive) = self
+                    .end()
+                    .map_or((f64::INFINITY, false), |(end, inclusive)| {
+                        (end as f64, inclusive)
+                    });
+                Some(FloatRange {
+                    start,
+                    end,
+                    inclusive,
+                    step: self.step().into(),
+                    remaining: 0..0,
+                })
+            }
+        }
+    }
+
+    /// Returns true if the range contains integers
+    pub fn is_integer(&self) -> bool {
+        !matches!(self.0, Inner::Chars(_) | Inner::Floats(_))
+    }
+
+    /// Returns the start and end of a character range, or None if the range isn't a character range
+    ///
+    /// The return value includes a flag stating whether or not the range end is inclusive or not.
+    pub fn char_bounds(&self) -> Option<(char, char, bool)> {
+        match &self.0 {
+            Inner::Chars(codes) => {
+                let (start, (end, inclusive)) = (codes.start()?, codes.end()?);
+                let to_char = |code| code_to_char(code).unwrap_or(char::REPLACEMENT_CHARACTER);
+                Some((to_char(start), to_char(end), inclusive))
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the start and end of a float range, or None if the range isn't a float range
+    ///
+    /// The return value includes a flag stating whether or not the range end is inclusive or not.
+    pub fn float_bounds(&self) -> Option<(f64, f64, bool)> {
+        match &self.0 {
+            Inner::Floats(r) => Some((r.start, r.end, r.inclusive)),
+            _ => None,
+        }
+    }
+
+    /// Returns the start of an integer range
+    ///
+    /// None is returned for character and float ranges, see [KRange::char_bounds] and
+    /// [KRange::float_bounds].
     pub fn start(&self) -> Option<i64> {
         use Inner::*;
         match &self.0 {
             From { start } => Some(*start),
             Bounded { start, .. } => Some(*start as i64),
             BoundedLarge(r) => Some(r.start),
+            Stepped(r) => r.start,
             _ => None,
         }
     }
 
-    /// Returns the end of the range
+    /// Returns the end of an integer range
     ///
     /// The return value includes flag stating whether or not the range end is inclusive or not.
+    ///
+    /// None is returned for character and float ranges, see [KRange::char_bounds] and
+    /// [KRange::float_bounds].
     pub fn end(&self) -> Option<(i64, bool)> {
         use Inner::*;
         match &self.0 {
             To { end, inclusive } => Some((*end, *inclusive)),
             Bounded { end, inclusive, .. } => Some((*end as i64, *inclusive)),
             BoundedLarge(r) => Some((r.end, r.inclusive)),
+            Stepped(r) => r.end,
             _ => None,
         }
     }
 
+    /// Returns the step between the range's values
+    ///
+    /// Ranges without an explicit step have a step of 1.
+    pub fn step(&self) -> KNumber {
+        match &self.0 {
+            Inner::Stepped(r) => r.step.into(),
+            Inner::Chars(codes) => codes.step(),
+            Inner::Floats(r) => r.step.into(),
+            _ => 1.into(),
+        }
+    }
+
+    // Returns the first and last values produced by a bounded integer range,
+    // along with the delta between values.
+    //
+    // None is returned if the range is empty or unbounded.
+    fn progression(&self) -> Option<(i64, i64, i64)> {
+        match &self.0 {
+            Inner::Stepped(r) => r.stepped_bounds(),
+            _ => {
+                let (start, (end, inclusive)) = (self.start()?, self.end()?);
+                let (first, last) = first_and_last(start, end, inclusive)?;
+                Some((first, last, if last >= first { 1 } else { -1 }))
+            }
+        }
+    }
+
     /// Returns a sorted translation of the range with missing boundaries replaced by min/max values
     ///
     /// No clamping of the range boundaries is performed (as in [KRange::indices]),
     /// so negative indices will be preserved.
+    ///
+    /// Character ranges are translated into their code points,
+    /// and float ranges have their bounds truncated to integers.
     pub fn as_sorted_range(&self) -> Range<i64> {
-        use std::i64::{MAX, MIN};
         use Inner::*;
 
         let sort_bounded = |start, end, inclusive| {
@@ -119,15 +494,25 @@ impl KRange {
 
         let (start, end) = {
             match &self.0 {
-                From { start } => (*start, MAX),
-                To { end, inclusive } => (MIN, if *inclusive { *end + 1 } else { *end }),
+                From { start } => (*start, i64::MAX),
+                To { end, inclusive } => (i64::MIN, if *inclusive { *end + 1 } else { *end }),
                 Bounded {
                     start,
                     end,
                     inclusive,
                 } => sort_bounded(*start as i64, *end as i64, *inclusive),
                 BoundedLarge(r) => sort_bounded(r.start, r.end, r.inclusive),
-                Unbounded => (MIN, MAX),
+                Stepped(r) => match (r.start, r.end) {
+                    (Some(start), Some((end, inclusive))) => sort_bounded(start, end, inclusive),
+                    (Some(start), None) => (start, i64::MAX),
+                    (None, Some((end, inclusive))) => {
+                        (i64::MIN, if inclusive { end + 1 } else { end })
+                    }
+                    (None, None) => (i64::MIN, i64::MAX),
+                },
+                Chars(codes) => return codes.as_sorted_range(),
+                Floats(r) => sort_bounded(r.start as i64, r.end as i64, r.inclusive),
+                Unbounded => (i64::MIN, i64::MAX),
             }
         };
 
@@ -135,28 +520,190 @@ impl KRange {
     }
 
     /// Returns true if the provided number is within the range
+    ///
+    /// For stepped integer ranges, the number must also be one of the values produced by the range.
+    ///
+    /// Float ranges check that the number is within the range's bounds, without taking the
+    /// range's step into account. Float steps are generally inexact (e.g. `0.1` can't be
+    /// represented exactly), so checking for a step match would give surprising results.
+    /// Character ranges don't contain numbers.
     pub fn contains(&self, n: KNumber) -> bool {
+        match &self.0 {
+            Inner::Chars(_) => return false,
+            Inner::Floats(r) => return r.contains(n.into()),
+            _ => {}
+        }
+
         let n: i64 = if n < 0.0 { n.floor() } else { n.ceil() }.into();
-        self.as_sorted_range().contains(&n)
+
+        match &self.0 {
+            Inner::Stepped(r) => match r.stepped_bounds() {
+                Some((first, last, delta)) => {
+                    first.min(last) <= n && n <= first.max(last) && (n - first) % delta == 0
+                }
+                None => {
+                    // The range is either empty or unbounded,
+                    // the step is checked when a start value is available.
+                    self.as_sorted_range().contains(&n)
+                        && match (r.start, r.end) {
+                            (Some(start), None) if r.step > 0 => (n - start) % r.step == 0,
+                            (Some(_), Some(_)) => false,
+                            _ => true,
+                        }
+                }
+            },
+            _ => self.as_sorted_range().contains(&n),
+        }
+    }
+
+    /// Returns true if the provided character is one of the values produced by a character range
+    pub fn contains_char(&self, c: char) -> bool {
+        match &self.0 {
+            Inner::Chars(codes) => codes.contains((c as i64).into()),
+            _ => false,
+        }
+    }
+
+    /// Returns true if the other range is entirely contained within the range
+    ///
+    /// Integer and float ranges are compared using their bounds, so an integer range can contain
+    /// a float range and vice versa. Character ranges only contain other character ranges.
+    pub fn contains_range(&self, other: &KRange) -> bool {
+        match (&self.0, &other.0) {
+            (Inner::Chars(_), Inner::Chars(_)) => {}
+            (Inner::Chars(_), _) | (_, Inner::Chars(_)) => return false,
+            (Inner::Floats(_), _) | (_, Inner::Floats(_)) => {
+                let (Some(a), Some(b)) = (self.to_float_range(), other.to_float_range()) else {
+                    return false;
+                };
+                let (a_min, a_max) = a.sorted_bounds();
+                let end_contained = if b.inclusive {
+                    a.contains(b.end)
+                } else {
+                    a_min <= b.end && b.end <= a_max
+                };
+                return a.contains(b.start) && end_contained;
+            }
+            _ => {}
+        }
+
+        let a = self.as_sorted_range();
+        let b = other.as_sorted_range();
+        b.start >= a.start && b.end <= a.end
+    }
+
+    /// Returns the range as an integer range that can be used to access a container's elements
+    ///
+    /// Float ranges with a whole number step have their bounds truncated to integers,
+    /// e.g. `1.0..5.5` is treated as `1..5`.
+    ///
+    /// None is returned for character ranges and float ranges with a fractional step.
+    pub fn to_index_range(&self) -> Option<Self> {
+        match &self.0 {
+            Inner::Chars(_) => None,
+            Inner::Floats(r) if r.step.fract() == 0.0 => {
+                Self::new(Some(r.start as i64), Some((r.end as i64, r.inclusive)))
+                    .with_step(r.step as i64)
+                    .ok()
+            }
+            Inner::Floats(_) => None,
+            _ => Some(self.clone()),
+        }
     }
 
     /// Returns the range translated into non-negative indices, suitable for container access
     ///
+    /// Negative start or end values are treated as counting back from `max_index`,
+    /// e.g. `-2..` with a `max_index` of 10 will produce `8..10`.
+    ///
     /// The start index will be clamped to the range `0..=max_index`.
     /// The end index will be clamped to the range `start..=max_index`
     ///
     /// If the start value is `None` then the resulting start index will be `0`.
     /// If the end value is `None` then the resulting end index will be `max_index`.
+    ///
+    /// The range's step is ignored, see [KRange::stepped_indices].
     pub fn indices(&self, max_index: usize) -> Range<usize> {
         let max_index = max_index as i64;
-        let range = self.as_sorted_range();
+        let from_end = |i: i64| if i < 0 { i + max_index } else { i };
+        let range = Self::new(
+            self.start().map(from_end),
+            self.end()
+                .map(|(end, inclusive)| (from_end(end), inclusive)),
+        )
+        .as_sorted_range();
         let start = range.start.clamp(0, max_index);
         let end = range.end.clamp(start, max_index);
         (start as usize)..(end as usize)
     }
 
+    /// Returns the range's indices for container access, taking the range's step into account
+    ///
+    /// See [KRange::indices].
+    pub fn stepped_indices(&self, max_index: usize) -> impl Iterator<Item = usize> {
+        let indices = self.indices(max_index);
+        let step = self.step().as_i64();
+        let step_size = step.unsigned_abs() as usize;
+
+        // Negative steps take indices from the end of the range
+        let (forward, backward) = if step > 0 {
+            (Some(indices.step_by(step_size)), None)
+        } else {
+            (None, Some(indices.rev().step_by(step_size)))
+        };
+
+        forward
+            .into_iter()
+            .flatten()
+            .chain(backward.into_iter().flatten())
+    }
+
     /// Returns the intersection of two ranges
+    ///
+    /// None is returned if the ranges don't overlap, or if a character range is intersected with a
+    /// range of another kind.
+    ///
+    /// The intersection of two character ranges is an inclusive ascending range.
+    /// If either range is a float range then the intersection is calculated using the ranges'
+    /// bounds, producing an ascending float range with the first range's step.
     pub fn intersection(&self, other: &KRange) -> Option<Self> {
+        match (&self.0, &other.0) {
+            (Inner::Chars(a), Inner::Chars(b)) => {
+                let (a, b) = (a.as_sorted_range(), b.as_sorted_range());
+                let mut first = a.start.max(b.start);
+                let mut last = a.end.min(b.end) - 1;
+                if SURROGATES.contains(&first) {
+                    first = SURROGATES.end() + 1;
+                }
+                if SURROGATES.contains(&last) {
+                    last = SURROGATES.start() - 1;
+                }
+                return if first <= last {
+                    Some(Self::with_chars(
+                        code_to_char(first)?,
+                        code_to_char(last)?,
+                        true,
+                    ))
+                } else {
+                    None
+                };
+            }
+            (Inner::Chars(_), _) | (_, Inner::Chars(_)) => return None,
+            (Inner::Floats(_), _) | (_, Inner::Floats(_)) => {
+                let (a, b) = (self.to_float_range()?, other.to_float_range()?);
+                let (a_min, a_max) = a.sorted_bounds();
+                let (b_min, b_max) = b.sorted_bounds();
+                let (start, end) = (a_min.max(b_min), a_max.min(b_max));
+                let inclusive = a.contains(end) && b.contains(end);
+                return if start < end || (start == end && inclusive) {
+                    Self::with_floats(start, end, inclusive, a.step.abs()).ok()
+                } else {
+                    None
+                };
+            }
+            _ => {}
+        }
+
         let this = self.as_sorted_range();
         // let mut result = Self::with_bounds(start, end, inclusive);
         let other = other.as_sorted_range();
@@ -177,6 +724,13 @@ impl KRange {
             To { end, .. } => *end > 0,
             Bounded { start, end, .. } => *start <= *end,
             BoundedLarge(r) => r.start <= r.end,
+            Stepped(r) => match (r.start, r.end) {
+                (Some(start), Some((end, _))) => start <= end,
+                (None, Some((end, _))) => end > 0,
+                _ => true,
+            },
+            Chars(codes) => codes.is_ascending(),
+            Floats(r) => r.start <= r.end,
             _ => true,
         }
     }
@@ -185,6 +739,27 @@ impl KRange {
     ///
     /// Descending ranges have a non-negative size, i.e. the size is equal to `start - end`.
     pub fn size(&self) -> Option<usize> {
+        match &self.0 {
+            Inner::Chars(codes) => {
+                // Surrogate code points are skipped when iterating over character ranges
+                let surrogates = codes.progression().map_or(0, |(first, last, delta)| {
+                    count_in_interval(first, last, delta, &SURROGATES)
+                });
+                return codes.size().map(|size| size - surrogates as usize);
+            }
+            Inner::Floats(r) => return Some(r.remaining.len()),
+            _ => {}
+        }
+
+        if let Inner::Stepped(r) = &self.0 {
+            return match (r.start, r.end) {
+                (Some(_), Some(_)) => Some(r.stepped_bounds().map_or(0, |(first, last, delta)| {
+                    ((last - first) / delta) as usize + 1
+                })),
+                _ => None,
+            };
+        }
+
         if self.is_bounded() {
             let range = self.as_sorted_range();
             Some((range.end - range.start) as usize)
@@ -196,7 +771,11 @@ impl KRange {
     /// Returns true if the range has defined start and end boundaries
     pub fn is_bounded(&self) -> bool {
         use Inner::*;
-        matches!(self.0, Bounded { .. } | BoundedLarge { .. })
+        match &self.0 {
+            Bounded { .. } | BoundedLarge { .. } | Chars(_) | Floats(_) => true,
+            Stepped(r) => r.start.is_some() && r.end.is_some(),
+            _ => false,
+        }
     }
 
     /// Removes and returns the first element in the range.
@@ -204,10 +783,16 @@ impl KRange {
     /// This is used by RangeIterator and in the VM to iterate over temporary ranges.
     ///
     /// Returns an error if the range is not bounded.
-    pub fn pop_front(&mut self) -> Result<Option<i64>, Error> {
+    pub fn pop_front(&mut self) -> Result<Option<KValue>, Error> {
         use Inner::*;
         use Ordering::*;
 
+        match &mut self.0 {
+            Stepped(_) => return self.pop_stepped(true).map(|n| n.map(KValue::from)),
+            Chars(_) | Floats(_) => return self.pop_non_integer(true),
+            _ => {}
+        }
+
         let result = match &mut self.0 {
             Bounded {
                 start,
@@ -261,72 +846,127 @@ impl KRange {
             _ => return runtime_error!("KRange::pop_front can only be used with bounded ranges"),
         };
 
-        Ok(result)
+        Ok(result.map(KValue::from))
     }
 
-    /// Removes and returns the first element in the range.
+    /// Removes and returns the last element in the range.
     ///
-    /// This is used by RangeIterator and in the VM to iterate over temporary ranges.
+    /// This is used by RangeIterator to iterate over ranges in reverse order.
     ///
     /// Returns an error if the range is not bounded.
-    pub fn pop_back(&mut self) -> Result<Option<i64>, Error> {
+    pub fn pop_back(&mut self) -> Result<Option<KValue>, Error> {
         use Inner::*;
-        use Ordering::*;
+
+        match &mut self.0 {
+            Stepped(_) => return self.pop_stepped(false).map(|n| n.map(KValue::from)),
+            Chars(_) | Floats(_) => return self.pop_non_integer(false),
+            _ => {}
+        }
 
         let result = match &mut self.0 {
             Bounded {
                 start,
                 end,
                 inclusive,
-            } => match start.cmp(&end) {
-                Less => {
-                    let result = *end as i64;
-                    *end -= 1;
-                    Some(result)
-                }
-                Greater => {
-                    let result = *start as i64;
-                    *start -= 1;
-                    Some(result)
-                }
-                Equal => {
-                    if *inclusive {
-                        let result = *start as i64;
-                        *inclusive = false; // Allow iteration to stop
-                        Some(result)
-                    } else {
-                        None
-                    }
-                }
-            },
+            } => split_last(*start as i64, *end as i64, *inclusive).map(
+                |(last, remaining_end, remaining_inclusive)| {
+                    // The remaining end is within the range's original bounds, so fits in an i32
+                    *end = remaining_end as i32;
+                    *inclusive = remaining_inclusive;
+                    last
+                },
+            ),
             BoundedLarge(r) => {
                 let r = Ptr::make_mut(r);
-                match r.start.cmp(&r.end) {
-                    Less => {
-                        let result = r.end;
-                        r.end += 1;
-                        Some(result)
-                    }
-                    Greater => {
-                        let result = r.start;
-                        r.start -= 1;
-                        Some(result)
-                    }
-                    Equal => {
-                        if r.inclusive {
-                            let result = r.start;
-                            r.inclusive = false; // Allow iteration to stop
-                            Some(result)
-                        } else {
-                            None
-                        }
-                    }
-                }
+                split_last(r.start, r.end, r.inclusive).map(
+                    |(last, remaining_end, remaining_inclusive)| {
+                        r.end = remaining_end;
+                        r.inclusive = remaining_inclusive;
+                        last
+                    },
+                )
             }
             _ => return runtime_error!("KRange::pop_back can only be used with bounded ranges"),
         };
 
-        Ok(result)
+        Ok(result.map(KValue::from))
+    }
+
+    // Pops a value from the front or back of a stepped range
+    //
+    // The remaining values are represented as an inclusive range with a positive step,
+    // with the direction of iteration determined by the range's bounds.
+    fn pop_stepped(&mut self, front: bool) -> Result<Option<i64>, Error> {
+        let Inner::Stepped(r) = &self.0 else {
+            unreachable!();
+        };
+
+        if !self.is_bounded() {
+            return runtime_error!("Stepped ranges can only be iterated when they're bounded");
+        }
+
+        let Some((first, last, delta)) = r.stepped_bounds() else {
+            return Ok(None);
+        };
+
+        let (result, remaining_start, remaining_end) = if front {
+            (first, first + delta, last)
+        } else {
+            (last, first, last - delta)
+        };
+
+        let remaining = if first == last {
+            // The last value has been popped, so leave an empty range
+            SteppedRange {
+                start: Some(first),
+                end: Some((first, false)),
+                step: delta.abs(),
+            }
+        } else {
+            SteppedRange {
+                start: Some(remaining_start),
+                end: Some((remaining_end, true)),
+                step: delta.abs(),
+            }
+        };
+
+        self.0 = remaining.into();
+        Ok(Some(result))
+    }
+
+    // Pops a value from the front or back of a character or float range
+    fn pop_non_integer(&mut self, front: bool) -> Result<Option<KValue>, Error> {
+        match &mut self.0 {
+            Inner::Chars(codes) => {
+                let codes = Ptr::make_mut(codes);
+                loop {
+                    let code = if front {
+                        codes.pop_front()?
+                    } else {
+                        codes.pop_back()?
+                    };
+                    match code {
+                        // Surrogate code points are skipped
+                        Some(KValue::Number(code)) => {
+                            if let Some(c) = code_to_char(code.into()) {
+                                return Ok(Some(c.to_string().into()));
+                            }
+                        }
+                        _ => return Ok(None),
+                    }
+                }
+            }
+            Inner::Floats(r) => {
+                let r = Ptr::make_mut(r);
+                let index = if front {
+                    r.remaining.next()
+                } else {
+                    r.remaining.next_back()
+                };
+                Ok(index.map(|i| r.value(i).into()))
+            }
+            _ => unreachable!(),
+        }
     }
 }
 
@@ -353,17 +993,35 @@ where
 
 impl fmt::Display for KRange {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        if let Some(start) = self.start() {
-            write!(f, "{start}")?;
-        }
+        let write_end_prefix = |f: &mut fmt::Formatter<'_>, inclusive| {
+            f.write_str(if inclusive { "..=" } else { ".." })
+        };
 
-        f.write_str("..")?;
+        if let Some((start, end, inclusive)) = self.char_bounds() {
+            write!(f, "'{start}'")?;
+            write_end_prefix(f, inclusive)?;
+            write!(f, "'{end}'")?;
+        } else if let Some((start, end, inclusive)) = self.float_bounds() {
+            write!(f, "{}", KNumber::from(start))?;
+            write_end_prefix(f, inclusive)?;
+            write!(f, "{}", KNumber::from(end))?;
+        } else {
+            if let Some(start) = self.start() {
+                write!(f, "{start}")?;
+            }
 
-        if let Some((end, inclusive)) = self.end() {
-            if inclusive {
-                f.write_str("=")?;
+            match self.end() {
+                Some((end, inclusive)) => {
+                    write_end_prefix(f, inclusive)?;
+                    write!(f, "{end}")?;
+                }
+                None => f.write_str("..")?,
             }
-            write!(f, "{end}")?;
+        }
+
+        let step = self.step();
+        if step != 1 {
+            write!(f, "..{step}")?;
         }
 
         Ok(())
@@ -417,6 +1075,134 @@ mod tests {
         assert!(KRange::from(20..).is_ascending());
     }
 
+    #[test]
+    fn stepped() {
+        let collect = |mut range: KRange| {
+            let mut result = Vec::new();
+            while let Some(KValue::Number(n)) = range.pop_front().unwrap() {
+                result.push(i64::from(n));
+            }
+            result
+        };
+
+        let r = KRange::from(0..10).with_step(3).unwrap();
+        assert_eq!(r.size(), Some(4));
+        assert_eq!(collect(r.clone()), [0, 3, 6, 9]);
+        assert!(r.contains(6.into()));
+        assert!(!r.contains(7.into()));
+
+        let r = KRange::from(0..10).with_step(-3).unwrap();
+        assert_eq!(collect(r.clone()), [9, 6, 3, 0]);
+        let mut back = r.clone();
+        let mut from_back = Vec::new();
+        while let Some(KValue::Number(n)) = back.pop_back().unwrap() {
+            from_back.push(i64::from(n));
+        }
+        assert_eq!(from_back, [0, 3, 6, 9]);
+
+        assert_eq!(collect(KRange::from(0..0).with_step(2).unwrap()), []);
+        assert!(KRange::from(0..10).with_step(0).is_err());
+        assert_eq!(
+            KRange::from(0..10).with_step(1).unwrap(),
+            KRange::from(0..10)
+        );
+    }
+
+    #[test]
+    fn pop_back() {
+        let collect_back = |mut range: KRange| {
+            let mut result = Vec::new();
+            while let Some(KValue::Number(n)) = range.pop_back().unwrap() {
+                result.push(i64::from(n));
+            }
+            result
+        };
+
+        assert_eq!(collect_back(KRange::from(0..3)), [2, 1, 0]);
+        assert_eq!(collect_back(KRange::from(0..=3)), [3, 2, 1, 0]);
+        assert_eq!(
+            collect_back(KRange::new(Some(3), Some((0, false)))),
+            [1, 2, 3]
+        );
+        assert_eq!(collect_back(KRange::from(0..0)), []);
+        let big = 2_i64.pow(42);
+        assert_eq!(collect_back(KRange::from(big..big + 2)), [big + 1, big]);
+    }
+
+    #[test]
+    fn chars() {
+        let collect = |mut range: KRange| {
+            let mut result = String::new();
+            while let Some(KValue::Str(c)) = range.pop_front().unwrap() {
+                result.push_str(&c);
+            }
+            result
+        };
+
+        let r = KRange::with_chars('a', 'e', true);
+        assert_eq!(r.size(), Some(5));
+        assert_eq!(collect(r.clone()), "abcde");
+        assert_eq!(collect(r.with_step(-2).unwrap()), "eca");
+        assert_eq!(collect(KRange::with_chars('c', 'a', false)), "cb");
+        assert!(r.contains_char('c'));
+        assert!(!r.contains_char('f'));
+        assert!(!r.contains(99.into()));
+
+        // Surrogate code points are skipped
+        let r = KRange::with_chars('\u{D7FE}', '\u{E001}', true);
+        assert_eq!(r.size(), Some(4));
+        assert_eq!(collect(r), "\u{D7FE}\u{D7FF}\u{E000}\u{E001}");
+    }
+
+    #[test]
+    fn floats() {
+        let collect = |mut range: KRange| {
+            let mut result = Vec::new();
+            while let Some(KValue::Number(n)) = range.pop_front().unwrap() {
+                result.push(f64::from(n));
+            }
+            result
+        };
+
+        let r = KRange::with_floats(0.0, 1.0, false, 0.25).unwrap();
+        assert_eq!(r.size(), Some(4));
+        assert_eq!(collect(r.clone()), [0.0, 0.25, 0.5, 0.75]);
+        assert!(r.contains(0.9.into()));
+        assert!(!r.contains(1.0.into()));
+
+        let r = KRange::with_floats(0.0, 0.3, true, 0.1).unwrap();
+        assert_eq!(collect(r), [0.0, 0.1, 0.2, 0.3]);
+
+        let r = KRange::with_floats(1.0, 0.0, true, -0.5).unwrap();
+        assert_eq!(collect(r), [1.0, 0.5, 0.0]);
+
+        let r = KRange::with_floats(0.0, 1.0, true, -0.5).unwrap();
+        assert_eq!(collect(r), [1.0, 0.5, 0.0]);
+
+        assert!(KRange::with_floats(0.0, 1.0, false, 0.0).is_err());
+        assert!(KRange::with_floats(0.0, f64::INFINITY, false, 1.0).is_err());
+    }
+
+    #[test]
+    fn stepped_indices() {
+        let indices =
+            |range: KRange, max_index| range.stepped_indices(max_index).collect::<Vec<_>>();
+
+        assert_eq!(
+            indices(KRange::from(..).with_step(2).unwrap(), 5),
+            [0, 2, 4]
+        );
+        assert_eq!(
+            indices(KRange::from(..).with_step(-2).unwrap(), 5),
+            [4, 2, 0]
+        );
+        assert_eq!(
+            indices(KRange::from(-3..).with_step(-1).unwrap(), 5),
+            [4, 3, 2]
+        );
+        assert_eq!(indices(KRange::from(1..-1), 5), [1, 2, 3]);
+    }
+
     #[test]
     fn bounded_large() {
         let start_big = 2_i64.pow(42);
@@ -0,0 +1,131 @@
+use crate::{prelude::*, Borrow, BorrowMut, PtrMut, Result};
+use indexmap::IndexSet;
+use std::{
+    hash::BuildHasherDefault,
+    ops::{Deref, DerefMut},
+};
+
+type ValueSetType = IndexSet<ValueKey, BuildHasherDefault<KotoHasher>>;
+
+/// The set of [ValueKeys](crate::ValueKey) used by [KSet]
+///
+/// Values are kept in insertion order.
+#[derive(Clone, Default)]
+pub struct ValueSet(ValueSetType);
+
+impl ValueSet {
+    /// Creates a new ValueSet with the given capacity
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self(ValueSetType::with_capacity_and_hasher(
+            capacity,
+            Default::default(),
+        ))
+    }
+}
+
+impl Deref for ValueSet {
+    type Target = ValueSetType;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for ValueSet {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl FromIterator<ValueKey> for ValueSet {
+    fn from_iter<T: IntoIterator<Item = ValueKey>>(iter: T) -> ValueSet {
+        Self(ValueSetType::from_iter(iter))
+    }
+}
+
+/// The Koto runtime's Set type
+///
+/// Only hashable values can be stored in a set, see [KValue::is_hashable].
+#[derive(Clone, Default)]
+pub struct KSet(PtrMut<ValueSet>);
+
+impl KSet {
+    /// Creates an empty KSet
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates an empty set with the given capacity
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self(ValueSet::with_capacity(capacity).into())
+    }
+
+    /// Creates a set containing the provided data
+    pub fn with_data(data: ValueSet) -> Self {
+        Self(data.into())
+    }
+
+    /// Returns the number of values contained in the set
+    pub fn len(&self) -> usize {
+        self.data().len()
+    }
+
+    /// Returns true if the set is empty
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns a reference to the set's values
+    pub fn data(&self) -> Borrow<'_, ValueSet> {
+        self.0.borrow()
+    }
+
+    /// Returns a mutable reference to the set's values
+    pub fn data_mut(&self) -> BorrowMut<'_, ValueSet> {
+        self.0.borrow_mut()
+    }
+
+    /// Returns true if the set contains the given key
+    pub fn contains(&self, key: &ValueKey) -> bool {
+        self.data().contains(key)
+    }
+
+    /// Inserts a key into the set, returning true if the key wasn't already present
+    pub fn insert(&self, key: ValueKey) -> bool {
+        self.data_mut().insert(key)
+    }
+
+    /// Returns true if the two sets contain the same values, independent of order
+    pub fn is_equal(&self, other: &KSet) -> bool {
+        if PtrMut::ptr_eq(&self.0, &other.0) {
+            return true;
+        }
+        self.data().0 == other.data().0
+    }
+
+    /// Renders the set to the provided display context
+    ///
+    /// Sets are rendered with a `set` prefix to distinguish them from maps, e.g. `set{1, 2}`.
+    pub fn display(&self, ctx: &mut DisplayContext) -> Result<()> {
+        ctx.append("set{");
+
+        let id = PtrMut::address(&self.0);
+        if ctx.is_in_parents(id) {
+            ctx.append("...");
+        } else {
+            ctx.push_container(id);
+
+            for (i, key) in self.data().iter().enumerate() {
+                if i > 0 {
+                    ctx.append(", ");
+                }
+                key.value().display(ctx)?;
+            }
+
+            ctx.pop_container();
+        }
+
+        ctx.append('}');
+        Ok(())
+    }
+}
@@ -28,6 +28,9 @@ pub enum KValue {
     /// The hash map type used in Koto
     Map(KMap),
 
+    /// The set type used in Koto
+    Set(KSet),
+
     /// The string type used in Koto
     Str(KString),
 
@@ -85,6 +88,7 @@ impl KValue {
                 let meta = m.meta_map().map(|meta| meta.borrow().clone());
                 KMap::with_contents(data, meta).into()
             }
+            KValue::Set(s) => KSet::with_data(s.data().clone()).into(),
             KValue::Iterator(i) => i.make_copy()?.into(),
             KValue::Object(o) => o.try_borrow()?.copy().into(),
             _ => self.clone(),
@@ -118,11 +122,19 @@ impl KValue {
     /// Returns true if the value is hashable
     ///
     /// Only hashable values are acceptable as map keys.
+    ///
+    /// Lists and maps are hashable when their contents are hashable, or when a map implements
+    /// `@hash`. Objects are hashable when they provide a hash via [KotoObject::hash].
     pub fn is_hashable(&self) -> bool {
         use KValue::*;
         match self {
             Null | Bool(_) | Number(_) | Range(_) | Str(_) => true,
             Tuple(t) => t.is_hashable(),
+            List(l) => l.data().iter().all(KValue::is_hashable),
+            Map(m) => {
+                m.contains_meta_key(&MetaKey::Hash) || m.data().values().all(KValue::is_hashable)
+            }
+            Object(o) => o.try_borrow().is_ok_and(|o| o.hash().is_some()),
             _ => false,
         }
     }
@@ -131,7 +143,7 @@ impl KValue {
     pub fn is_iterable(&self) -> bool {
         use KValue::*;
         match self {
-            Range(_) | List(_) | Tuple(_) | Map(_) | Str(_) | Iterator(_) => true,
+            Range(_) | List(_) | Tuple(_) | Map(_) | Set(_) | Str(_) | Iterator(_) => true,
             Object(o) => o.try_borrow().map_or(false, |o| {
                 !matches!(o.is_iterable(), IsIterable::NotIterable)
             }),
@@ -155,6 +167,7 @@ impl KValue {
                 None => TYPE_OBJECT.with(|x| x.clone()),
             },
             Map(_) => TYPE_MAP.with(|x| x.clone()),
+            Set(_) => TYPE_SET.with(|x| x.clone()),
             Str(_) => TYPE_STRING.with(|x| x.clone()),
             Tuple(_) => TYPE_TUPLE.with(|x| x.clone()),
             Function(f) if f.generator => TYPE_GENERATOR.with(|x| x.clone()),
@@ -189,6 +202,7 @@ impl KValue {
             List(l) => return l.display(ctx),
             Tuple(t) => return t.display(ctx),
             Map(m) => return m.display(ctx),
+            Set(s) => return s.display(ctx),
             Object(o) => return o.try_borrow()?.display(ctx),
         };
         if result.is_ok() {
@@ -208,6 +222,7 @@ thread_local! {
     static TYPE_RANGE: KString = "Range".into();
     static TYPE_MAP: KString = "Map".into();
     static TYPE_OBJECT: KString = "Object".into();
+    static TYPE_SET: KString = "Set".into();
     static TYPE_STRING: KString = "String".into();
     static TYPE_TUPLE: KString = "Tuple".into();
     static TYPE_FUNCTION: KString = "Function".into();
@@ -282,6 +297,12 @@ impl From<KMap> for KValue {
     }
 }
 
+impl From<KSet> for KValue {
+    fn from(value: KSet) -> Self {
+        Self::Set(value)
+    }
+}
+
 impl From<KObject> for KValue {
     fn from(value: KObject) -> Self {
         Self::Object(value)
@@ -1,41 +1,262 @@
-use crate::{prelude::*, Error};
-use indexmap::Equivalent;
+use crate::{prelude::*, Error, Ptr, PtrMut, Result};
+use indexmap::{Equivalent, IndexMap};
 use std::{
     cmp::Ordering,
     fmt,
-    hash::{Hash, Hasher},
+    hash::{BuildHasherDefault, Hash, Hasher},
+    mem::discriminant,
 };
 
 /// The key type used by [ValueMap](crate::ValueMap)
 ///
 /// Only hashable values can be used as keys, see [KValue::is_hashable]
+///
+/// Lists, maps, and objects are copied when they're used as keys, so that the key can't be
+/// modified after it has been inserted into a map. Keys for maps that implement `@hash` need to
+/// be made with [KotoVm::make_value_key].
 #[derive(Clone)]
-pub struct ValueKey(KValue);
+pub struct ValueKey(Inner);
+
+#[derive(Clone)]
+enum Inner {
+    // Core value types that can be hashed and compared directly
+    Value(KValue),
+    // Keys that need to be hashed when they're created, e.g. lists, maps, and objects
+    Custom(Ptr<CustomKey>),
+}
+
+struct CustomKey {
+    // A snapshot of the value that was used to make the key
+    value: KValue,
+    // The precomputed hash of the key
+    hash: u64,
+    kind: CustomKeyKind,
+}
+
+enum CustomKeyKind {
+    // A list, or a tuple that contains custom keys
+    Sequence(Vec<ValueKey>),
+    // A map without `@hash`, compared by its entries, independent of order
+    Entries(IndexMap<ValueKey, ValueKey, BuildHasherDefault<KotoHasher>>),
+    // A map that implements `@hash`
+    Meta {
+        // The result of calling `@hash`
+        identity: ValueKey,
+        // If the map implements `@==`, then the VM that's used to call it when comparing keys
+        vm: Option<PtrMut<KotoVm>>,
+    },
+    // An object that provides a hash via [KotoObject::hash]
+    Object,
+}
 
 impl ValueKey {
     /// Returns a reference to the key's value
     pub fn value(&self) -> &KValue {
-        &self.0
+        match &self.0 {
+            Inner::Value(value) => value,
+            Inner::Custom(key) => &key.value,
+        }
+    }
+
+    // Makes a key from a core value, see `is_core_key`
+    fn core(value: KValue) -> Self {
+        Self(Inner::Value(value))
+    }
+
+    /// Makes a key from the given value, using the VM to call `@hash` for maps that implement it
+    ///
+    /// See also: [KotoVm::make_value_key]
+    pub(crate) fn with_vm(value: KValue, vm: &mut KotoVm) -> Result<Self> {
+        Self::make(value, Some(vm))
+    }
+
+    fn make(value: KValue, vm: Option<&mut KotoVm>) -> Result<Self> {
+        if is_core_key(&value) {
+            Ok(Self::core(value))
+        } else if value.is_hashable() {
+            Self::make_custom(value.deep_copy()?, vm)
+        } else {
+            runtime_error!("Only hashable values can be used as value keys")
+        }
+    }
+
+    // Makes a custom key from a value that has already been copied
+    fn make_custom(value: KValue, mut vm: Option<&mut KotoVm>) -> Result<Self> {
+        use KValue::*;
+
+        if is_core_key(&value) {
+            return Ok(Self::core(value));
+        }
+
+        let (hash, kind) = match &value {
+            List(l) => make_sequence_key(l.data().clone(), vm)?,
+            Tuple(t) => make_sequence_key(t.iter().cloned(), vm)?,
+            Map(m) => match m.get_meta_value(&MetaKey::Hash) {
+                Some(hash_fn) => {
+                    let Some(vm) = vm else {
+                        return runtime_error!(
+                            "A VM is needed to make a key from a map with @hash"
+                        );
+                    };
+                    let identity = vm.call_instance_function(value.clone(), hash_fn, &[])?;
+                    let identity = Self::make(identity, Some(vm))?;
+                    let mut hasher = KotoHasher::default();
+                    identity.hash(&mut hasher);
+                    let vm = if m.contains_meta_key(&BinaryOp::Equal.into()) {
+                        Some(vm.key_comparison_vm())
+                    } else {
+                        None
+                    };
+                    (hasher.finish(), CustomKeyKind::Meta { identity, vm })
+                }
+                None => {
+                    let mut entries = IndexMap::default();
+                    // The entries are hashed independently and then combined,
+                    // so that the key's hash doesn't depend on the order of the entries.
+                    let mut hash = 0u64;
+                    for (key, entry_value) in m.data().iter() {
+                        let entry_value =
+                            Self::make_custom(entry_value.clone(), vm.as_deref_mut())?;
+                        let mut entry_hasher = KotoHasher::default();
+                        key.hash(&mut entry_hasher);
+                        entry_value.hash(&mut entry_hasher);
+                        hash = hash.wrapping_add(entry_hasher.finish());
+                        entries.insert(key.clone(), entry_value);
+                    }
+                    (hash, CustomKeyKind::Entries(entries))
+                }
+            },
+            Object(o) => match o.try_borrow()?.hash() {
+                Some(hash) => (hash, CustomKeyKind::Object),
+                None => return runtime_error!("Only hashable values can be used as value keys"),
+            },
+            _ => return runtime_error!("Only hashable values can be used as value keys"),
+        };
+
+        Ok(Self(Inner::Custom(make_ptr!(CustomKey {
+            value,
+            hash,
+            kind
+        }))))
+    }
+}
+
+fn make_sequence_key(
+    elements: impl IntoIterator<Item = KValue>,
+    mut vm: Option<&mut KotoVm>,
+) -> Result<(u64, CustomKeyKind)> {
+    let keys = elements
+        .into_iter()
+        .map(|element| ValueKey::make_custom(element, vm.as_deref_mut()))
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut hasher = KotoHasher::default();
+    keys.hash(&mut hasher);
+
+    Ok((hasher.finish(), CustomKeyKind::Sequence(keys)))
+}
+
+// Returns true if the value can be used directly as a key without needing to be copied or hashed
+fn is_core_key(value: &KValue) -> bool {
+    use KValue::*;
+
+    match value {
+        Null | Bool(_) | Number(_) | Range(_) | Str(_) => true,
+        Tuple(t) => t.iter().all(is_core_key),
+        _ => false,
     }
 }
 
 impl TryFrom<KValue> for ValueKey {
     type Error = Error;
 
-    fn try_from(value: KValue) -> Result<Self, Self::Error> {
-        if value.is_hashable() {
-            Ok(Self(value))
-        } else {
-            runtime_error!("Only hashable values can be used as value keys")
+    fn try_from(value: KValue) -> Result<Self> {
+        Self::make(value, None)
+    }
+}
+
+impl CustomKey {
+    fn is_equal(&self, other: &Self) -> bool {
+        use CustomKeyKind::*;
+
+        if self.hash != other.hash {
+            return false;
+        }
+
+        match (&self.kind, &other.kind) {
+            (Sequence(a), Sequence(b)) => {
+                discriminant(&self.value) == discriminant(&other.value) && a == b
+            }
+            (Entries(a), Entries(b)) => {
+                a.len() == b.len() && a.iter().all(|(key, value)| b.get(key) == Some(value))
+            }
+            (Meta { identity: a, vm }, Meta { identity: b, .. }) => {
+                match vm.as_ref().and_then(|vm| vm.try_borrow_mut()) {
+                    Some(mut vm) => matches!(
+                        vm.run_binary_op(BinaryOp::Equal, self.value.clone(), other.value.clone()),
+                        Ok(KValue::Bool(true))
+                    ),
+                    // Without `@==`, or if the VM is already busy comparing keys (e.g. when
+                    // `@==` itself uses maps with `@==` keys), the results of `@hash` are compared.
+                    None => a == b,
+                }
+            }
+            (Object, Object) => match &self.value {
+                KValue::Object(o) => o
+                    .try_borrow()
+                    .is_ok_and(|o| o.equal(&other.value).unwrap_or(false)),
+                _ => false,
+            },
+            _ => false,
         }
     }
 }
 
+impl CustomKey {
+    // Custom keys are ordered by their hashes, and then by identity
+    //
+    // Sequences are ordered by their elements, and the results of `@hash` are used as the
+    // identity of maps that implement `@hash`. Other keys with matching hashes are ordered by
+    // their addresses.
+    fn compare(&self, other: &Self) -> Ordering {
+        use CustomKeyKind::*;
+
+        if let (Sequence(a), Sequence(b)) = (&self.kind, &other.kind) {
+            if let Some(ordering) = a.partial_cmp(b) {
+                return ordering;
+            }
+        }
+
+        if self.is_equal(other) {
+            return Ordering::Equal;
+        }
+
+        self.hash
+            .cmp(&other.hash)
+            .then_with(|| match (&self.kind, &other.kind) {
+                (Meta { identity: a, .. }, Meta { identity: b, .. }) => {
+                    a.partial_cmp(b).unwrap_or(Ordering::Equal)
+                }
+                _ => Ordering::Equal,
+            })
+            .then_with(|| {
+                let address = |key: &Self| key as *const Self as usize;
+                address(self).cmp(&address(other))
+            })
+    }
+}
+
 impl PartialEq for ValueKey {
     fn eq(&self, other: &Self) -> bool {
         use KValue::*;
 
-        match (&self.0, &other.0) {
+        let (a, b) = match (&self.0, &other.0) {
+            (Inner::Value(a), Inner::Value(b)) => (a, b),
+            (Inner::Custom(a), Inner::Custom(b)) => return a.is_equal(b),
+            _ => return false,
+        };
+
+        match (a, b) {
             (Number(a), Number(b)) => a == b,
             (Bool(a), Bool(b)) => a == b,
             (Str(a), Str(b)) => a == b,
@@ -43,9 +264,9 @@ impl PartialEq for ValueKey {
             (Null, Null) => true,
             (Tuple(a), Tuple(b)) => {
                 a.len() == b.len()
-                    && a.iter()
-                        .zip(b.iter())
-                        .all(|(value_a, value_b)| Self(value_a.clone()) == Self(value_b.clone()))
+                    && a.iter().zip(b.iter()).all(|(value_a, value_b)| {
+                        Self::core(value_a.clone()) == Self::core(value_b.clone())
+                    })
             }
             _ => false,
         }
@@ -57,7 +278,12 @@ impl Hash for ValueKey {
     fn hash<H: Hasher>(&self, state: &mut H) {
         use KValue::*;
 
-        match &self.0 {
+        let value = match &self.0 {
+            Inner::Value(value) => value,
+            Inner::Custom(key) => return state.write_u64(key.hash),
+        };
+
+        match value {
             Null => {}
             Bool(b) => b.hash(state),
             Number(n) => n.hash(state),
@@ -65,7 +291,7 @@ impl Hash for ValueKey {
             Range(r) => r.hash(state),
             Tuple(t) => {
                 for value in t.iter() {
-                    Self(value.clone()).hash(state)
+                    Self::core(value.clone()).hash(state)
                 }
             }
             _ => {}
@@ -77,7 +303,14 @@ impl PartialOrd for ValueKey {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         use KValue::*;
 
-        match (&self.0, &other.0) {
+        let (a, b) = match (&self.0, &other.0) {
+            (Inner::Value(a), Inner::Value(b)) => (a, b),
+            (Inner::Custom(a), Inner::Custom(b)) => return Some(a.compare(b)),
+            (Inner::Value(_), Inner::Custom(_)) => return Some(Ordering::Less),
+            (Inner::Custom(_), Inner::Value(_)) => return Some(Ordering::Greater),
+        };
+
+        match (a, b) {
             (Null, Null) => Some(Ordering::Equal),
             (Null, _) => Some(Ordering::Less),
             (_, Null) => Some(Ordering::Greater),
@@ -86,9 +319,10 @@ impl PartialOrd for ValueKey {
             (Tuple(a), Tuple(b)) => match a.len().cmp(&b.len()) {
                 Ordering::Equal => {
                     for (value_a, value_b) in a.iter().zip(b.iter()) {
-                        // Only ValueRef-able values will be contained in a tuple that's made it
-                        // into a ValueKey
-                        match Self(value_a.clone()).partial_cmp(&Self(value_b.clone())) {
+                        // Only core values will be contained in a tuple that's made it
+                        // into a ValueKey::Value
+                        match Self::core(value_a.clone()).partial_cmp(&Self::core(value_b.clone()))
+                        {
                             Some(Ordering::Equal) => {}
                             other => return other,
                         }
@@ -103,10 +337,21 @@ impl PartialOrd for ValueKey {
 }
 
 impl fmt::Display for ValueKey {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         use KValue::*;
 
-        match &self.0 {
+        let value = match &self.0 {
+            Inner::Value(value) => value,
+            Inner::Custom(key) => {
+                let mut ctx = DisplayContext::default();
+                return match key.value.display(&mut ctx) {
+                    Ok(_) => f.write_str(&ctx.result()),
+                    Err(_) => f.write_str(&key.value.type_as_string()),
+                };
+            }
+        };
+
+        match value {
             Null => f.write_str("null"),
             Bool(b) => write!(f, "{b}"),
             Number(n) => write!(f, "{n}"),
@@ -118,7 +363,7 @@ impl fmt::Display for ValueKey {
                     if i > 0 {
                         f.write_str(", ")?;
                     }
-                    write!(f, "{}", Self(value.clone()))?;
+                    write!(f, "{}", Self::core(value.clone()))?;
                 }
                 f.write_str(")")
             }
@@ -129,7 +374,7 @@ impl fmt::Display for ValueKey {
 
 impl From<KString> for ValueKey {
     fn from(value: KString) -> Self {
-        Self(KValue::Str(value))
+        Self::core(KValue::Str(value))
     }
 }
 
@@ -138,13 +383,13 @@ where
     KNumber: From<T>,
 {
     fn from(value: T) -> Self {
-        Self(KValue::Number(value.into()))
+        Self::core(KValue::Number(value.into()))
     }
 }
 
 impl From<&str> for ValueKey {
     fn from(value: &str) -> Self {
-        Self(KValue::Str(value.into()))
+        Self::core(KValue::Str(value.into()))
     }
 }
 
@@ -152,7 +397,7 @@ impl From<&str> for ValueKey {
 impl Equivalent<ValueKey> for str {
     fn equivalent(&self, other: &ValueKey) -> bool {
         match &other.0 {
-            KValue::Str(s) => self == s.as_str(),
+            Inner::Value(KValue::Str(s)) => self == s.as_str(),
             _ => false,
         }
     }
@@ -161,7 +406,7 @@ impl Equivalent<ValueKey> for str {
 impl Equivalent<ValueKey> for KString {
     fn equivalent(&self, other: &ValueKey) -> bool {
         match &other.0 {
-            KValue::Str(s) => self == s,
+            Inner::Value(KValue::Str(s)) => self == s,
             _ => false,
         }
     }
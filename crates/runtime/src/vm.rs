@@ -1,19 +1,23 @@
 use crate::{
-    core_lib::CoreLib,
+    core_lib::{string::format::format_value, CoreLib},
     error::{Error, ErrorKind},
     prelude::*,
     types::{meta_id_to_key, value::RegisterSlice},
-    DefaultStderr, DefaultStdin, DefaultStdout, KCaptureFunction, KFunction, Ptr, Result,
+    BorrowMut, DefaultStderr, DefaultStdin, DefaultStdout, KCaptureFunction, KFunction, Ptr,
+    PtrMut, Result,
 };
 use instant::Instant;
 use koto_bytecode::{Chunk, Instruction, InstructionReader, Loader};
-use koto_parser::{ConstantIndex, MetaKeyId, StringAlignment, StringFormatOptions};
+use koto_parser::{ConstantIndex, MetaKeyId, StringFormatOptions};
 use rustc_hash::FxHasher;
 use std::{
     collections::HashMap,
     fmt,
+    future::Future,
     hash::BuildHasherDefault,
     path::{Path, PathBuf},
+    pin::Pin,
+    task::{Context, Poll},
     time::Duration,
 };
 use unicode_segmentation::UnicodeSegmentation;
@@ -41,6 +45,7 @@ pub enum ControlFlow {
     Continue,
     Return(KValue),
     Yield(KValue),
+    Await(KFuture),
 }
 
 /// State shared between concurrent VMs
@@ -100,6 +105,12 @@ pub struct KotoVmSettings {
     /// block execution.
     pub execution_limit: Option<Duration>,
 
+    /// Whether or not integer overflow in arithmetic operations should be treated as an error
+    ///
+    /// By default, integer arithmetic wraps on overflow. When enabled, overflowing integer
+    /// operations will result in a runtime error.
+    pub checked_integer_overflow: bool,
+
     /// An optional callback that is called whenever a module is imported by the runtime
     ///
     /// This allows you to track the runtime's dependencies, which might be useful if you want to
@@ -121,6 +132,7 @@ impl Default for KotoVmSettings {
         Self {
             run_import_tests: true,
             execution_limit: None,
+            checked_integer_overflow: false,
             module_imported_callback: None,
             stdin: make_ptr!(DefaultStdin::default()),
             stdout: make_ptr!(DefaultStdout::default()),
@@ -150,6 +162,8 @@ pub struct KotoVm {
     instruction_ip: u32,
     // The current execution state
     execution_state: ExecutionState,
+    // A VM that's used to call `@==` when comparing map keys, created when it's first needed
+    key_comparison_vm: Option<PtrMut<KotoVm>>,
 }
 
 /// The execution state of a VM
@@ -159,7 +173,8 @@ pub enum ExecutionState {
     Inactive,
     /// The VM is currently executing instructions
     Active,
-    /// The VM is executing a generator function that has just yielded a value
+    /// The VM is executing a generator function that has just yielded a value,
+    /// or the VM is awaiting a pending future during an async run
     Suspended,
 }
 
@@ -182,6 +197,7 @@ impl KotoVm {
             string_builders: Vec::new(),
             instruction_ip: 0,
             execution_state: ExecutionState::Inactive,
+            key_comparison_vm: None,
         }
     }
 
@@ -203,6 +219,24 @@ impl KotoVm {
             string_builders: Vec::new(),
             instruction_ip: 0,
             execution_state: ExecutionState::Inactive,
+            key_comparison_vm: self.key_comparison_vm.clone(),
+        }
+    }
+
+    /// Spawn a VM with its own exports map that shares the runtime's context
+    ///
+    /// The spawned VM shares the prelude, the loader's cache of compiled modules, and the cache of
+    /// imported modules, but otherwise runs independently.
+    ///
+    /// With the `arc` feature enabled the spawned VM can be moved to another thread, allowing Koto
+    /// functions to be called in parallel.
+    #[must_use]
+    pub fn spawn_independent_vm(&self) -> Self {
+        Self {
+            exports: KMap::default(),
+            reader: InstructionReader::default(),
+            key_comparison_vm: None,
+            ..self.spawn_shared_vm()
         }
     }
 
@@ -229,6 +263,13 @@ impl KotoVm {
         &mut self.exports
     }
 
+    /// Returns true if integer overflow should be treated as an error
+    ///
+    /// See [KotoVmSettings::checked_integer_overflow]
+    pub fn checked_integer_overflow(&self) -> bool {
+        self.context.settings.checked_integer_overflow
+    }
+
     /// The stdin wrapper used by the VM
     pub fn stdin(&self) -> &Ptr<dyn KotoFile> {
         &self.context.settings.stdin
@@ -244,6 +285,146 @@ impl KotoVm {
         &self.context.settings.stderr
     }
 
+    /// Reloads previously imported modules
+    ///
+    /// Each module is recompiled from disk and then executed with a fresh exports map.
+    /// If the module defines a `@reload` function then it's called with the module's previous
+    /// exports map as its argument, allowing state to be migrated to the new version of the module.
+    ///
+    /// The new exports are then patched into the module's existing exports map, so that modules
+    /// that have already imported the module will see the reloaded values. Entries that are no
+    /// longer exported by the module are left in place.
+    ///
+    /// Tests are run for each reloaded module if `run_import_tests` is enabled, while `@main` is
+    /// only run when the module is first imported.
+    ///
+    /// An error will be returned if any of the paths don't refer to an imported module.
+    /// A failure to reload one module doesn't prevent the remaining modules from being reloaded,
+    /// with the errors for each failed module being combined into a single error.
+    pub fn reload_modules(&mut self, paths: &[PathBuf]) -> Result<()> {
+        let errors = paths
+            .iter()
+            .filter_map(|path| self.reload_module(path).err().map(|error| (path, error)))
+            .collect::<Vec<_>>();
+
+        combine_reload_errors(errors)
+    }
+
+    /// Reloads any imported modules whose files have been modified since they were compiled
+    ///
+    /// See [KotoVm::reload_modules].
+    ///
+    /// The paths of the reloaded modules are returned.
+    ///
+    /// Modules that fail to reload will be retried by the next call to this function.
+    pub fn reload_modified_modules(&mut self) -> Result<Vec<PathBuf>> {
+        let modified = self.context.loader.borrow().modified_modules();
+        let mut reloaded = Vec::with_capacity(modified.len());
+        let mut errors = Vec::new();
+
+        for path in modified {
+            // Modules that failed to import aren't reloaded, they'll be compiled when next imported
+            let is_imported = matches!(
+                self.context.imported_modules.borrow().get(&path),
+                Some(Some(_))
+            );
+            if is_imported {
+                match self.reload_module(&path) {
+                    Ok(()) => reloaded.push(path),
+                    Err(error) => errors.push((path, error)),
+                }
+            }
+        }
+
+        combine_reload_errors(errors).map(|_| reloaded)
+    }
+
+    fn reload_module(&mut self, path: &Path) -> Result<()> {
+        let module_name = path.display().to_string();
+
+        let module_path = match dunce::canonicalize(path) {
+            Ok(module_path) => module_path,
+            Err(error) => return runtime_error!("Failed to reload '{module_name}': {error}"),
+        };
+
+        // Check that the module has been imported before recompiling it
+        let mut previous_exports = match self.context.imported_modules.borrow().get(&module_path) {
+            Some(Some(exports)) => exports.clone(),
+            Some(None) => return runtime_error!("Recursive reload of module '{module_name}'"),
+            None => return runtime_error!("Module '{module_name}' hasn't been imported"),
+        };
+
+        // The recompiled chunk is only cached once the module has been successfully reloaded
+        let compile_result = match self
+            .context
+            .loader
+            .borrow_mut()
+            .recompile_module(&module_path)
+        {
+            Ok(result) => result,
+            Err(error) => return runtime_error!("Failed to reload '{module_name}': {error}"),
+        };
+
+        // Insert a placeholder while the module is being executed, preventing recursive imports
+        self.context
+            .imported_modules
+            .borrow_mut()
+            .insert(compile_result.path.clone(), None);
+
+        let importer_exports = std::mem::take(&mut self.exports);
+
+        let reload_result = {
+            || {
+                self.run(compile_result.chunk.clone())?;
+
+                let maybe_reload = self.exports.get_meta_value(&MetaKey::Reload);
+                match maybe_reload {
+                    Some(reload) if reload.is_callable() => {
+                        self.call_function(reload, KValue::Map(previous_exports.clone()))?;
+                    }
+                    Some(unexpected) => return type_error("callable function", &unexpected),
+                    None => {}
+                }
+
+                self.run_module_tests(&module_name)
+            }
+        }();
+
+        let reloaded_exports = std::mem::replace(&mut self.exports, importer_exports);
+
+        if reload_result.is_ok() {
+            // Patch the new exports into the existing exports map
+            previous_exports
+                .data_mut()
+                .merge_reloaded(&reloaded_exports.data());
+
+            if let Some(reloaded_meta) = reloaded_exports.meta_map() {
+                let reloaded_meta = reloaded_meta.borrow().clone();
+                match previous_exports.meta_map() {
+                    Some(meta) => meta.borrow_mut().extend(&reloaded_meta),
+                    None => previous_exports.set_meta_map(Some(reloaded_meta.into())),
+                }
+            }
+
+            self.context
+                .loader
+                .borrow_mut()
+                .cache_module(&compile_result);
+
+            if let Some(callback) = &self.context.settings.module_imported_callback {
+                callback(&compile_result.path);
+            }
+        }
+
+        // Restore the module's exports in the cache, whether or not the reload was successful
+        self.context
+            .imported_modules
+            .borrow_mut()
+            .insert(compile_result.path, Some(previous_exports));
+
+        reload_result
+    }
+
     /// Runs the provided [Chunk], returning the resulting [KValue]
     pub fn run(&mut self, chunk: Ptr<Chunk>) -> Result<KValue> {
         // Set up an execution frame to run the chunk in
@@ -267,6 +448,54 @@ impl KotoVm {
         result
     }
 
+    /// Runs the provided [Chunk] asynchronously
+    ///
+    /// The returned [Future] resolves to the chunk's result. When the script uses `await` on a
+    /// pending [KFuture], execution is suspended and the future is polled until it's ready,
+    /// after which execution continues.
+    ///
+    /// Execution can only be suspended in the chunk's top-level frame and in Koto functions called
+    /// from there. Awaiting a pending future in other contexts (e.g. in a function that's called
+    /// from a native function, or in a generator) results in an error.
+    ///
+    /// If the returned future is dropped before completion then execution is cancelled.
+    pub fn run_async(&mut self, chunk: Ptr<Chunk>) -> AsyncRun<'_> {
+        AsyncRun {
+            vm: self,
+            state: AsyncRunState::NotStarted(chunk),
+        }
+    }
+
+    // Sets up an execution frame for an async run, returning the result register
+    fn start_async_run(&mut self, chunk: Ptr<Chunk>) -> u8 {
+        let result_register = self.next_register();
+        let frame_base = result_register + 1;
+        self.registers.push(KValue::Null); // result register
+        self.registers.push(KValue::Null); // instance register
+        self.push_frame(chunk, 0, frame_base, result_register);
+
+        // Ensure that execution stops here if an error is thrown,
+        // and allow execution to be suspended when a pending future is awaited
+        let frame = self.frame_mut();
+        frame.execution_barrier = true;
+        frame.allow_await_suspension = true;
+
+        result_register
+    }
+
+    // Called when an async run is cancelled while awaiting a future
+    fn cancel_async_run(&mut self, result_register: u8) {
+        while let Some(frame) = self.call_stack.last() {
+            let is_async_root = frame.allow_await_suspension;
+            if self.pop_frame(KValue::Null).is_err() || is_async_root {
+                break;
+            }
+        }
+
+        self.execution_state = ExecutionState::Inactive;
+        self.truncate_registers(result_register);
+    }
+
     /// Continues execution in a suspended VM
     ///
     /// This is currently used to support generators, which yield incremental results and then
@@ -527,10 +756,34 @@ impl KotoVm {
         result
     }
 
+    /// Makes a map key from the provided value
+    ///
+    /// Unlike `ValueKey::try_from`, maps that implement `@hash` are supported.
+    pub fn make_value_key(&mut self, value: KValue) -> Result<ValueKey> {
+        ValueKey::with_vm(value, self)
+    }
+
+    // Returns the VM that's shared by map keys that need to call `@==` when they're compared
+    //
+    // The VM is created the first time it's needed, and is then shared with spawned VMs.
+    pub(crate) fn key_comparison_vm(&mut self) -> PtrMut<KotoVm> {
+        if let Some(vm) = &self.key_comparison_vm {
+            return vm.clone();
+        }
+
+        let vm = make_ptr_mut!(self.spawn_shared_vm());
+        self.key_comparison_vm = Some(vm.clone());
+        vm
+    }
+
     /// Makes a KIterator that iterates over the provided value's contents
     pub fn make_iterator(&mut self, value: KValue) -> Result<KIterator> {
         use KValue::*;
 
+        if let Some(result) = KIterator::with_core_value(&value) {
+            return result;
+        }
+
         match value {
             Map(ref m) if m.contains_meta_key(&UnaryOp::Next.into()) => {
                 KIterator::with_meta_next(self.spawn_shared_vm(), value)
@@ -541,12 +794,6 @@ impl KotoVm {
                 let iterator_call_result = self.run_unary_op(UnaryOp::Iterator, value)?;
                 self.make_iterator(iterator_call_result)
             }
-            Iterator(i) => Ok(i),
-            Range(r) => KIterator::with_range(r),
-            List(l) => Ok(KIterator::with_list(l)),
-            Tuple(t) => Ok(KIterator::with_tuple(t)),
-            Str(s) => Ok(KIterator::with_string(s)),
-            Map(m) => Ok(KIterator::with_map(m)),
             Object(o) => {
                 use IsIterable::*;
 
@@ -678,6 +925,10 @@ impl KotoVm {
                     self.execution_state = ExecutionState::Suspended;
                     return Ok(value);
                 }
+                Ok(ControlFlow::Await(future)) => {
+                    self.execution_state = ExecutionState::Suspended;
+                    return Ok(future.into());
+                }
                 Err(error) => match self.pop_call_stack_on_error(error.clone(), true) {
                     Ok((recover_register, ip)) => {
                         let catch_value = match error.error {
@@ -726,6 +977,7 @@ impl KotoVm {
             }
             LoadNonLocal { register, constant } => self.run_load_non_local(register, constant)?,
             ValueExport { name, value } => self.run_value_export(name, value)?,
+            ConstExport { name, value } => self.run_const_export(name, value)?,
             Import { register } => self.run_import(register)?,
             MakeTempTuple {
                 register,
@@ -779,6 +1031,14 @@ impl KotoVm {
                 self.run_make_range(register, Some(start), None, false)?
             }
             RangeFull { register } => self.run_make_range(register, None, None, false)?,
+            RangeStep { register, step } => self.run_range_step(register, step)?,
+            SteppedRange {
+                register,
+                start,
+                end,
+                step,
+                inclusive,
+            } => self.run_make_stepped_range(register, start, end, step, inclusive)?,
             MakeIterator { register, iterable } => {
                 self.run_make_iterator(register, iterable, true)?
             }
@@ -812,6 +1072,16 @@ impl KotoVm {
             JumpBack { offset } => self.jump_ip_back(offset as u32),
             JumpIfTrue { register, offset } => self.run_jump_if_true(register, offset as u32)?,
             JumpIfFalse { register, offset } => self.run_jump_if_false(register, offset as u32)?,
+            JumpIfNull { register, offset } => {
+                if matches!(self.get_register(register), KValue::Null) {
+                    self.jump_ip(offset as u32);
+                }
+            }
+            JumpIfNotNull { register, offset } => {
+                if !matches!(self.get_register(register), KValue::Null) {
+                    self.jump_ip(offset as u32);
+                }
+            }
             Call {
                 result,
                 function,
@@ -833,6 +1103,20 @@ impl KotoVm {
                 }
             }
             Yield { register } => control_flow = ControlFlow::Yield(self.clone_register(register)),
+            Await { register, value } => control_flow = self.run_await(register, value)?,
+            Close { register } => self.run_close(register)?,
+            MakeCell { register } => {
+                // Cells are single-element lists that are shared by cloning the list
+                self.set_register(register, KList::from_slice(&[KValue::Null]).into())
+            }
+            LoadCell { register, cell } => {
+                let value = self.cell_data(cell)?[0].clone();
+                self.set_register(register, value);
+            }
+            StoreCell { cell, value } => {
+                let value = self.clone_register(value);
+                self.cell_data(cell)?[0] = value;
+            }
             Throw { register } => {
                 let thrown_value = self.clone_register(register);
 
@@ -928,6 +1212,10 @@ impl KotoVm {
             Debug { register, constant } => self.run_debug(register, constant)?,
             CheckSizeEqual { register, size } => self.run_check_size_equal(register, size)?,
             CheckSizeMin { register, size } => self.run_check_size_min(register, size)?,
+            CheckType {
+                register,
+                type_constant,
+            } => self.run_check_type(register, type_constant)?,
         }
 
         Ok(control_flow)
@@ -952,7 +1240,14 @@ impl KotoVm {
     fn run_value_export(&mut self, name_register: u8, value_register: u8) -> Result<()> {
         let name = ValueKey::try_from(self.clone_register(name_register))?;
         let value = self.clone_register(value_register);
-        self.exports.data_mut().insert(name, value);
+        self.exports.data_mut().insert(name, value)?;
+        Ok(())
+    }
+
+    fn run_const_export(&mut self, name_register: u8, value_register: u8) -> Result<()> {
+        self.run_value_export(name_register, value_register)?;
+        let name = ValueKey::try_from(self.clone_register(name_register))?;
+        self.exports.add_const_key(name);
         Ok(())
     }
 
@@ -975,12 +1270,32 @@ impl KotoVm {
         end_register: Option<u8>,
         inclusive: bool,
     ) -> Result<()> {
-        use KValue::Number;
+        use KValue::{Number, Str};
 
         let start = start_register.map(|r| self.get_register(r));
         let end = end_register.map(|r| self.get_register(r));
 
+        if let (Some(Str(start)), Some(Str(end))) = (start, end) {
+            let result = match (single_char(start), single_char(end)) {
+                (Some(start), Some(end)) => KRange::with_chars(start, end, inclusive),
+                _ => {
+                    return runtime_error!(
+                        "Character ranges require single characters (found '{start}' and '{end}')"
+                    )
+                }
+            };
+            self.set_register(register, result.into());
+            return Ok(());
+        }
+
         let (range_start, range_end) = match (start, end) {
+            // Bounded ranges with a float bound are float ranges,
+            // ranges with a single bound have the bound truncated to an integer.
+            (Some(Number(start)), Some(Number(end))) if start.is_f64() || end.is_f64() => {
+                let result = KRange::with_floats(start.into(), end.into(), inclusive, 1.0)?;
+                self.set_register(register, result.into());
+                return Ok(());
+            }
             (Some(Number(start)), Some(Number(end))) => {
                 (Some(start.into()), Some((end.into(), inclusive)))
             }
@@ -999,6 +1314,58 @@ impl KotoVm {
         Ok(())
     }
 
+    fn run_range_step(&mut self, register: u8, step_register: u8) -> Result<()> {
+        use KValue::{Number, Range};
+
+        let result = match (
+            self.get_register(register),
+            self.get_register(step_register),
+        ) {
+            (Range(range), Number(KNumber::I64(step))) => range.with_step(*step)?,
+            (Range(range), Number(KNumber::F64(step))) => range.with_float_step(*step)?,
+            (Range(_), unexpected) => {
+                return type_error("a Number for the range's step", unexpected)
+            }
+            (unexpected, _) => return type_error("a Range", unexpected),
+        };
+
+        self.set_register(register, result.into());
+        Ok(())
+    }
+
+    fn run_make_stepped_range(
+        &mut self,
+        register: u8,
+        start_register: u8,
+        end_register: u8,
+        step_register: u8,
+        inclusive: bool,
+    ) -> Result<()> {
+        use KValue::Number;
+
+        match (
+            self.get_register(start_register),
+            self.get_register(end_register),
+            self.get_register(step_register),
+        ) {
+            // Float steps make float ranges, using the original bounds of the range
+            (Number(start), Number(end), Number(KNumber::F64(step))) => {
+                let result = KRange::with_floats(start.into(), end.into(), inclusive, *step)?;
+                self.set_register(register, result.into());
+                Ok(())
+            }
+            _ => {
+                self.run_make_range(
+                    register,
+                    Some(start_register),
+                    Some(end_register),
+                    inclusive,
+                )?;
+                self.run_range_step(register, step_register)
+            }
+        }
+    }
+
     // Runs the MakeIterator instruction
     //
     // This function is distinct from the public `make_iterator`, which will defer to this function
@@ -1041,6 +1408,7 @@ impl KotoVm {
             Tuple(tuple) => KIterator::with_tuple(tuple).into(),
             Str(s) => KIterator::with_string(s).into(),
             Map(map) => KIterator::with_map(map).into(),
+            Set(set) => KIterator::with_set(set).into(),
             Object(o) => {
                 use IsIterable::*;
                 let o_inner = o.try_borrow()?;
@@ -1131,7 +1499,7 @@ impl KotoVm {
                 let (output, new_iterable) = match other {
                     Range(mut r) => {
                         let output = r.pop_front()?;
-                        (output.map(KValue::from), Range(r))
+                        (output, Range(r))
                     }
                     Tuple(mut t) => {
                         let output = t.pop_front();
@@ -1382,6 +1750,10 @@ impl KotoVm {
         use UnaryOp::Negate;
 
         let result_value = match self.clone_register(value) {
+            Number(n) if self.checked_integer_overflow() => match n.checked_neg() {
+                Some(result) => Number(result),
+                None => return runtime_error!("Integer overflow while negating '{n}'"),
+            },
             Number(n) => Number(-n),
             Map(m) if m.contains_meta_key(&Negate.into()) => {
                 let op = m.get_meta_value(&Negate.into()).unwrap();
@@ -1436,7 +1808,7 @@ impl KotoVm {
         let lhs_value = self.get_register(lhs);
         let rhs_value = self.get_register(rhs);
         let result_value = match (lhs_value, rhs_value) {
-            (Number(a), Number(b)) => Number(a + b),
+            (Number(a), Number(b)) => Number(self.run_number_op(*a, *b, Add)?),
             (Str(a), Str(b)) => {
                 let result = a.to_string() + b.as_ref();
                 Str(result.into())
@@ -1456,7 +1828,7 @@ impl KotoVm {
             }
             (Map(a), Map(b)) => {
                 let mut data = a.data().clone();
-                data.extend(b.data().iter().map(|(k, v)| (k.clone(), v.clone())));
+                data.extend(b.data().iter().map(|(k, v)| (k.clone(), v.clone())))?;
                 let meta = match (a.meta_map(), b.meta_map()) {
                     (None, None) => None,
                     (Some(meta_a), None) => Some(meta_a.borrow().clone()),
@@ -1470,6 +1842,7 @@ impl KotoVm {
                 Map(KMap::with_contents(data, meta))
             }
             (Object(o), _) => o.try_borrow()?.add(rhs_value)?,
+            (Number(_), Object(o)) => o.try_borrow()?.add_rhs(lhs_value)?,
             _ => return binary_op_error(lhs_value, rhs_value, Add),
         };
 
@@ -1484,13 +1857,14 @@ impl KotoVm {
         let lhs_value = self.get_register(lhs);
         let rhs_value = self.get_register(rhs);
         let result_value = match (lhs_value, rhs_value) {
-            (Number(a), Number(b)) => Number(a - b),
+            (Number(a), Number(b)) => Number(self.run_number_op(*a, *b, Subtract)?),
             (Map(m), _) if m.contains_meta_key(&Subtract.into()) => {
                 let op = m.get_meta_value(&Subtract.into()).unwrap();
                 let rhs_value = rhs_value.clone();
                 return self.call_overridden_binary_op(result, lhs, rhs_value, op);
             }
             (Object(o), _) => o.try_borrow()?.subtract(rhs_value)?,
+            (Number(_), Object(o)) => o.try_borrow()?.subtract_rhs(lhs_value)?,
             _ => return binary_op_error(lhs_value, rhs_value, Subtract),
         };
 
@@ -1506,13 +1880,14 @@ impl KotoVm {
         let rhs_value = self.get_register(rhs);
 
         let result_value = match (lhs_value, rhs_value) {
-            (Number(a), Number(b)) => Number(a * b),
+            (Number(a), Number(b)) => Number(self.run_number_op(*a, *b, Multiply)?),
             (Map(m), _) if m.contains_meta_key(&Multiply.into()) => {
                 let op = m.get_meta_value(&Multiply.into()).unwrap();
                 let rhs_value = rhs_value.clone();
                 return self.call_overridden_binary_op(result, lhs, rhs_value, op);
             }
             (Object(o), _) => o.try_borrow()?.multiply(rhs_value)?,
+            (Number(_), Object(o)) => o.try_borrow()?.multiply_rhs(lhs_value)?,
             _ => return binary_op_error(lhs_value, rhs_value, Multiply),
         };
 
@@ -1534,6 +1909,7 @@ impl KotoVm {
                 return self.call_overridden_binary_op(result, lhs, rhs_value, op);
             }
             (Object(o), _) => o.try_borrow()?.divide(rhs_value)?,
+            (Number(_), Object(o)) => o.try_borrow()?.divide_rhs(lhs_value)?,
             _ => return binary_op_error(lhs_value, rhs_value, Divide),
         };
 
@@ -1553,13 +1929,14 @@ impl KotoVm {
                 // avoid a panic and return NaN instead.
                 Number(f64::NAN.into())
             }
-            (Number(a), Number(b)) => Number(a % b),
+            (Number(a), Number(b)) => Number(self.run_number_op(*a, *b, Remainder)?),
             (Map(m), _) if m.contains_meta_key(&Remainder.into()) => {
                 let op = m.get_meta_value(&Remainder.into()).unwrap();
                 let rhs_value = rhs_value.clone();
                 return self.call_overridden_binary_op(result, lhs, rhs_value, op);
             }
             (Object(o), _) => o.try_borrow()?.remainder(rhs_value)?,
+            (Number(_), Object(o)) => o.try_borrow()?.remainder_rhs(lhs_value)?,
             _ => return binary_op_error(lhs_value, rhs_value, Remainder),
         };
         self.set_register(result, result_value);
@@ -1567,6 +1944,43 @@ impl KotoVm {
         Ok(())
     }
 
+    // Performs an arithmetic operation on a pair of numbers
+    //
+    // If checked integer overflow is enabled, then an error is returned when an integer operation
+    // overflows.
+    fn run_number_op(&self, a: KNumber, b: KNumber, op: BinaryOp) -> Result<KNumber> {
+        use BinaryOp::*;
+
+        if matches!(op, Remainder | RemainderAssign) && matches!(b, KNumber::I64(0)) {
+            // Integer remainder with a divisor of zero would panic, return NaN instead
+            return Ok(f64::NAN.into());
+        }
+
+        if !self.checked_integer_overflow() {
+            let result = match op {
+                Add | AddAssign => a + b,
+                Subtract | SubtractAssign => a - b,
+                Multiply | MultiplyAssign => a * b,
+                Remainder | RemainderAssign => a % b,
+                _ => unreachable!(), // Only called for arithmetic operations that can overflow
+            };
+            return Ok(result);
+        }
+
+        let result = match op {
+            Add | AddAssign => a.checked_add(b),
+            Subtract | SubtractAssign => a.checked_sub(b),
+            Multiply | MultiplyAssign => a.checked_mul(b),
+            Remainder | RemainderAssign => a.checked_rem(b),
+            _ => unreachable!(),
+        };
+
+        match result {
+            Some(result) => Ok(result),
+            None => runtime_error!("Integer overflow in '{a} {op} {b}'"),
+        }
+    }
+
     fn run_add_assign(&mut self, lhs: u8, rhs: u8) -> Result<()> {
         use BinaryOp::AddAssign;
         use KValue::*;
@@ -1575,7 +1989,8 @@ impl KotoVm {
         let rhs_value = self.get_register(rhs);
         match (lhs_value, rhs_value) {
             (Number(a), Number(b)) => {
-                self.set_register(lhs, Number(a + b));
+                let result = self.run_number_op(*a, *b, AddAssign)?;
+                self.set_register(lhs, Number(result));
                 Ok(())
             }
             (Map(m), _) if m.contains_meta_key(&AddAssign.into()) => {
@@ -1590,6 +2005,11 @@ impl KotoVm {
                 o.try_borrow_mut()?.add_assign(&o2)
             }
             (Object(o), _) => o.try_borrow_mut()?.add_assign(rhs_value),
+            (Number(_), Object(o)) => {
+                let result = o.try_borrow()?.add_rhs(lhs_value)?;
+                self.set_register(lhs, result);
+                Ok(())
+            }
             _ => binary_op_error(lhs_value, rhs_value, AddAssign),
         }
     }
@@ -1602,7 +2022,8 @@ impl KotoVm {
         let rhs_value = self.get_register(rhs);
         match (lhs_value, rhs_value) {
             (Number(a), Number(b)) => {
-                self.set_register(lhs, Number(a - b));
+                let result = self.run_number_op(*a, *b, SubtractAssign)?;
+                self.set_register(lhs, Number(result));
                 Ok(())
             }
             (Map(m), _) if m.contains_meta_key(&SubtractAssign.into()) => {
@@ -1617,6 +2038,11 @@ impl KotoVm {
                 o.try_borrow_mut()?.subtract_assign(&o2)
             }
             (Object(o), _) => o.try_borrow_mut()?.subtract_assign(rhs_value),
+            (Number(_), Object(o)) => {
+                let result = o.try_borrow()?.subtract_rhs(lhs_value)?;
+                self.set_register(lhs, result);
+                Ok(())
+            }
             _ => binary_op_error(lhs_value, rhs_value, SubtractAssign),
         }
     }
@@ -1629,7 +2055,8 @@ impl KotoVm {
         let rhs_value = self.get_register(rhs);
         match (lhs_value, rhs_value) {
             (Number(a), Number(b)) => {
-                self.set_register(lhs, Number(a * b));
+                let result = self.run_number_op(*a, *b, MultiplyAssign)?;
+                self.set_register(lhs, Number(result));
                 Ok(())
             }
             (Map(m), _) if m.contains_meta_key(&MultiplyAssign.into()) => {
@@ -1644,6 +2071,11 @@ impl KotoVm {
                 o.try_borrow_mut()?.multiply_assign(&o2)
             }
             (Object(o), _) => o.try_borrow_mut()?.multiply_assign(rhs_value),
+            (Number(_), Object(o)) => {
+                let result = o.try_borrow()?.multiply_rhs(lhs_value)?;
+                self.set_register(lhs, result);
+                Ok(())
+            }
             _ => binary_op_error(lhs_value, rhs_value, MultiplyAssign),
         }
     }
@@ -1671,6 +2103,11 @@ impl KotoVm {
                 o.try_borrow_mut()?.divide_assign(&o2)
             }
             (Object(o), _) => o.try_borrow_mut()?.divide_assign(rhs_value),
+            (Number(_), Object(o)) => {
+                let result = o.try_borrow()?.divide_rhs(lhs_value)?;
+                self.set_register(lhs, result);
+                Ok(())
+            }
             _ => binary_op_error(lhs_value, rhs_value, DivideAssign),
         }
     }
@@ -1683,7 +2120,8 @@ impl KotoVm {
         let rhs_value = self.get_register(rhs);
         match (lhs_value, rhs_value) {
             (Number(a), Number(b)) => {
-                self.set_register(lhs, Number(a % b));
+                let result = self.run_number_op(*a, *b, RemainderAssign)?;
+                self.set_register(lhs, Number(result));
                 Ok(())
             }
             (Map(m), _) if m.contains_meta_key(&RemainderAssign.into()) => {
@@ -1698,6 +2136,11 @@ impl KotoVm {
                 o.try_borrow_mut()?.remainder_assign(&o2)
             }
             (Object(o), _) => o.try_borrow_mut()?.remainder_assign(rhs_value),
+            (Number(_), Object(o)) => {
+                let result = o.try_borrow()?.remainder_rhs(lhs_value)?;
+                self.set_register(lhs, result);
+                Ok(())
+            }
             _ => binary_op_error(lhs_value, rhs_value, RemainderAssign),
         }
     }
@@ -1717,6 +2160,7 @@ impl KotoVm {
                 return self.call_overridden_binary_op(result, lhs, rhs_value, op);
             }
             (Object(o), _) => o.try_borrow()?.less(rhs_value)?.into(),
+            (Number(_), Object(o)) => o.try_borrow()?.greater(lhs_value)?.into(),
             _ => return binary_op_error(lhs_value, rhs_value, Less),
         };
         self.set_register(result, result_value);
@@ -1739,6 +2183,7 @@ impl KotoVm {
                 return self.call_overridden_binary_op(result, lhs, rhs_value, op);
             }
             (Object(o), _) => o.try_borrow()?.less_or_equal(rhs_value)?.into(),
+            (Number(_), Object(o)) => o.try_borrow()?.greater_or_equal(lhs_value)?.into(),
             _ => return binary_op_error(lhs_value, rhs_value, LessOrEqual),
         };
         self.set_register(result, result_value);
@@ -1761,6 +2206,7 @@ impl KotoVm {
                 return self.call_overridden_binary_op(result, lhs, rhs_value, op);
             }
             (Object(o), _) => o.try_borrow()?.greater(rhs_value)?.into(),
+            (Number(_), Object(o)) => o.try_borrow()?.less(lhs_value)?.into(),
             _ => return binary_op_error(lhs_value, rhs_value, Greater),
         };
         self.set_register(result, result_value);
@@ -1783,6 +2229,7 @@ impl KotoVm {
                 return self.call_overridden_binary_op(result, lhs, rhs_value, op);
             }
             (Object(o), _) => o.try_borrow()?.greater_or_equal(rhs_value)?.into(),
+            (Number(_), Object(o)) => o.try_borrow()?.less_or_equal(lhs_value)?.into(),
             _ => return binary_op_error(lhs_value, rhs_value, GreaterOrEqual),
         };
         self.set_register(result, result_value);
@@ -1829,7 +2276,9 @@ impl KotoVm {
                     false
                 }
             }
+            (Set(a), Set(b)) => a.is_equal(b),
             (Object(o), _) => o.try_borrow()?.equal(rhs_value)?,
+            (Number(_), Object(o)) => o.try_borrow()?.equal(lhs_value)?,
             (CaptureFunction(a), CaptureFunction(b)) => {
                 if a.info == b.info {
                     let captures_a = a.captures.clone();
@@ -1889,7 +2338,9 @@ impl KotoVm {
                     true
                 }
             }
+            (Set(a), Set(b)) => !a.is_equal(b),
             (Object(o), _) => o.try_borrow()?.not_equal(rhs_value)?,
+            (Number(_), Object(o)) => o.try_borrow()?.not_equal(lhs_value)?,
             (CaptureFunction(a), CaptureFunction(b)) => {
                 if a.info == b.info {
                     let captures_a = a.captures.clone();
@@ -2027,6 +2478,68 @@ impl KotoVm {
         Ok(())
     }
 
+    fn run_await(&mut self, result_register: u8, value_register: u8) -> Result<ControlFlow> {
+        let future = match self.get_register(value_register) {
+            KValue::Object(o) if o.is_a::<KFuture>() => o.cast::<KFuture>()?.clone(),
+            // Values that aren't futures are passed through unchanged
+            other => {
+                self.set_register(result_register, other.clone());
+                return Ok(ControlFlow::Continue);
+            }
+        };
+
+        match future.result() {
+            Some(Ok(result)) => {
+                self.set_register(result_register, result);
+                Ok(ControlFlow::Continue)
+            }
+            Some(Err(error)) => Err(error),
+            None => {
+                let can_suspend = self
+                    .call_stack
+                    .iter()
+                    .rev()
+                    .find(|frame| frame.execution_barrier)
+                    .is_some_and(|frame| frame.allow_await_suspension);
+
+                if can_suspend {
+                    // Execution will resume by running the await instruction again,
+                    // at which point the future should be ready.
+                    self.set_ip(self.instruction_ip);
+                    Ok(ControlFlow::Await(future))
+                } else {
+                    runtime_error!(
+                        "Unable to suspend execution while awaiting a pending future \
+                         (see KotoVm::run_async)"
+                    )
+                }
+            }
+        }
+    }
+
+    fn run_close(&mut self, register: u8) -> Result<()> {
+        match self.clone_register(register) {
+            KValue::Map(m) if m.contains_meta_key(&MetaKey::Close) => {
+                let close_fn = m.get_meta_value(&MetaKey::Close).unwrap();
+                if !close_fn.is_callable() {
+                    return type_error("a callable @close function", &close_fn);
+                }
+                self.call_instance_function(KValue::Map(m), close_fn, &[])?;
+                Ok(())
+            }
+            KValue::Object(o) => o.try_borrow_mut()?.close(),
+            unexpected => type_error("a value that implements @close", &unexpected),
+        }
+    }
+
+    // Provides access to the contents of a cell that was made with the MakeCell instruction
+    fn cell_data(&self, register: u8) -> Result<BorrowMut<'_, ValueVec>> {
+        match self.get_register(register) {
+            KValue::List(cell) if cell.len() == 1 => Ok(cell.data_mut()),
+            unexpected => type_error("a cell", unexpected),
+        }
+    }
+
     fn run_size(
         &mut self,
         result_register: u8,
@@ -2048,6 +2561,7 @@ impl KotoVm {
                 return self.call_overridden_unary_op(result_register, value_register, op);
             }
             Map(m) => Some(m.len()),
+            Set(s) => Some(s.len()),
             Object(o) => o.try_borrow()?.size(),
             TemporaryTuple(RegisterSlice { count, .. }) => Some(*count as usize),
             _ => None,
@@ -2142,22 +2656,7 @@ impl KotoVm {
         let import_result = {
             || {
                 self.run(compile_result.chunk.clone())?;
-
-                if self.context.settings.run_import_tests {
-                    let maybe_tests = self.exports.get_meta_value(&MetaKey::Tests);
-                    match maybe_tests {
-                        Some(KValue::Map(tests)) => {
-                            self.run_tests(tests)?;
-                        }
-                        Some(other) => {
-                            return runtime_error!(
-                                "Expected map for tests in module '{import_name}', found '{}'",
-                                other.type_as_string()
-                            )
-                        }
-                        None => {}
-                    }
-                }
+                self.run_module_tests(&import_name)?;
 
                 let maybe_main = self.exports.get_meta_value(&MetaKey::Main);
                 match maybe_main {
@@ -2198,6 +2697,27 @@ impl KotoVm {
         import_result
     }
 
+    // Runs the @tests defined in the active module's exports, if import tests are enabled
+    fn run_module_tests(&mut self, module_name: &str) -> Result<()> {
+        if self.context.settings.run_import_tests {
+            let maybe_tests = self.exports.get_meta_value(&MetaKey::Tests);
+            match maybe_tests {
+                Some(KValue::Map(tests)) => {
+                    self.run_tests(tests)?;
+                }
+                Some(other) => {
+                    return runtime_error!(
+                        "Expected map for tests in module '{module_name}', found '{}'",
+                        other.type_as_string()
+                    )
+                }
+                None => {}
+            }
+        }
+
+        Ok(())
+    }
+
     fn run_set_index(
         &mut self,
         indexable_register: u8,
@@ -2216,39 +2736,52 @@ impl KotoVm {
                 let list_len = list_data.len();
                 match index_value {
                     Number(index) => {
-                        let u_index = usize::from(index);
-                        if index >= 0.0 && u_index < list_len {
-                            list_data[u_index] = value;
-                        } else {
-                            return runtime_error!("Index '{index}' not in List");
-                        }
+                        let index = self.validate_index(index, list_len)?;
+                        list_data[index] = value;
                     }
                     Range(range) => {
-                        for i in range.indices(list_len) {
+                        let Some(range) = range.to_index_range() else {
+                            return invalid_index_range(&range);
+                        };
+                        for i in range.stepped_indices(list_len) {
                             list_data[i] = value.clone();
                         }
                     }
                     unexpected => return type_error("index", &unexpected),
                 }
             }
+            Map(map) => match map.get_meta_value(&MetaKey::SetIndex) {
+                Some(set_index) => {
+                    self.call_instance_function(Map(map), set_index, &[index_value, value])?;
+                }
+                None => {
+                    let key = self.make_value_key(index_value)?;
+                    map.data_mut().insert(key, value)?;
+                }
+            },
+            Object(o) => o.try_borrow_mut()?.set_index(&index_value, value)?,
             unexpected => return type_error("a mutable indexable value", &unexpected),
         };
 
         Ok(())
     }
 
-    fn validate_index(&self, n: KNumber, size: Option<usize>) -> Result<usize> {
-        let index = usize::from(n);
+    // Converts a number into an index for a container with the given size
+    //
+    // Negative indices count back from the end of the container.
+    fn validate_index(&self, n: KNumber, size: usize) -> Result<usize> {
+        let index = n.as_i64();
+        let index = if index < 0 {
+            index + size as i64
+        } else {
+            index
+        };
 
-        if n < 0.0 {
-            return runtime_error!("Negative indices aren't allowed ('{n}')");
-        } else if let Some(size) = size {
-            if index >= size {
-                return runtime_error!("Index out of bounds - index: {n}, size: {size}");
-            }
+        if index < 0 || index as usize >= size {
+            return runtime_error!("Index out of bounds - index: {n}, size: {size}");
         }
 
-        Ok(index)
+        Ok(index as usize)
     }
 
     fn run_index(
@@ -2261,28 +2794,49 @@ impl KotoVm {
         use KValue::*;
 
         let value = self.clone_register(value_register);
-        let index = self.clone_register(index_register);
+        let index = match (&value, self.clone_register(index_register)) {
+            (List(_) | Tuple(_) | Str(_), Range(range)) => match range.to_index_range() {
+                Some(range) => Range(range),
+                None => return invalid_index_range(&range),
+            },
+            (_, index) => index,
+        };
 
         match (&value, index) {
             (List(l), Number(n)) => {
-                let index = self.validate_index(n, Some(l.len()))?;
+                let index = self.validate_index(n, l.len())?;
                 self.set_register(result_register, l.data()[index].clone());
             }
-            (List(l), Range(range)) => self.set_register(
+            (List(l), Range(range)) if range.step() == 1 => self.set_register(
                 result_register,
                 List(KList::from_slice(&l.data()[range.indices(l.len())])),
             ),
+            (List(l), Range(range)) => {
+                let data = l.data();
+                let result = range
+                    .stepped_indices(data.len())
+                    .map(|i| data[i].clone())
+                    .collect::<ValueVec>();
+                self.set_register(result_register, List(KList::with_data(result)));
+            }
             (Tuple(t), Number(n)) => {
-                let index = self.validate_index(n, Some(t.len()))?;
+                let index = self.validate_index(n, t.len())?;
                 self.set_register(result_register, t[index].clone());
             }
-            (Tuple(t), Range(range)) => {
+            (Tuple(t), Range(range)) if range.step() == 1 => {
                 // Safety: The tuple's length is passed into range.indices, so the range is valid
                 let result = t.make_sub_tuple(range.indices(t.len())).unwrap();
                 self.set_register(result_register, Tuple(result))
             }
+            (Tuple(t), Range(range)) => {
+                let result = range
+                    .stepped_indices(t.len())
+                    .map(|i| t[i].clone())
+                    .collect::<Vec<_>>();
+                self.set_register(result_register, Tuple(result.into()))
+            }
             (Str(s), Number(n)) => {
-                let index = self.validate_index(n, Some(s.len()))?;
+                let index = self.validate_index(n, s.len())?;
                 let Some(result) = s.with_bounds(index..index + 1) else {
                     return runtime_error!(
                         "indexing with ({index}) would result in invalid UTF-8 data"
@@ -2290,6 +2844,24 @@ impl KotoVm {
                 };
                 self.set_register(result_register, Str(result));
             }
+            (Str(s), Range(range)) if range.step() != 1 => {
+                // The range's bounds are byte indices, while the step is taken over the
+                // slice's graphemes, so that multi-byte characters are kept intact.
+                let Some(slice) = s.with_bounds(range.indices(s.len())) else {
+                    return runtime_error!(
+                        "indexing with ({range}) would result in invalid UTF-8 data"
+                    );
+                };
+                let step = range.step().as_i64();
+                let step_size = step.unsigned_abs() as usize;
+                let graphemes = slice.graphemes(true);
+                let result: String = if step > 0 {
+                    graphemes.step_by(step_size).collect()
+                } else {
+                    graphemes.rev().step_by(step_size).collect()
+                };
+                self.set_register(result_register, Str(result.into()));
+            }
             (Str(s), Range(range)) => {
                 let Some(result) = s.with_bounds(range.indices(s.len())) else {
                     return runtime_error!(
@@ -2325,12 +2897,12 @@ impl KotoVm {
         key_register: u8,
         value_register: u8,
     ) -> Result<()> {
-        let key = ValueKey::try_from(self.clone_register(key_register))?;
+        let key = self.make_value_key(self.clone_register(key_register))?;
         let value = self.clone_register(value_register);
 
         match self.get_register_mut(map_register) {
             KValue::Map(map) => {
-                map.data_mut().insert(key, value);
+                map.data_mut().insert(key, value)?;
                 Ok(())
             }
             KValue::Object(o) => {
@@ -2446,34 +3018,52 @@ impl KotoVm {
             List(_) => core_op!(list, true),
             Number(_) => core_op!(number, false),
             Range(_) => core_op!(range, true),
+            Set(_) => core_op!(set, true),
             Str(_) => core_op!(string, true),
             Tuple(_) => core_op!(tuple, true),
             Iterator(_) => core_op!(iterator, false),
             Map(map) => {
                 let mut lookup_map = map.clone();
                 let mut access_result = None;
+                // The first @access function found in the map or its bases
+                let mut access_fn = None;
                 while access_result.is_none() {
                     let maybe_value = lookup_map.get(&key);
                     match maybe_value {
                         Some(value) => access_result = Some(value),
                         // Fallback to the map module when there's no metamap
                         None if lookup_map.meta_map().is_none() => {
-                            core_op!(map, true);
-                            return Ok(());
+                            if access_fn.is_none() {
+                                core_op!(map, true);
+                                return Ok(());
+                            }
+                            access_result = self
+                                .context
+                                .core_lib
+                                .map
+                                .get(&key)
+                                .or_else(|| self.context.core_lib.iterator.get(&key));
+                            break;
+                        }
+                        _ => {
+                            if access_fn.is_none() {
+                                access_fn = lookup_map.get_meta_value(&MetaKey::Access);
+                            }
+
+                            match lookup_map.get_meta_value(&MetaKey::Named(key_string.clone())) {
+                                Some(value) => access_result = Some(value),
+                                None => match lookup_map.get_meta_value(&MetaKey::Base) {
+                                    Some(Map(base)) => {
+                                        // Attempt the lookup again with the base map
+                                        lookup_map = base;
+                                    }
+                                    Some(unexpected) => {
+                                        return type_error("Map as base value", &unexpected)
+                                    }
+                                    None => break,
+                                },
+                            }
                         }
-                        _ => match lookup_map.get_meta_value(&MetaKey::Named(key_string.clone())) {
-                            Some(value) => access_result = Some(value),
-                            None => match lookup_map.get_meta_value(&MetaKey::Base) {
-                                Some(Map(base)) => {
-                                    // Attempt the lookup again with the base map
-                                    lookup_map = base;
-                                }
-                                Some(unexpected) => {
-                                    return type_error("Map as base value", &unexpected)
-                                }
-                                None => break,
-                            },
-                        },
                     }
                 }
 
@@ -2482,12 +3072,18 @@ impl KotoVm {
                     && (map.contains_meta_key(&UnaryOp::Iterator.into())
                         || map.contains_meta_key(&UnaryOp::Next.into()))
                 {
-                    access_result = Some(self.get_core_op(
-                        &key,
-                        &self.context.core_lib.iterator,
-                        false,
-                        &accessed_value.type_as_string(),
-                    )?);
+                    access_result = self.context.core_lib.iterator.get(&key);
+                }
+
+                // @access fallback?
+                if access_result.is_none() {
+                    if let Some(access_fn) = access_fn {
+                        access_result = Some(self.call_instance_function(
+                            accessed_value.clone(),
+                            access_fn,
+                            &[key_string.into()],
+                        )?);
+                    }
                 }
 
                 let Some(value) = access_result else {
@@ -2509,12 +3105,12 @@ impl KotoVm {
 
                 // Iterator fallback?
                 if result.is_none() && !matches!(o.is_iterable(), IsIterable::NotIterable) {
-                    result = Some(self.get_core_op(
-                        &key,
-                        &self.context.core_lib.iterator,
-                        false,
-                        &o.type_string(),
-                    )?);
+                    result = self.context.core_lib.iterator.get(&key);
+                }
+
+                // Computed entries fallback?
+                if result.is_none() {
+                    result = o.access(&key_string);
                 }
 
                 if let Some(result) = result {
@@ -2792,6 +3388,17 @@ impl KotoVm {
         }
     }
 
+    fn run_check_type(&self, value_register: u8, type_constant: ConstantIndex) -> Result<()> {
+        let value = self.get_register(value_register);
+        let expected_type = self.get_constant_str(type_constant);
+
+        if value_matches_type_hint(value, expected_type) {
+            Ok(())
+        } else {
+            type_error(&format!("'{expected_type}'"), value)
+        }
+    }
+
     fn get_value_size(&mut self, value_register: u8) -> Result<usize> {
         match self.run_unary_op(UnaryOp::Size, self.clone_register(value_register))? {
             KValue::Number(n) => Ok(n.into()),
@@ -2834,73 +3441,21 @@ impl KotoVm {
         format_options: &Option<StringFormatOptions>,
     ) -> Result<()> {
         let value = self.clone_register(value_register);
-        let value_is_number = matches!(&value, KValue::Number(_));
-
-        // Render the value as a string, applying the precision option if specified
-        let precision = format_options.and_then(|options| options.precision);
-        let rendered = match value {
-            KValue::Number(n) => match precision {
-                Some(precision) if n.is_f64() || n.is_i64_in_f64_range() => {
-                    format!("{:.*}", precision as usize, f64::from(n))
-                }
-                _ => n.to_string(),
-            },
-            other => match self.run_unary_op(UnaryOp::Display, other)? {
-                KValue::Str(rendered) => match precision {
-                    Some(precision) => {
-                        // precision acts as a maximum width for non-number values
-                        let mut truncated =
-                            String::with_capacity((precision as usize).min(rendered.len()));
-                        for grapheme in rendered.graphemes(true).take(precision as usize) {
-                            truncated.push_str(grapheme);
-                        }
-                        truncated
-                    }
-                    None => rendered.to_string(),
-                },
-                other => return type_error("String", &other),
-            },
-        };
 
-        // Apply other formatting options to the rendered string
         let result = match format_options {
             Some(options) => {
-                let len = rendered.graphemes(true).count();
-                let min_width = options.min_width.unwrap_or(0) as usize;
-                if len < min_width {
-                    let fill = match options.fill_character {
-                        Some(constant) => self.koto_string_from_constant(constant),
-                        None => KString::from(" "),
-                    };
-                    let fill_chars = min_width - len;
-
-                    match options.alignment {
-                        StringAlignment::Default => {
-                            if value_is_number {
-                                // Right-alignment by default for numbers
-                                fill.repeat(fill_chars) + &rendered
-                            } else {
-                                // Left alignment by default for non-numbers
-                                rendered + &fill.repeat(fill_chars)
-                            }
-                        }
-                        StringAlignment::Left => rendered + &fill.repeat(fill_chars),
-                        StringAlignment::Center => {
-                            let half_fill_chars = fill_chars as f32 / 2.0;
-                            format!(
-                                "{}{}{}",
-                                fill.repeat(half_fill_chars.floor() as usize),
-                                rendered,
-                                fill.repeat(half_fill_chars.ceil() as usize),
-                            )
-                        }
-                        StringAlignment::Right => fill.repeat(fill_chars) + &rendered,
-                    }
-                } else {
-                    rendered
-                }
-            }
-            None => rendered,
+                let fill = options
+                    .fill_character
+                    .map(|constant| self.koto_string_from_constant(constant));
+                format_value(self, value, options, fill.as_deref())?
+            }
+            None => match value {
+                KValue::Number(n) => n.to_string(),
+                other => match self.run_unary_op(UnaryOp::Display, other)? {
+                    KValue::Str(rendered) => rendered.to_string(),
+                    other => return type_error("String", &other),
+                },
+            },
         };
 
         // Add the result to the string builder
@@ -3133,6 +3688,32 @@ fn binary_op_error(lhs: &KValue, rhs: &KValue, op: BinaryOp) -> Result<()> {
     })
 }
 
+// Returns true if the value matches the type named in a type hint
+//
+// The value's type name is matched, along with the following special cases:
+//   - `Any` matches all values.
+//   - `Number` matches floats and integers.
+//   - `Map` matches all maps, including maps that have a custom `@type`.
+//   - `Callable` and `Iterable` match values that can be called or iterated over.
+fn value_matches_type_hint(value: &KValue, type_hint: &str) -> bool {
+    match type_hint {
+        "Any" => true,
+        "Number" => matches!(value, KValue::Number(_)),
+        "Map" => matches!(value, KValue::Map(_)),
+        "Callable" => value.is_callable(),
+        "Iterable" => value.is_iterable(),
+        _ => value.type_as_string().as_str() == type_hint,
+    }
+}
+
+// The error thrown when a range can't be used as an index, see KRange::to_index_range
+fn invalid_index_range<T>(range: &KRange) -> Result<T> {
+    runtime_error!(
+        "Only integer ranges and float ranges with whole number steps can be used as indices \
+         (found '{range}')"
+    )
+}
+
 fn signed_index_to_unsigned(index: i8, size: usize) -> usize {
     if index < 0 {
         size - (index as isize).unsigned_abs().min(size)
@@ -3141,6 +3722,33 @@ fn signed_index_to_unsigned(index: i8, size: usize) -> usize {
     }
 }
 
+// Returns the string's character if it contains a single character, used for character ranges
+fn single_char(s: &str) -> Option<char> {
+    let mut chars = s.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) => Some(c),
+        _ => None,
+    }
+}
+
+// Combines the errors that occurred while reloading modules
+//
+// A single error is returned unchanged, while multiple errors are combined into one message.
+fn combine_reload_errors(mut errors: Vec<(impl AsRef<Path>, Error)>) -> Result<()> {
+    match errors.len() {
+        0 => Ok(()),
+        1 => Err(errors.remove(0).1),
+        count => {
+            let messages = errors
+                .iter()
+                .map(|(path, error)| format!("- '{}': {error}", path.as_ref().display()))
+                .collect::<Vec<_>>()
+                .join("\n");
+            runtime_error!("Failed to reload {count} modules:\n{messages}")
+        }
+    }
+}
+
 // Used when calling iterator.copy on a generator
 //
 // The idea here is to clone the VM, and then scan through the value stack to make copies of
@@ -3225,6 +3833,8 @@ struct Frame {
     //   - an external function is calling back into the VM with a functor
     //   - a module is being imported
     pub execution_barrier: bool,
+    // True if execution can be suspended while awaiting a future, see Vm::run_async
+    pub allow_await_suspension: bool,
 }
 
 impl Frame {
@@ -3236,6 +3846,7 @@ impl Frame {
             return_instruction_ip: 0,
             catch_stack: vec![],
             execution_barrier: false,
+            allow_await_suspension: false,
         }
     }
 }
@@ -3325,6 +3936,97 @@ impl ExecutionTimeout {
     }
 }
 
+/// A [Future] that runs a chunk in a [KotoVm], see [KotoVm::run_async]
+pub struct AsyncRun<'a> {
+    vm: &'a mut KotoVm,
+    state: AsyncRunState,
+}
+
+enum AsyncRunState {
+    NotStarted(Ptr<Chunk>),
+    Running {
+        result_register: u8,
+    },
+    Awaiting {
+        result_register: u8,
+        future: KFuture,
+    },
+    Finished,
+}
+
+impl Future for AsyncRun<'_> {
+    type Output = Result<KValue>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+
+        loop {
+            let result_register = match std::mem::replace(&mut this.state, AsyncRunState::Finished)
+            {
+                AsyncRunState::NotStarted(chunk) => this.vm.start_async_run(chunk),
+                AsyncRunState::Running { result_register } => result_register,
+                AsyncRunState::Awaiting {
+                    result_register,
+                    mut future,
+                } => {
+                    if Pin::new(&mut future).poll(cx).is_pending() {
+                        this.state = AsyncRunState::Awaiting {
+                            result_register,
+                            future,
+                        };
+                        return Poll::Pending;
+                    }
+                    result_register
+                }
+                AsyncRunState::Finished => {
+                    return Poll::Ready(runtime_error!("The async run has already finished"));
+                }
+            };
+
+            // Set the state in case execution panics, so that the VM can be cleaned up on drop
+            this.state = AsyncRunState::Running { result_register };
+
+            let result = this.vm.execute_instructions();
+
+            match (result, &this.vm.execution_state) {
+                (Ok(KValue::Object(o)), ExecutionState::Suspended) if o.is_a::<KFuture>() => {
+                    let future = o.cast::<KFuture>()?.clone();
+                    this.state = AsyncRunState::Awaiting {
+                        result_register,
+                        future,
+                    };
+                }
+                (Ok(_), ExecutionState::Suspended) => {
+                    this.vm.cancel_async_run(result_register);
+                    this.state = AsyncRunState::Finished;
+                    return Poll::Ready(runtime_error!("Unexpected yield during an async run"));
+                }
+                (result, _) => {
+                    if result.is_err() {
+                        // Errors stop at the async root frame, which then needs to be popped
+                        let _ = this.vm.pop_frame(KValue::Null);
+                    }
+                    this.vm.truncate_registers(result_register);
+                    this.state = AsyncRunState::Finished;
+                    return Poll::Ready(result);
+                }
+            }
+        }
+    }
+}
+
+impl Drop for AsyncRun<'_> {
+    fn drop(&mut self) {
+        match self.state {
+            AsyncRunState::Running { result_register }
+            | AsyncRunState::Awaiting {
+                result_register, ..
+            } => self.vm.cancel_async_run(result_register),
+            _ => {}
+        }
+    }
+}
+
 /// An output value from [KotoVm::continue_running], either from a `return` or `yield` expression
 #[allow(missing_docs)]
 pub enum ReturnOrYield {
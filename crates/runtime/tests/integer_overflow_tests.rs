@@ -0,0 +1,108 @@
+mod integer_overflow {
+    use koto_bytecode::{CompilerSettings, Loader};
+    use koto_runtime::{prelude::*, Result};
+
+    fn run_script(script: &str, checked_integer_overflow: bool) -> Result<KValue> {
+        let mut vm = KotoVm::with_settings(KotoVmSettings {
+            checked_integer_overflow,
+            ..Default::default()
+        });
+
+        let mut loader = Loader::default();
+        let chunk = match loader.compile_script(script, None, CompilerSettings::default()) {
+            Ok(chunk) => chunk,
+            Err(error) => {
+                panic!("Error while compiling script: {error}");
+            }
+        };
+
+        vm.run(chunk)
+    }
+
+    fn check_script_wraps(script: &str, expected: i64) {
+        match run_script(script, false) {
+            Ok(KValue::Number(n)) if n == expected => {}
+            Ok(unexpected) => panic!("Unexpected result: {unexpected:?}, expected {expected}"),
+            Err(e) => panic!("Unexpected error: {e}"),
+        }
+    }
+
+    fn check_script_overflows(script: &str) {
+        if let Ok(result) = run_script(script, true) {
+            panic!("Script didn't fail as expected, result: {result:?}");
+        }
+    }
+
+    #[test]
+    fn add() {
+        let script = "
+x = 9223372036854775807
+x + 1
+";
+        check_script_wraps(script, i64::MIN);
+        check_script_overflows(script);
+    }
+
+    #[test]
+    fn add_assign() {
+        let script = "
+x = 9223372036854775807
+x += 1
+";
+        check_script_wraps(script, i64::MIN);
+        check_script_overflows(script);
+    }
+
+    #[test]
+    fn subtract() {
+        let script = "
+x = -9223372036854775807
+x - 2
+";
+        check_script_wraps(script, i64::MAX);
+        check_script_overflows(script);
+    }
+
+    #[test]
+    fn multiply() {
+        let script = "
+x = 4611686018427387904
+x * 2
+";
+        check_script_wraps(script, i64::MIN);
+        check_script_overflows(script);
+    }
+
+    #[test]
+    fn negate() {
+        let script = "
+x = -9223372036854775807 - 1
+-x
+";
+        check_script_wraps(script, i64::MIN);
+        check_script_overflows(script);
+    }
+
+    #[test]
+    fn pow() {
+        let script = "
+x = 2
+x.pow 63
+";
+        check_script_wraps(script, i64::MIN);
+        check_script_overflows(script);
+    }
+
+    #[test]
+    fn no_overflow() {
+        let script = "
+x = 9223372036854775806
+x + 1
+";
+        match run_script(script, true) {
+            Ok(KValue::Number(n)) if n == i64::MAX => {}
+            Ok(unexpected) => panic!("Unexpected result: {unexpected:?}"),
+            Err(e) => panic!("Unexpected error: {e}"),
+        }
+    }
+}
@@ -64,6 +64,34 @@ y.next().get()
         }
     }
 
+    mod chunk_by {
+        use super::*;
+
+        #[test]
+        fn make_copy() {
+            let script = "
+x = (1, 1, 2, 3, 3).chunk_by()
+x.next().get() # (1, 1)
+y = copy x
+x.next().get() # (2)
+x.next().get() # (3, 3)
+y.next().get()
+";
+            check_script_output(script, number_tuple(&[2]));
+        }
+
+        #[test]
+        fn mixed_next_and_next_back() {
+            let script = "
+x = (1, 2, 2, 2, 1).chunk_by()
+x.next().get() # (1)
+x.next_back().get() # (1)
+x.next().get()
+";
+            check_script_output(script, number_tuple(&[2, 2, 2]));
+        }
+    }
+
     mod chunks {
         use super::*;
 
@@ -325,6 +353,23 @@ result
         }
     }
 
+    mod scan {
+        use super::*;
+
+        #[test]
+        fn make_copy() {
+            let script = "
+x = (1..10).scan 0, |sum, n| sum + n
+x.next().get() # 1
+y = copy x
+x.next().get() # 3
+x.next().get() # 6
+y.next().get()
+";
+            check_script_output(script, 3);
+        }
+    }
+
     mod skip {
         use super::*;
 
@@ -354,6 +399,24 @@ y.next().get()
         }
     }
 
+    mod unique {
+        use super::*;
+
+        #[test]
+        fn make_copy() {
+            let script = "
+x = (1, 2, 1, 3, 2, 4).unique()
+x.next().get() # 1
+x.next().get() # 2
+y = copy x
+x.next().get() # 3
+x.next().get() # 4
+y.next().get()
+";
+            check_script_output(script, 3);
+        }
+    }
+
     mod windows {
         use super::*;
 
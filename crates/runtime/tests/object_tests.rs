@@ -47,6 +47,13 @@ mod objects {
                 unexpected => type_error_with_slice("TestExternal", unexpected),
             }
         }
+
+        // Keys of the form `plus_N` are computed on demand
+        #[koto_access]
+        fn access(&self, key: &KString) -> Option<KValue> {
+            let n: i64 = key.strip_prefix("plus_")?.parse().ok()?;
+            Some((self.x + n).into())
+        }
     }
 
     macro_rules! arithmetic_op {
@@ -140,6 +147,17 @@ mod objects {
             }
         }
 
+        fn set_index(&mut self, index: &KValue, value: KValue) -> Result<()> {
+            match (index, value) {
+                // Update x so that indexing with the same index returns the assigned value
+                (KValue::Number(index), KValue::Number(value)) => {
+                    self.x = i64::from(value) - i64::from(index);
+                    Ok(())
+                }
+                (_, unexpected) => type_error("Number", &unexpected),
+            }
+        }
+
         fn size(&self) -> Option<usize> {
             Some(self.x.unsigned_abs() as usize)
         }
@@ -216,6 +234,15 @@ mod objects {
             comparison_op!(self, rhs, !=)
         }
 
+        fn hash(&self) -> Option<u64> {
+            Some(self.x as u64)
+        }
+
+        fn close(&mut self) -> Result<()> {
+            self.x = 0;
+            Ok(())
+        }
+
         fn is_iterable(&self) -> IsIterable {
             IsIterable::Iterable
         }
@@ -330,6 +357,25 @@ x.as_number()
 ";
             test_object_script(script, 60);
         }
+
+        #[test]
+        fn computed_entry() {
+            let script = "
+x = make_object 10
+x.plus_32
+";
+            test_object_script(script, 42);
+        }
+
+        #[test]
+        fn computed_entry_with_dynamic_key() {
+            let script = "
+x = make_object 10
+n = 90
+x.'plus_{n}'
+";
+            test_object_script(script, 100);
+        }
     }
 
     mod unary_op {
@@ -592,6 +638,28 @@ x[23]
             test_object_script(script, 123);
         }
 
+        #[test]
+        fn set_index() {
+            let script = "
+x = make_object 100
+x[10] = 20
+x[5]
+";
+            test_object_script(script, 15);
+        }
+
+        #[test]
+        fn map_key() {
+            let script = "
+m = {}
+m[make_object(1)] = 10
+m[make_object(2)] = 20
+m[make_object(1)] = 30
+(size m) + (m.get make_object 1)
+";
+            test_object_script(script, 32);
+        }
+
         #[test]
         fn size() {
             let script = "
@@ -643,6 +711,34 @@ x.foo
         test_object_script(script, 123);
     }
 
+    mod with_block {
+        use super::*;
+
+        #[test]
+        fn object_is_closed() {
+            let script = "
+x = make_object 42
+with x
+  y = x.as_number()
+y + x.as_number()
+";
+            test_object_script(script, 42);
+        }
+
+        #[test]
+        fn object_is_closed_after_early_return() {
+            let script = "
+x = make_object 42
+f = ||
+  with x
+    return x.as_number()
+y = f()
+y + x.as_number()
+";
+            test_object_script(script, 42);
+        }
+    }
+
     mod temporaries {
         use super::*;
 
@@ -153,28 +153,48 @@ x[3] = 3
             use super::*;
 
             #[test]
-            fn list_as_key() {
+            fn function_as_key() {
                 let script = "
 x = {}
-x.insert [1, 2], 'hello'
+x.insert (|| 42), 'hello'
 ";
                 check_script_fails(script);
             }
 
             #[test]
-            fn map_as_key() {
+            fn list_as_key_with_contained_function() {
                 let script = "
 x = {}
-x.insert {foo: 42}, 'hello'
+x.insert [1, || 2], 'hello'
 ";
                 check_script_fails(script);
             }
 
             #[test]
-            fn tuple_as_key_with_contained_list() {
+            fn map_as_key_with_contained_function() {
                 let script = "
 x = {}
-x.insert (1, [2, 3]), 'hello'
+x.insert {foo: || 42}, 'hello'
+";
+                check_script_fails(script);
+            }
+
+            #[test]
+            fn tuple_as_key_with_contained_function() {
+                let script = "
+x = {}
+x.insert (1, [2, || 3]), 'hello'
+";
+                check_script_fails(script);
+            }
+
+            #[test]
+            fn hash_returning_unhashable_value() {
+                let script = "
+x = {}
+key =
+  @hash: || [|| 42]
+x[key] = 'hello'
 ";
                 check_script_fails(script);
             }
@@ -209,6 +229,26 @@ a, b = x
 x =
   @next_back: || 42
 x.reversed().next()
+";
+                check_script_fails(script);
+            }
+
+            #[test]
+            fn with_resource_without_close() {
+                let script = "
+with x = 42
+  x + 1
+";
+                check_script_fails(script);
+            }
+
+            #[test]
+            fn close_with_non_function() {
+                let script = "
+x =
+  @close: 42
+with x
+  x
 ";
                 check_script_fails(script);
             }
@@ -196,6 +196,33 @@ a, b, c
             check_script_output("4..=0", KRange::from(4..=0));
             check_script_output("2 * 2..=3 * 3", KRange::from(4..=9));
         }
+
+        #[test]
+        fn range_with_step() {
+            check_script_output("0..10..2", KRange::from(0..10).with_step(2).unwrap());
+            check_script_output("x = 3\n0..=x..x", KRange::from(0..=3).with_step(3).unwrap());
+        }
+
+        #[test]
+        fn char_range() {
+            check_script_output("'a'..'z'", KRange::with_chars('a', 'z', false));
+            check_script_output(
+                "'a'..='z'..2",
+                KRange::with_chars('a', 'z', true).with_step(2).unwrap(),
+            );
+        }
+
+        #[test]
+        fn float_range() {
+            check_script_output(
+                "0.5..1.5..0.25",
+                KRange::with_floats(0.5, 1.5, false, 0.25).unwrap(),
+            );
+            check_script_output(
+                "0..=1..0.5",
+                KRange::with_floats(0.0, 1.0, true, 0.5).unwrap(),
+            );
+        }
     }
 
     mod tuples {
@@ -2734,6 +2761,15 @@ x = ('foo', 'bar')
         #[test_case("'{'hello':.2}'", "he"; "precision with string")]
         #[test_case("'{'hello':10}'", "hello     "; "min width with string")]
         #[test_case("'{'hello':~>4.2}'", "~~he"; "right-aligned truncated string")]
+        #[test_case("'{42:+}'", "+42"; "sign with positive integer")]
+        #[test_case("'{-42:+06}'", "-00042"; "zero-padded negative integer with sign")]
+        #[test_case("'{1234567.891:,.2}'", "1,234,567.89"; "thousands separator with float")]
+        #[test_case("'{255:x}'", "ff"; "lowercase hex")]
+        #[test_case("'{-255:#>6X}'", "###-FF"; "right-aligned negative uppercase hex")]
+        #[test_case("'{5:08b}'", "00000101"; "zero-padded binary")]
+        #[test_case("'{8:o}'", "10"; "octal")]
+        #[test_case("'{0.00123:.2e}'", "1.23e-3"; "lowercase exponent with precision")]
+        #[test_case("'{1500:E}'", "1.5E3"; "uppercase exponent")]
         fn formatted_expression(input: &str, expected: &str) {
             check_script_output(input, expected);
         }
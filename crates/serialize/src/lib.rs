@@ -42,6 +42,13 @@ impl<'a> Serialize for SerializableValue<'a> {
                 }
                 seq.end()
             }
+            KValue::Set(set) => {
+                let mut seq = s.serialize_seq(Some(set.len()))?;
+                for element in set.data().iter() {
+                    seq.serialize_element(&SerializableValue(element.value()))?;
+                }
+                seq.end()
+            }
             KValue::Str(string) => s.serialize_str(string),
             // TODO, is it ok to do nothing for non-fundamental types, e.g. External Values?
             _ => s.serialize_unit(),
@@ -12,9 +12,11 @@ struct ExampleTestRunner {
 }
 
 impl ExampleTestRunner {
-    fn new(mut prelude_entries: ValueMap) -> Self {
+    fn new(prelude_entries: ValueMap) -> Self {
         let (vm, output) = OutputCapture::make_vm_with_output_capture();
-        vm.prelude().data_mut().extend(prelude_entries.drain(..));
+        for (key, value) in prelude_entries.iter() {
+            vm.prelude().insert(key.clone(), value.clone());
+        }
 
         Self {
             loader: Loader::default(),
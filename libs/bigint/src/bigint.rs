@@ -0,0 +1,249 @@
+use crate::integer::Integer;
+use koto_runtime::{derive::*, prelude::*, Result};
+use std::{
+    cmp::Ordering,
+    fmt,
+    hash::{Hash, Hasher},
+};
+
+macro_rules! bigint_arithmetic_op {
+    ($lhs:expr, $rhs:expr, $op:tt) => {{
+        let result: Integer = &$lhs $op &$rhs;
+        Ok(BigInt(result).into())
+    }};
+}
+
+macro_rules! bigint_compound_assign_op {
+    ($self:ident, $rhs:expr, $op:tt) => {{
+        $self.0 = &$self.0 $op &integer_from_value($rhs)?;
+        Ok(())
+    }};
+}
+
+macro_rules! bigint_comparison_op {
+    ($self:ident, $rhs:expr, $($ordering:pat_param)|+) => {
+        Ok(matches!($self.compare($rhs)?, Some($($ordering)|+)))
+    };
+}
+
+/// An arbitrary-precision integer
+#[derive(Clone, Debug, PartialEq, Eq, KotoCopy, KotoType)]
+pub struct BigInt(Integer);
+
+#[koto_impl(runtime = koto_runtime)]
+impl BigInt {
+    /// Returns a reference to the wrapped integer
+    pub fn inner(&self) -> &Integer {
+        &self.0
+    }
+
+    #[koto_method]
+    fn abs(&self) -> KValue {
+        Self(self.0.abs()).into()
+    }
+
+    #[koto_method]
+    fn is_negative(&self) -> KValue {
+        self.0.is_negative().into()
+    }
+
+    #[koto_method]
+    fn pow(&self, args: &[KValue]) -> Result<KValue> {
+        match args {
+            [KValue::Number(n)] if *n >= 0 && n.is_i64() => match u32::try_from(i64::from(n)) {
+                Ok(exponent) => Ok(Self(self.0.pow(exponent)).into()),
+                Err(_) => runtime_error!("the exponent {n} is too large"),
+            },
+            unexpected => type_error_with_slice("a non-negative integer", unexpected),
+        }
+    }
+
+    #[koto_method]
+    fn to_number(&self) -> KValue {
+        match self.0.to_i64() {
+            Some(n) => n.into(),
+            None => self.0.to_f64().into(),
+        }
+    }
+
+    #[koto_method]
+    fn to_string(&self, args: &[KValue]) -> Result<KValue> {
+        match args {
+            [] => Ok(self.0.to_string().into()),
+            [KValue::Number(radix)] => {
+                let radix = parse_radix(radix)?;
+                Ok(self.0.to_str_radix(radix).into())
+            }
+            unexpected => type_error_with_slice("an optional radix", unexpected),
+        }
+    }
+
+    // Compares the integer with a BigInt or a Number
+    //
+    // None is returned when comparing against NaN.
+    fn compare(&self, rhs: &KValue) -> Result<Option<Ordering>> {
+        match rhs {
+            KValue::Number(KNumber::F64(n)) if n.fract() != 0.0 || !n.is_finite() => {
+                Ok(self.0.to_f64().partial_cmp(n))
+            }
+            _ => Ok(Some(self.0.cmp(&integer_from_value(rhs)?))),
+        }
+    }
+}
+
+impl KotoObject for BigInt {
+    fn display(&self, ctx: &mut DisplayContext) -> Result<()> {
+        ctx.append(self.0.to_string());
+        Ok(())
+    }
+
+    fn negate(&self, _vm: &mut KotoVm) -> Result<KValue> {
+        Ok(Self(-&self.0).into())
+    }
+
+    fn add(&self, rhs: &KValue) -> Result<KValue> {
+        bigint_arithmetic_op!(self.0, integer_from_value(rhs)?, +)
+    }
+
+    fn subtract(&self, rhs: &KValue) -> Result<KValue> {
+        bigint_arithmetic_op!(self.0, integer_from_value(rhs)?, -)
+    }
+
+    fn multiply(&self, rhs: &KValue) -> Result<KValue> {
+        bigint_arithmetic_op!(self.0, integer_from_value(rhs)?, *)
+    }
+
+    fn divide(&self, rhs: &KValue) -> Result<KValue> {
+        divide(&self.0, &integer_from_value(rhs)?)
+    }
+
+    fn remainder(&self, rhs: &KValue) -> Result<KValue> {
+        remainder(&self.0, &integer_from_value(rhs)?)
+    }
+
+    fn add_assign(&mut self, rhs: &KValue) -> Result<()> {
+        bigint_compound_assign_op!(self, rhs, +)
+    }
+
+    fn subtract_assign(&mut self, rhs: &KValue) -> Result<()> {
+        bigint_compound_assign_op!(self, rhs, -)
+    }
+
+    fn multiply_assign(&mut self, rhs: &KValue) -> Result<()> {
+        bigint_compound_assign_op!(self, rhs, *)
+    }
+
+    fn divide_assign(&mut self, rhs: &KValue) -> Result<()> {
+        self.0 = div_rem(&self.0, &integer_from_value(rhs)?)?.0;
+        Ok(())
+    }
+
+    fn remainder_assign(&mut self, rhs: &KValue) -> Result<()> {
+        self.0 = div_rem(&self.0, &integer_from_value(rhs)?)?.1;
+        Ok(())
+    }
+
+    fn add_rhs(&self, lhs: &KValue) -> Result<KValue> {
+        bigint_arithmetic_op!(integer_from_value(lhs)?, self.0, +)
+    }
+
+    fn subtract_rhs(&self, lhs: &KValue) -> Result<KValue> {
+        bigint_arithmetic_op!(integer_from_value(lhs)?, self.0, -)
+    }
+
+    fn multiply_rhs(&self, lhs: &KValue) -> Result<KValue> {
+        bigint_arithmetic_op!(integer_from_value(lhs)?, self.0, *)
+    }
+
+    fn divide_rhs(&self, lhs: &KValue) -> Result<KValue> {
+        divide(&integer_from_value(lhs)?, &self.0)
+    }
+
+    fn remainder_rhs(&self, lhs: &KValue) -> Result<KValue> {
+        remainder(&integer_from_value(lhs)?, &self.0)
+    }
+
+    fn less(&self, rhs: &KValue) -> Result<bool> {
+        bigint_comparison_op!(self, rhs, Ordering::Less)
+    }
+
+    fn less_or_equal(&self, rhs: &KValue) -> Result<bool> {
+        bigint_comparison_op!(self, rhs, Ordering::Less | Ordering::Equal)
+    }
+
+    fn greater(&self, rhs: &KValue) -> Result<bool> {
+        bigint_comparison_op!(self, rhs, Ordering::Greater)
+    }
+
+    fn greater_or_equal(&self, rhs: &KValue) -> Result<bool> {
+        bigint_comparison_op!(self, rhs, Ordering::Greater | Ordering::Equal)
+    }
+
+    fn equal(&self, rhs: &KValue) -> Result<bool> {
+        bigint_comparison_op!(self, rhs, Ordering::Equal)
+    }
+
+    fn not_equal(&self, rhs: &KValue) -> Result<bool> {
+        self.equal(rhs).map(|result| !result)
+    }
+
+    fn hash(&self) -> Option<u64> {
+        let mut hasher = KotoHasher::default();
+        self.0.hash(&mut hasher);
+        Some(hasher.finish())
+    }
+}
+
+impl From<Integer> for BigInt {
+    fn from(n: Integer) -> Self {
+        Self(n)
+    }
+}
+
+impl From<BigInt> for KValue {
+    fn from(n: BigInt) -> Self {
+        KObject::from(n).into()
+    }
+}
+
+impl fmt::Display for BigInt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+/// Converts a BigInt or an integral Number into an [Integer]
+pub fn integer_from_value(value: &KValue) -> Result<Integer> {
+    match value {
+        KValue::Number(KNumber::I64(n)) => Ok(Integer::from(*n)),
+        KValue::Number(KNumber::F64(n)) => match Integer::from_f64(*n) {
+            Some(result) => Ok(result),
+            None => runtime_error!("expected an integer, found '{n}'"),
+        },
+        KValue::Object(o) if o.is_a::<BigInt>() => Ok(o.cast::<BigInt>()?.0.clone()),
+        unexpected => type_error("a BigInt or Number", unexpected),
+    }
+}
+
+/// Checks that a Number is a valid radix, in the range `2..=36`
+pub fn parse_radix(radix: &KNumber) -> Result<u32> {
+    match radix {
+        KNumber::I64(n @ 2..=36) => Ok(*n as u32),
+        _ => runtime_error!("the radix must be an integer in the range 2..=36 (found '{radix}')"),
+    }
+}
+
+fn div_rem(lhs: &Integer, rhs: &Integer) -> Result<(Integer, Integer)> {
+    match lhs.div_rem(rhs) {
+        Some(result) => Ok(result),
+        None => runtime_error!("division by zero"),
+    }
+}
+
+fn divide(lhs: &Integer, rhs: &Integer) -> Result<KValue> {
+    div_rem(lhs, rhs).map(|(quotient, _)| BigInt(quotient).into())
+}
+
+fn remainder(lhs: &Integer, rhs: &Integer) -> Result<KValue> {
+    div_rem(lhs, rhs).map(|(_, remainder)| BigInt(remainder).into())
+}
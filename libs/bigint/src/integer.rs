@@ -0,0 +1,485 @@
+use std::{cmp::Ordering, fmt, ops};
+
+/// An arbitrary-precision signed integer
+///
+/// The magnitude is stored as little-endian base 2^32 digits, with no trailing zero digits.
+/// Zero is represented by an empty list of digits, and is never negative.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Integer {
+    negative: bool,
+    digits: Vec<u32>,
+}
+
+impl Integer {
+    /// Returns true if the integer is zero
+    pub fn is_zero(&self) -> bool {
+        self.digits.is_empty()
+    }
+
+    /// Returns true if the integer is less than zero
+    pub fn is_negative(&self) -> bool {
+        self.negative
+    }
+
+    /// Returns the absolute value of the integer
+    #[must_use]
+    pub fn abs(&self) -> Self {
+        Self {
+            negative: false,
+            digits: self.digits.clone(),
+        }
+    }
+
+    /// Returns the quotient and remainder of dividing by `divisor`
+    ///
+    /// The quotient is truncated towards zero, and the remainder has the same sign as `self`.
+    ///
+    /// None is returned if the divisor is zero.
+    pub fn div_rem(&self, divisor: &Self) -> Option<(Self, Self)> {
+        if divisor.is_zero() {
+            return None;
+        }
+
+        let (quotient, remainder) = div_rem_digits(&self.digits, &divisor.digits);
+        Some((
+            Self::with_sign(self.negative != divisor.negative, quotient),
+            Self::with_sign(self.negative, remainder),
+        ))
+    }
+
+    /// Raises the integer to the power of `exponent`
+    #[must_use]
+    pub fn pow(&self, mut exponent: u32) -> Self {
+        let mut result = Self::from(1);
+        let mut base = self.clone();
+
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                result = &result * &base;
+            }
+            exponent >>= 1;
+            if exponent > 0 {
+                base = &base * &base;
+            }
+        }
+
+        result
+    }
+
+    /// Parses an integer from a string of digits in the given radix
+    ///
+    /// The digits can be preceded by a `+` or `-` sign, and `_` separators are ignored.
+    ///
+    /// None is returned if the string is invalid, or if the radix isn't in the range `2..=36`.
+    pub fn from_str_radix(s: &str, radix: u32) -> Option<Self> {
+        if !(2..=36).contains(&radix) {
+            return None;
+        }
+
+        let (negative, s) = match s.as_bytes().first() {
+            Some(b'-') => (true, &s[1..]),
+            Some(b'+') => (false, &s[1..]),
+            _ => (false, s),
+        };
+
+        let mut digits = Vec::new();
+        let mut digit_count = 0;
+
+        for c in s.chars().filter(|c| *c != '_') {
+            let digit = c.to_digit(radix)?;
+            mul_add_small(&mut digits, radix, digit);
+            digit_count += 1;
+        }
+
+        if digit_count == 0 {
+            return None;
+        }
+
+        Some(Self::with_sign(negative, digits))
+    }
+
+    /// Returns a string representation of the integer in the given radix
+    ///
+    /// Digits above 9 are rendered as lowercase letters.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the radix isn't in the range `2..=36`.
+    pub fn to_str_radix(&self, radix: u32) -> String {
+        assert!((2..=36).contains(&radix));
+
+        if self.is_zero() {
+            return "0".into();
+        }
+
+        // Divide by the largest power of the radix that fits in a u32,
+        // producing a chunk of output digits for each division.
+        let mut chunk_divisor = radix;
+        let mut chunk_size = 1;
+        while let Some(next) = chunk_divisor.checked_mul(radix) {
+            chunk_divisor = next;
+            chunk_size += 1;
+        }
+
+        let mut digits = self.digits.clone();
+        let mut output = Vec::new();
+
+        while !digits.is_empty() {
+            let mut chunk = div_rem_small(&mut digits, chunk_divisor);
+            for _ in 0..chunk_size {
+                output.push(std::char::from_digit(chunk % radix, radix).unwrap());
+                chunk /= radix;
+                if digits.is_empty() && chunk == 0 {
+                    break;
+                }
+            }
+        }
+
+        if self.negative {
+            output.push('-');
+        }
+
+        output.iter().rev().collect()
+    }
+
+    /// Returns the integer as an i64, or None if it's out of the i64 range
+    pub fn to_i64(&self) -> Option<i64> {
+        if self.digits.len() > 2 {
+            return None;
+        }
+
+        let magnitude = self
+            .digits
+            .iter()
+            .rev()
+            .fold(0u64, |result, digit| (result << 32) | *digit as u64);
+
+        if self.negative {
+            0i64.checked_sub_unsigned(magnitude)
+        } else {
+            i64::try_from(magnitude).ok()
+        }
+    }
+
+    /// Returns the closest f64 value to the integer
+    pub fn to_f64(&self) -> f64 {
+        let magnitude = self
+            .digits
+            .iter()
+            .rev()
+            .fold(0.0, |result, digit| result * 4294967296.0 + *digit as f64);
+
+        if self.negative {
+            -magnitude
+        } else {
+            magnitude
+        }
+    }
+
+    /// Makes an integer from a float, returning None if the float isn't a finite whole number
+    pub fn from_f64(n: f64) -> Option<Self> {
+        if !n.is_finite() || n.fract() != 0.0 {
+            return None;
+        }
+
+        let negative = n < 0.0;
+        let mut magnitude = n.abs();
+        let mut digits = Vec::new();
+
+        while magnitude >= 1.0 {
+            let digit = magnitude % 4294967296.0;
+            digits.push(digit as u32);
+            magnitude = ((magnitude - digit) / 4294967296.0).trunc();
+        }
+
+        Some(Self::with_sign(negative, digits))
+    }
+
+    // Makes a normalized integer from a sign and digits
+    fn with_sign(negative: bool, mut digits: Vec<u32>) -> Self {
+        trim(&mut digits);
+        Self {
+            negative: negative && !digits.is_empty(),
+            digits,
+        }
+    }
+}
+
+impl From<i64> for Integer {
+    fn from(n: i64) -> Self {
+        let magnitude = n.unsigned_abs();
+        let digits = vec![magnitude as u32, (magnitude >> 32) as u32];
+        Self::with_sign(n < 0, digits)
+    }
+}
+
+impl fmt::Display for Integer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_str_radix(10))
+    }
+}
+
+impl Ord for Integer {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.negative, other.negative) {
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+            (false, false) => cmp_digits(&self.digits, &other.digits),
+            (true, true) => cmp_digits(&other.digits, &self.digits),
+        }
+    }
+}
+
+impl PartialOrd for Integer {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl ops::Neg for &Integer {
+    type Output = Integer;
+
+    fn neg(self) -> Integer {
+        Integer::with_sign(!self.negative, self.digits.clone())
+    }
+}
+
+impl ops::Add for &Integer {
+    type Output = Integer;
+
+    fn add(self, other: &Integer) -> Integer {
+        if self.negative == other.negative {
+            return Integer::with_sign(self.negative, add_digits(&self.digits, &other.digits));
+        }
+
+        match cmp_digits(&self.digits, &other.digits) {
+            Ordering::Equal => Integer::default(),
+            Ordering::Greater => {
+                Integer::with_sign(self.negative, sub_digits(&self.digits, &other.digits))
+            }
+            Ordering::Less => {
+                Integer::with_sign(other.negative, sub_digits(&other.digits, &self.digits))
+            }
+        }
+    }
+}
+
+impl ops::Sub for &Integer {
+    type Output = Integer;
+
+    fn sub(self, other: &Integer) -> Integer {
+        self + &(-other)
+    }
+}
+
+impl ops::Mul for &Integer {
+    type Output = Integer;
+
+    fn mul(self, other: &Integer) -> Integer {
+        Integer::with_sign(
+            self.negative != other.negative,
+            mul_digits(&self.digits, &other.digits),
+        )
+    }
+}
+
+// Removes any trailing zero digits
+fn trim(digits: &mut Vec<u32>) {
+    while digits.last() == Some(&0) {
+        digits.pop();
+    }
+}
+
+fn cmp_digits(a: &[u32], b: &[u32]) -> Ordering {
+    a.len()
+        .cmp(&b.len())
+        .then_with(|| a.iter().rev().cmp(b.iter().rev()))
+}
+
+fn add_digits(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let (long, short) = if a.len() >= b.len() { (a, b) } else { (b, a) };
+    let mut result = Vec::with_capacity(long.len() + 1);
+    let mut carry = 0u64;
+
+    for (i, digit) in long.iter().enumerate() {
+        let sum = *digit as u64 + *short.get(i).unwrap_or(&0) as u64 + carry;
+        result.push(sum as u32);
+        carry = sum >> 32;
+    }
+
+    if carry > 0 {
+        result.push(carry as u32);
+    }
+
+    result
+}
+
+// Subtracts b from a, where a >= b
+fn sub_digits(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut result = Vec::with_capacity(a.len());
+    let mut borrow = false;
+
+    for (i, digit) in a.iter().enumerate() {
+        let (difference, borrow_a) = digit.overflowing_sub(*b.get(i).unwrap_or(&0));
+        let (difference, borrow_b) = difference.overflowing_sub(borrow as u32);
+        result.push(difference);
+        borrow = borrow_a || borrow_b;
+    }
+
+    debug_assert!(!borrow);
+    trim(&mut result);
+    result
+}
+
+fn mul_digits(a: &[u32], b: &[u32]) -> Vec<u32> {
+    if a.is_empty() || b.is_empty() {
+        return Vec::new();
+    }
+
+    let mut result = vec![0u32; a.len() + b.len()];
+
+    for (i, digit_a) in a.iter().enumerate() {
+        let mut carry = 0u64;
+        for (j, digit_b) in b.iter().enumerate() {
+            let product = *digit_a as u64 * *digit_b as u64 + result[i + j] as u64 + carry;
+            result[i + j] = product as u32;
+            carry = product >> 32;
+        }
+        result[i + b.len()] = carry as u32;
+    }
+
+    trim(&mut result);
+    result
+}
+
+// Multiplies the digits by a small factor and then adds a small value
+fn mul_add_small(digits: &mut Vec<u32>, factor: u32, add: u32) {
+    let mut carry = add as u64;
+
+    for digit in digits.iter_mut() {
+        let product = *digit as u64 * factor as u64 + carry;
+        *digit = product as u32;
+        carry = product >> 32;
+    }
+
+    if carry > 0 {
+        digits.push(carry as u32);
+    }
+}
+
+// Divides the digits in place by a small divisor, returning the remainder
+fn div_rem_small(digits: &mut Vec<u32>, divisor: u32) -> u32 {
+    let mut remainder = 0u64;
+
+    for digit in digits.iter_mut().rev() {
+        let value = (remainder << 32) | *digit as u64;
+        *digit = (value / divisor as u64) as u32;
+        remainder = value % divisor as u64;
+    }
+
+    trim(digits);
+    remainder as u32
+}
+
+// Divides a by b using binary long division, returning the quotient and remainder
+fn div_rem_digits(a: &[u32], b: &[u32]) -> (Vec<u32>, Vec<u32>) {
+    if cmp_digits(a, b) == Ordering::Less {
+        return (Vec::new(), a.to_vec());
+    }
+
+    if let [divisor] = b {
+        let mut quotient = a.to_vec();
+        let remainder = div_rem_small(&mut quotient, *divisor);
+        return (quotient, vec![remainder]);
+    }
+
+    let mut quotient = vec![0u32; a.len()];
+    let mut remainder = Vec::with_capacity(b.len() + 1);
+
+    for bit in (0..a.len() * 32).rev() {
+        // Shift the next bit of a into the remainder
+        mul_add_small(&mut remainder, 2, (a[bit / 32] >> (bit % 32)) & 1);
+
+        if cmp_digits(&remainder, b) != Ordering::Less {
+            remainder = sub_digits(&remainder, b);
+            quotient[bit / 32] |= 1 << (bit % 32);
+        }
+    }
+
+    trim(&mut quotient);
+    (quotient, remainder)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn int(s: &str) -> Integer {
+        Integer::from_str_radix(s, 10).unwrap()
+    }
+
+    #[test]
+    fn parse_and_display() {
+        for s in [
+            "0",
+            "1",
+            "-1",
+            "4294967296",
+            "-123456789012345678901234567890",
+        ] {
+            assert_eq!(int(s).to_string(), s);
+        }
+        assert_eq!(int("-0").to_string(), "0");
+        assert_eq!(
+            Integer::from_str_radix("ff", 16).unwrap(),
+            Integer::from(255)
+        );
+        assert_eq!(Integer::from(-255).to_str_radix(16), "-ff");
+        assert!(Integer::from_str_radix("", 10).is_none());
+        assert!(Integer::from_str_radix("12a", 10).is_none());
+    }
+
+    #[test]
+    fn i64_conversion() {
+        for n in [0, 1, -1, i64::MAX, i64::MIN] {
+            assert_eq!(Integer::from(n).to_i64(), Some(n));
+        }
+        assert_eq!(
+            (&Integer::from(i64::MAX) + &Integer::from(1)).to_i64(),
+            None
+        );
+    }
+
+    #[test]
+    fn arithmetic() {
+        let a = int("123456789012345678901234567890");
+        let b = int("-987654321098765432109876543210");
+
+        assert_eq!((&a + &b).to_string(), "-864197532086419753208641975320");
+        assert_eq!((&a - &b).to_string(), "1111111110111111111011111111100");
+        assert_eq!(
+            (&a * &b).to_string(),
+            "-121932631137021795226185032733622923332237463801111263526900"
+        );
+        assert_eq!(
+            Integer::from(2).pow(100).to_string(),
+            "1267650600228229401496703205376"
+        );
+    }
+
+    #[test]
+    fn division() {
+        let a = int("1267650600228229401496703205376");
+        let b = int("-4294967297");
+        let (q, r) = a.div_rem(&b).unwrap();
+        assert_eq!(&(&q * &b) + &r, a);
+        assert!(r.abs() < b.abs());
+        assert!(!r.is_negative());
+        assert!(q.is_negative());
+
+        let (q, r) = Integer::from(-7).div_rem(&Integer::from(2)).unwrap();
+        assert_eq!((q.to_i64(), r.to_i64()), (Some(-3), Some(-1)));
+
+        assert!(a.div_rem(&Integer::default()).is_none());
+    }
+}
@@ -0,0 +1,44 @@
+//! A Koto language module for working with arbitrary-precision integers
+
+mod bigint;
+mod integer;
+
+pub use bigint::BigInt;
+pub use integer::Integer;
+
+use bigint::{integer_from_value, parse_radix};
+use koto_runtime::{prelude::*, Result};
+
+pub fn make_module() -> KMap {
+    let mut result = KMap::default();
+
+    result.add_fn("new", |ctx| new(ctx.args()));
+
+    let mut meta = MetaMap::default();
+
+    meta.insert(MetaKey::Type, "bigint".into());
+    meta.add_fn(MetaKey::Call, |ctx| new(ctx.args()));
+
+    result.set_meta_map(Some(meta.into()));
+    result
+}
+
+fn new(args: &[KValue]) -> Result<KValue> {
+    use KValue::{Number, Str};
+
+    match args {
+        [Number(_)] => Ok(BigInt::from(integer_from_value(&args[0])?).into()),
+        [Str(s)] => from_str(s, 10),
+        [Str(s), Number(radix)] => from_str(s, parse_radix(radix)?),
+        unexpected => {
+            type_error_with_slice("a Number, or a String with optional radix", unexpected)
+        }
+    }
+}
+
+fn from_str(s: &str, radix: u32) -> Result<KValue> {
+    match Integer::from_str_radix(s.trim(), radix) {
+        Some(n) => Ok(BigInt::from(n).into()),
+        None => runtime_error!("failed to parse '{s}' as an integer"),
+    }
+}
@@ -0,0 +1,609 @@
+use koto_bigint::Integer;
+use koto_runtime::{derive::*, prelude::*, Result};
+use std::{
+    cmp::Ordering,
+    fmt,
+    hash::{Hash, Hasher},
+    ops,
+};
+
+/// The number of decimal places that are used for the results of division
+pub const DIVISION_SCALE: u32 = 28;
+
+/// The maximum number of decimal places that a decimal can have
+///
+/// The magnitude of exponents in parsed decimals is also limited to this value, which prevents
+/// arithmetic with extremely large or small values from exhausting time and memory.
+pub const MAX_SCALE: u32 = 10_000;
+
+macro_rules! decimal_arithmetic_op {
+    ($lhs:expr, $rhs:expr, $op:tt) => {{
+        let result: Decimal = &$lhs $op &$rhs;
+        Ok(result.into())
+    }};
+}
+
+macro_rules! decimal_compound_assign_op {
+    ($self:ident, $rhs:expr, $op:tt) => {{
+        *$self = &*$self $op &decimal_from_value($rhs)?;
+        Ok(())
+    }};
+}
+
+macro_rules! decimal_comparison_op {
+    ($self:ident, $rhs:expr, $($ordering:pat_param)|+) => {
+        Ok(matches!($self.compare($rhs)?, Some($($ordering)|+)))
+    };
+}
+
+/// The rounding modes that are supported by [Decimal::rounded]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum RoundingMode {
+    /// Round to the nearest value, with ties rounded to the nearest even digit
+    #[default]
+    HalfEven,
+    /// Round to the nearest value, with ties rounded away from zero
+    HalfUp,
+    /// Round to the nearest value, with ties rounded towards zero
+    HalfDown,
+    /// Round away from zero
+    Up,
+    /// Round towards zero
+    Down,
+    /// Round towards positive infinity
+    Ceil,
+    /// Round towards negative infinity
+    Floor,
+}
+
+impl RoundingMode {
+    /// Returns the rounding mode that matches the given name
+    pub fn from_name(name: &str) -> Option<Self> {
+        let result = match name {
+            "half_even" => Self::HalfEven,
+            "half_up" => Self::HalfUp,
+            "half_down" => Self::HalfDown,
+            "up" => Self::Up,
+            "down" => Self::Down,
+            "ceil" => Self::Ceil,
+            "floor" => Self::Floor,
+            _ => return None,
+        };
+        Some(result)
+    }
+}
+
+/// A decimal number with exact base-10 arithmetic
+///
+/// The value is stored as an arbitrary-precision integer mantissa, along with a scale that
+/// defines the number of digits following the decimal point.
+#[derive(Clone, Debug, Default, KotoCopy, KotoType)]
+pub struct Decimal {
+    mantissa: Integer,
+    scale: u32,
+}
+
+#[koto_impl(runtime = koto_runtime)]
+impl Decimal {
+    /// Makes a decimal with the value `mantissa * 10^-scale`
+    ///
+    /// None is returned if the scale is greater than [MAX_SCALE].
+    pub fn new(mantissa: Integer, scale: u32) -> Option<Self> {
+        (scale <= MAX_SCALE).then(|| Self::from_parts(mantissa, scale))
+    }
+
+    // Makes a decimal from a scale that's known to be within the limit
+    fn from_parts(mantissa: Integer, scale: u32) -> Self {
+        debug_assert!(scale <= MAX_SCALE);
+        Self { mantissa, scale }
+    }
+
+    /// Parses a decimal from a string, e.g. `-12.345` or `1.5e-3`
+    ///
+    /// The number of digits after the decimal point is preserved in the result's scale.
+    ///
+    /// None is returned if the string isn't a valid decimal, or if the exponent or the resulting
+    /// scale has a magnitude greater than [MAX_SCALE].
+    pub fn parse(s: &str) -> Option<Self> {
+        let (number, exponent) = match s.find(['e', 'E']) {
+            Some(index) => (&s[..index], s[index + 1..].parse::<i32>().ok()?),
+            None => (s, 0),
+        };
+
+        let (sign, number) = match number.as_bytes().first() {
+            Some(b'-') => ("-", &number[1..]),
+            Some(b'+') => ("", &number[1..]),
+            _ => ("", number),
+        };
+
+        let (integer, fraction) = number.split_once('.').unwrap_or((number, ""));
+        if integer.is_empty() && fraction.is_empty()
+            || !integer
+                .bytes()
+                .chain(fraction.bytes())
+                .all(|c| c.is_ascii_digit())
+        {
+            return None;
+        }
+
+        let max_scale = i64::from(MAX_SCALE);
+        let scale = i64::try_from(fraction.len()).ok()? - exponent as i64;
+        if i64::from(exponent).abs() > max_scale || scale.abs() > max_scale {
+            return None;
+        }
+
+        let mantissa = Integer::from_str_radix(&format!("{sign}{integer}{fraction}"), 10)?;
+
+        if scale < 0 {
+            let factor = power_of_ten(u32::try_from(-scale).ok()?);
+            Self::new(&mantissa * &factor, 0)
+        } else {
+            Self::new(mantissa, u32::try_from(scale).ok()?)
+        }
+    }
+
+    /// Makes a decimal from a number, returning None if the number isn't finite
+    ///
+    /// Floats are converted using their shortest decimal representation, e.g. `0.1` is converted
+    /// to exactly `0.1`.
+    pub fn from_number(n: KNumber) -> Option<Self> {
+        match n {
+            KNumber::I64(n) => Some(Self::from_parts(n.into(), 0)),
+            KNumber::F64(n) if n.is_finite() => Self::parse(&n.to_string()),
+            KNumber::F64(_) => None,
+        }
+    }
+
+    /// Returns the decimal as a number
+    ///
+    /// Integers that fit in an i64 are returned as integers, otherwise the closest float is
+    /// returned.
+    pub fn to_knumber(&self) -> KNumber {
+        let normalized = self.normalized();
+        match normalized.mantissa.to_i64() {
+            Some(n) if normalized.scale == 0 => n.into(),
+            _ => self.to_f64().into(),
+        }
+    }
+
+    /// Returns the closest f64 value to the decimal
+    pub fn to_f64(&self) -> f64 {
+        self.to_string().parse().unwrap_or(f64::NAN)
+    }
+
+    /// Returns true if the decimal is less than zero
+    pub fn is_negative(&self) -> bool {
+        self.mantissa.is_negative()
+    }
+
+    /// Returns the decimal rounded to the given number of decimal places
+    ///
+    /// If the decimal has fewer decimal places than requested then it's extended with zeros.
+    /// The number of places is limited to [MAX_SCALE].
+    #[must_use]
+    pub fn rounded(&self, places: u32, mode: RoundingMode) -> Self {
+        let places = places.min(MAX_SCALE);
+        if places >= self.scale {
+            return Self::from_parts(self.rescaled(places), places);
+        }
+
+        let divisor = power_of_ten(self.scale - places);
+        let (quotient, remainder) = self.mantissa.div_rem(&divisor).unwrap();
+        let negative = self.is_negative();
+        Self::from_parts(
+            round_quotient(quotient, &remainder, &divisor, negative, mode),
+            places,
+        )
+    }
+
+    /// Divides the decimal by another, returning None if the divisor is zero
+    ///
+    /// Results that can't be represented exactly are rounded to [DIVISION_SCALE] decimal places,
+    /// and trailing zeros are removed from the result.
+    pub fn checked_div(&self, other: &Self) -> Option<Self> {
+        let scale = DIVISION_SCALE.max(self.scale).max(other.scale);
+        // Scale the dividend so that the quotient has the target scale
+        let dividend = &self.mantissa * &power_of_ten(scale + other.scale - self.scale);
+        let (quotient, remainder) = dividend.div_rem(&other.mantissa)?;
+        let negative = self.is_negative() != other.is_negative();
+        let quotient = round_quotient(
+            quotient,
+            &remainder,
+            &other.mantissa,
+            negative,
+            RoundingMode::HalfEven,
+        );
+        Some(Self::from_parts(quotient, scale).normalized())
+    }
+
+    /// Returns the remainder of dividing by another decimal, or None if the divisor is zero
+    ///
+    /// The remainder has the same sign as `self`.
+    pub fn checked_rem(&self, other: &Self) -> Option<Self> {
+        let scale = self.scale.max(other.scale);
+        let (_, remainder) = self.rescaled(scale).div_rem(&other.rescaled(scale))?;
+        Some(Self::from_parts(remainder, scale))
+    }
+
+    #[koto_method]
+    fn abs(&self) -> KValue {
+        Self::from_parts(self.mantissa.abs(), self.scale).into()
+    }
+
+    #[koto_method]
+    fn ceil(&self) -> KValue {
+        self.rounded(0, RoundingMode::Ceil).into()
+    }
+
+    #[koto_method]
+    fn floor(&self) -> KValue {
+        self.rounded(0, RoundingMode::Floor).into()
+    }
+
+    #[koto_method]
+    fn round(&self, args: &[KValue]) -> Result<KValue> {
+        use KValue::{Number, Str};
+
+        let (places, mode) = match args {
+            [] => (0, RoundingMode::default()),
+            [Number(places)] => (decimal_places(places)?, RoundingMode::default()),
+            [Number(places), Str(mode)] => (decimal_places(places)?, rounding_mode(mode)?),
+            unexpected => {
+                return type_error_with_slice(
+                    "an optional number of decimal places, and an optional rounding mode",
+                    unexpected,
+                )
+            }
+        };
+
+        Ok(self.rounded(places, mode).into())
+    }
+
+    #[koto_method]
+    fn scale(&self) -> KValue {
+        self.scale.into()
+    }
+
+    #[koto_method]
+    fn to_number(&self) -> KValue {
+        self.to_knumber().into()
+    }
+
+    // Compares the decimal with another Decimal or a Number
+    //
+    // None is returned when comparing against NaN.
+    fn compare(&self, rhs: &KValue) -> Result<Option<Ordering>> {
+        match rhs {
+            KValue::Number(KNumber::F64(n)) if !n.is_finite() => Ok(self.to_f64().partial_cmp(n)),
+            _ => Ok(Some(self.cmp(&decimal_from_value(rhs)?))),
+        }
+    }
+
+    // Returns the decimal's mantissa, adjusted to the given scale
+    //
+    // The scale must be greater than or equal to the decimal's scale.
+    fn rescaled(&self, scale: u32) -> Integer {
+        debug_assert!(scale >= self.scale);
+        if scale == self.scale {
+            self.mantissa.clone()
+        } else {
+            &self.mantissa * &power_of_ten(scale - self.scale)
+        }
+    }
+
+    // Returns the decimal with trailing zeros removed from its fractional part
+    fn normalized(&self) -> Self {
+        let ten = Integer::from(10);
+        let mut result = self.clone();
+
+        while result.scale > 0 {
+            match result.mantissa.div_rem(&ten) {
+                Some((quotient, remainder)) if remainder.is_zero() => {
+                    result.mantissa = quotient;
+                    result.scale -= 1;
+                }
+                _ => break,
+            }
+        }
+
+        result
+    }
+}
+
+impl KotoObject for Decimal {
+    fn display(&self, ctx: &mut DisplayContext) -> Result<()> {
+        ctx.append(self.to_string());
+        Ok(())
+    }
+
+    fn display_with_precision(&self, precision: u32) -> Result<Option<String>> {
+        if precision > MAX_SCALE {
+            return runtime_error!(
+                "too many decimal places ({precision}), the maximum is {MAX_SCALE}"
+            );
+        }
+        Ok(Some(
+            self.rounded(precision, RoundingMode::default()).to_string(),
+        ))
+    }
+
+    fn negate(&self, _vm: &mut KotoVm) -> Result<KValue> {
+        Ok((-self).into())
+    }
+
+    fn add(&self, rhs: &KValue) -> Result<KValue> {
+        decimal_arithmetic_op!(*self, decimal_from_value(rhs)?, +)
+    }
+
+    fn subtract(&self, rhs: &KValue) -> Result<KValue> {
+        decimal_arithmetic_op!(*self, decimal_from_value(rhs)?, -)
+    }
+
+    fn multiply(&self, rhs: &KValue) -> Result<KValue> {
+        decimal_arithmetic_op!(*self, decimal_from_value(rhs)?, *)
+    }
+
+    fn divide(&self, rhs: &KValue) -> Result<KValue> {
+        divide(self, &decimal_from_value(rhs)?).map(KValue::from)
+    }
+
+    fn remainder(&self, rhs: &KValue) -> Result<KValue> {
+        remainder(self, &decimal_from_value(rhs)?).map(KValue::from)
+    }
+
+    fn add_assign(&mut self, rhs: &KValue) -> Result<()> {
+        decimal_compound_assign_op!(self, rhs, +)
+    }
+
+    fn subtract_assign(&mut self, rhs: &KValue) -> Result<()> {
+        decimal_compound_assign_op!(self, rhs, -)
+    }
+
+    fn multiply_assign(&mut self, rhs: &KValue) -> Result<()> {
+        decimal_compound_assign_op!(self, rhs, *)
+    }
+
+    fn divide_assign(&mut self, rhs: &KValue) -> Result<()> {
+        *self = divide(self, &decimal_from_value(rhs)?)?;
+        Ok(())
+    }
+
+    fn remainder_assign(&mut self, rhs: &KValue) -> Result<()> {
+        *self = remainder(self, &decimal_from_value(rhs)?)?;
+        Ok(())
+    }
+
+    fn add_rhs(&self, lhs: &KValue) -> Result<KValue> {
+        decimal_arithmetic_op!(decimal_from_value(lhs)?, *self, +)
+    }
+
+    fn subtract_rhs(&self, lhs: &KValue) -> Result<KValue> {
+        decimal_arithmetic_op!(decimal_from_value(lhs)?, *self, -)
+    }
+
+    fn multiply_rhs(&self, lhs: &KValue) -> Result<KValue> {
+        decimal_arithmetic_op!(decimal_from_value(lhs)?, *self, *)
+    }
+
+    fn divide_rhs(&self, lhs: &KValue) -> Result<KValue> {
+        divide(&decimal_from_value(lhs)?, self).map(KValue::from)
+    }
+
+    fn remainder_rhs(&self, lhs: &KValue) -> Result<KValue> {
+        remainder(&decimal_from_value(lhs)?, self).map(KValue::from)
+    }
+
+    fn less(&self, rhs: &KValue) -> Result<bool> {
+        decimal_comparison_op!(self, rhs, Ordering::Less)
+    }
+
+    fn less_or_equal(&self, rhs: &KValue) -> Result<bool> {
+        decimal_comparison_op!(self, rhs, Ordering::Less | Ordering::Equal)
+    }
+
+    fn greater(&self, rhs: &KValue) -> Result<bool> {
+        decimal_comparison_op!(self, rhs, Ordering::Greater)
+    }
+
+    fn greater_or_equal(&self, rhs: &KValue) -> Result<bool> {
+        decimal_comparison_op!(self, rhs, Ordering::Greater | Ordering::Equal)
+    }
+
+    fn equal(&self, rhs: &KValue) -> Result<bool> {
+        decimal_comparison_op!(self, rhs, Ordering::Equal)
+    }
+
+    fn not_equal(&self, rhs: &KValue) -> Result<bool> {
+        self.equal(rhs).map(|result| !result)
+    }
+
+    fn hash(&self) -> Option<u64> {
+        let mut hasher = KotoHasher::default();
+        Hash::hash(self, &mut hasher);
+        Some(hasher.finish())
+    }
+}
+
+impl ops::Neg for &Decimal {
+    type Output = Decimal;
+
+    fn neg(self) -> Decimal {
+        Decimal::from_parts(-&self.mantissa, self.scale)
+    }
+}
+
+impl ops::Add for &Decimal {
+    type Output = Decimal;
+
+    fn add(self, other: &Decimal) -> Decimal {
+        let scale = self.scale.max(other.scale);
+        Decimal::from_parts(&self.rescaled(scale) + &other.rescaled(scale), scale)
+    }
+}
+
+impl ops::Sub for &Decimal {
+    type Output = Decimal;
+
+    fn sub(self, other: &Decimal) -> Decimal {
+        let scale = self.scale.max(other.scale);
+        Decimal::from_parts(&self.rescaled(scale) - &other.rescaled(scale), scale)
+    }
+}
+
+impl ops::Mul for &Decimal {
+    type Output = Decimal;
+
+    // Products with more than MAX_SCALE decimal places are rounded to MAX_SCALE places
+    fn mul(self, other: &Decimal) -> Decimal {
+        let product = Decimal {
+            mantissa: &self.mantissa * &other.mantissa,
+            scale: self.scale + other.scale,
+        };
+        if product.scale > MAX_SCALE {
+            product.rounded(MAX_SCALE, RoundingMode::HalfEven)
+        } else {
+            product
+        }
+    }
+}
+
+impl Ord for Decimal {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let scale = self.scale.max(other.scale);
+        self.rescaled(scale).cmp(&other.rescaled(scale))
+    }
+}
+
+impl PartialOrd for Decimal {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Decimal {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Decimal {}
+
+impl Hash for Decimal {
+    // Decimals that are equal but have different scales should have matching hashes
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let normalized = self.normalized();
+        normalized.mantissa.hash(state);
+        normalized.scale.hash(state);
+    }
+}
+
+impl From<Decimal> for KValue {
+    fn from(n: Decimal) -> Self {
+        KObject::from(n).into()
+    }
+}
+
+impl fmt::Display for Decimal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_negative() {
+            f.write_str("-")?;
+        }
+
+        let digits = self.mantissa.abs().to_string();
+        let scale = self.scale as usize;
+
+        if scale == 0 {
+            f.write_str(&digits)
+        } else if digits.len() > scale {
+            let (integer, fraction) = digits.split_at(digits.len() - scale);
+            write!(f, "{integer}.{fraction}")
+        } else {
+            write!(f, "0.{digits:0>scale$}")
+        }
+    }
+}
+
+/// Converts a Decimal or a finite Number into a [Decimal]
+pub fn decimal_from_value(value: &KValue) -> Result<Decimal> {
+    match value {
+        KValue::Number(n) => match Decimal::from_number(*n) {
+            Some(result) => Ok(result),
+            None => runtime_error!("expected a finite number, found '{n}'"),
+        },
+        KValue::Object(o) if o.is_a::<Decimal>() => Ok(o.cast::<Decimal>()?.clone()),
+        unexpected => type_error("a Decimal or Number", unexpected),
+    }
+}
+
+fn decimal_places(n: &KNumber) -> Result<u32> {
+    match n {
+        KNumber::I64(n) if *n >= 0 => match u32::try_from(*n) {
+            Ok(places) if places <= MAX_SCALE => Ok(places),
+            _ => runtime_error!("too many decimal places ({n}), the maximum is {MAX_SCALE}"),
+        },
+        _ => runtime_error!("expected a non-negative integer, found '{n}'"),
+    }
+}
+
+fn rounding_mode(name: &str) -> Result<RoundingMode> {
+    match RoundingMode::from_name(name) {
+        Some(mode) => Ok(mode),
+        None => runtime_error!("unknown rounding mode '{name}'"),
+    }
+}
+
+fn divide(lhs: &Decimal, rhs: &Decimal) -> Result<Decimal> {
+    match lhs.checked_div(rhs) {
+        Some(result) => Ok(result),
+        None => runtime_error!("division by zero"),
+    }
+}
+
+fn remainder(lhs: &Decimal, rhs: &Decimal) -> Result<Decimal> {
+    match lhs.checked_rem(rhs) {
+        Some(result) => Ok(result),
+        None => runtime_error!("division by zero"),
+    }
+}
+
+fn power_of_ten(exponent: u32) -> Integer {
+    Integer::from(10).pow(exponent)
+}
+
+// Applies a rounding mode to the result of a truncating division
+//
+// `negative` indicates whether or not the exact result of the division is negative.
+fn round_quotient(
+    quotient: Integer,
+    remainder: &Integer,
+    divisor: &Integer,
+    negative: bool,
+    mode: RoundingMode,
+) -> Integer {
+    if remainder.is_zero() {
+        return quotient;
+    }
+
+    let half_comparison = (&remainder.abs() * &Integer::from(2)).cmp(&divisor.abs());
+    let round_away_from_zero = match mode {
+        RoundingMode::HalfEven => match half_comparison {
+            Ordering::Greater => true,
+            Ordering::Equal => quotient
+                .div_rem(&Integer::from(2))
+                .is_some_and(|(_, remainder)| !remainder.is_zero()),
+            Ordering::Less => false,
+        },
+        RoundingMode::HalfUp => half_comparison != Ordering::Less,
+        RoundingMode::HalfDown => half_comparison == Ordering::Greater,
+        RoundingMode::Up => true,
+        RoundingMode::Down => false,
+        RoundingMode::Ceil => !negative,
+        RoundingMode::Floor => negative,
+    };
+
+    if round_away_from_zero {
+        &quotient + &Integer::from(if negative { -1 } else { 1 })
+    } else {
+        quotient
+    }
+}
@@ -0,0 +1,37 @@
+//! A Koto language module for exact decimal arithmetic
+
+mod decimal;
+
+pub use decimal::{Decimal, RoundingMode, DIVISION_SCALE, MAX_SCALE};
+
+use koto_runtime::{prelude::*, Result};
+
+pub fn make_module() -> KMap {
+    let mut result = KMap::default();
+
+    result.add_fn("new", |ctx| new(ctx.args()));
+
+    let mut meta = MetaMap::default();
+
+    meta.insert(MetaKey::Type, "decimal".into());
+    meta.add_fn(MetaKey::Call, |ctx| new(ctx.args()));
+
+    result.set_meta_map(Some(meta.into()));
+    result
+}
+
+fn new(args: &[KValue]) -> Result<KValue> {
+    use KValue::{Number, Str};
+
+    match args {
+        [Number(n)] => match Decimal::from_number(*n) {
+            Some(result) => Ok(result.into()),
+            None => runtime_error!("expected a finite number, found '{n}'"),
+        },
+        [Str(s)] => match Decimal::parse(s.trim()) {
+            Some(result) => Ok(result.into()),
+            None => runtime_error!("failed to parse '{s}' as a decimal"),
+        },
+        unexpected => type_error_with_slice("a Number or String", unexpected),
+    }
+}
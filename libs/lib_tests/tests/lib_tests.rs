@@ -12,7 +12,9 @@ fn run_script(script: &str, path: &Path, should_fail_at_runtime: bool) {
     koto.set_script_path(Some(path)).unwrap();
 
     let prelude = koto.prelude();
+    prelude.insert("bigint", koto_bigint::make_module());
     prelude.insert("color", koto_color::make_module());
+    prelude.insert("decimal", koto_decimal::make_module());
     prelude.insert("geometry", koto_geometry::make_module());
     prelude.insert("json", koto_json::make_module());
     prelude.insert("random", koto_random::make_module());
@@ -66,7 +68,9 @@ macro_rules! lib_test {
 mod lib_tests {
     use super::*;
 
+    lib_test!(bigint);
     lib_test!(color);
+    lib_test!(decimal);
     lib_test!(geometry);
     lib_test!(json);
     lib_test!(random);
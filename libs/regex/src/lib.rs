@@ -89,13 +89,13 @@ impl Regex {
 
                                 if let Some(name) = name {
                                     // Also insert the match with the capture group's name
-                                    result.insert(name.into(), match_);
+                                    result.insert(name.into(), match_)?;
                                 } else {
                                     // Insert the match with the capture group's index
-                                    result.insert(i.into(), match_);
+                                    result.insert(i.into(), match_)?;
                                 }
                             } else {
-                                result.insert(i.into(), KValue::Null);
+                                result.insert(i.into(), KValue::Null)?;
                             }
                         }
 